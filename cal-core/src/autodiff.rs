@@ -0,0 +1,279 @@
+// autodiff.rs
+use crate::utils;
+use crate::special;
+use crate::error::CalError;
+
+use std::f64::consts;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use meval::{ContextProvider, Expr};
+use meval::tokenizer::{Operation, Token};
+
+
+
+// Duplication struct
+
+/*
+ * ---- Forward mode automatic differentiation ----
+ *
+ *  Dual number a + b e (e^2 = 0): f(a + e) = f(a) + f'(a) e
+ *  The RPN of meval is evaluated over Dual instead of f64, seeded with x = x + 1e,
+ *  so f(x) and f'(x) are exact to rounding in a single pass
+ *
+ *  Constants and extra functions are taken from utils context,
+ *  user-defined functions (utils::register_function) are not supported
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Dual {
+    pub(crate) value: f64,
+    pub(crate) slope: f64
+}
+
+
+
+// Add implement method
+
+impl Dual {
+    pub(crate) fn constant(value: f64) -> Self {
+        Dual { value, slope: 0.0 }
+    }
+
+    pub(crate) fn variable(value: f64) -> Self {
+        Dual { value, slope: 1.0 }
+    }
+
+    // f(u) with f'(u) => (f(u), f'(u) * u')
+    fn chain(self, value: f64, derivative: f64) -> Self {
+        Dual { value, slope: derivative * self.slope }
+    }
+
+    fn powd(self, exponent: Dual) -> Self {
+        let value: f64 = self.value.powf(exponent.value);
+
+        // constant exponent avoids ln(u) which is NaN for negative base
+        if exponent.slope == 0.0 {
+            return self.chain(value, exponent.value * self.value.powf(exponent.value - 1.0));
+        }
+
+        Dual {
+            value,
+            slope: value * (exponent.slope * self.value.ln() + exponent.value * self.slope / self.value)
+        }
+    }
+}
+
+impl Add for Dual {
+    type Output = Dual;
+    fn add(self, other: Dual) -> Dual {
+        Dual { value: self.value + other.value, slope: self.slope + other.slope }
+    }
+}
+
+impl Sub for Dual {
+    type Output = Dual;
+    fn sub(self, other: Dual) -> Dual {
+        Dual { value: self.value - other.value, slope: self.slope - other.slope }
+    }
+}
+
+impl Mul for Dual {
+    type Output = Dual;
+    fn mul(self, other: Dual) -> Dual {
+        Dual {
+            value: self.value * other.value,
+            slope: self.slope * other.value + self.value * other.slope
+        }
+    }
+}
+
+impl Div for Dual {
+    type Output = Dual;
+    fn div(self, other: Dual) -> Dual {
+        Dual {
+            value: self.value / other.value,
+            slope: (self.slope * other.value - self.value * other.slope) / (other.value * other.value)
+        }
+    }
+}
+
+impl Neg for Dual {
+    type Output = Dual;
+    fn neg(self) -> Dual {
+        Dual { value: -self.value, slope: -self.slope }
+    }
+}
+
+// false if any function in expression has no dual rule (then finite difference is needed)
+pub(crate) fn is_supported(expr: &Expr) -> bool {
+    expr.iter().all(|token| match token {
+        Token::Func(name, Some(1)) => derivative_factor(name, 1.0).is_some(),
+        Token::Func(name, Some(2)) => matches!(name.as_str(), "log" | "hypot" | "atan2" | "mod"),
+        Token::Func(..)            => false,
+        _                          => true,
+    })
+}
+
+// (f(x), f'(x)) where x is bound as "x"
+pub(crate) fn evaluate_dual(expr: &Expr, x: f64) -> Result<Dual, CalError> {
+    let mut stack: Vec<Dual> = Vec::new();
+
+    for token in expr.iter() {
+        let dual: Dual = match token {
+            Token::Number(value) => Dual::constant(*value),
+            Token::Var(name) if name == "x" => Dual::variable(x),
+            Token::Var(name)     => match utils::with_context(|ctx| ctx.get_var(name)) {
+                Some(value) => Dual::constant(value),
+                None        => return Err(CalError::EvaluationFailed { x, reason: format!("Unknown variable: {}", name) }),
+            },
+            Token::Unary(Operation::Minus) => -pop(&mut stack, x)?,
+            Token::Unary(_)      => continue,
+            Token::Binary(op)    => {
+                let right : Dual = pop(&mut stack, x)?;
+                let left  : Dual = pop(&mut stack, x)?;
+
+                match op {
+                    Operation::Plus  => left + right,
+                    Operation::Minus => left - right,
+                    Operation::Times => left * right,
+                    Operation::Div   => left / right,
+                    // u % v = u - v * trunc(u / v)
+                    Operation::Rem   => Dual {
+                        value: left.value % right.value,
+                        slope: left.slope - right.slope * (left.value / right.value).trunc()
+                    },
+                    Operation::Pow   => left.powd(right),
+                }
+            },
+            Token::Func(name, Some(1)) => {
+                let u     : Dual = pop(&mut stack, x)?;
+                let value : f64  = match utils::with_context(|ctx| ctx.eval_func(name, &[u.value])) {
+                    Ok(value) => value,
+                    Err(e)    => return Err(CalError::EvaluationFailed { x, reason: format!("{}: {}", name, e) }),
+                };
+
+                match derivative_factor(name, u.value) {
+                    Some(factor) => u.chain(value, factor),
+                    None         => return Err(unsupported(name)),
+                }
+            },
+            Token::Func(name, Some(2)) => {
+                let v : Dual = pop(&mut stack, x)?;
+                let u : Dual = pop(&mut stack, x)?;
+
+                binary_func(name, u, v)?
+            },
+            Token::Func(name, _) => return Err(unsupported(name)),
+            _ => return Err(CalError::EvaluationFailed { x, reason: "Malformed expression".to_string() }),
+        };
+
+        stack.push(dual);
+    }
+
+    let result: Dual = match (stack.pop(), stack.is_empty()) {
+        (Some(result), true) => result,
+        _ => return Err(CalError::EvaluationFailed { x, reason: "Malformed expression".to_string() }),
+    };
+
+    if !result.value.is_finite() {
+        return Err(CalError::NonFiniteValue { x, value: result.value });
+    }
+    if !result.slope.is_finite() {
+        return Err(CalError::NonFiniteValue { x, value: result.slope });
+    }
+
+    Ok(result)
+}
+
+
+
+// Calculations
+
+fn unsupported(name: &str) -> CalError {
+    CalError::invalid_argument(name, "automatic derivative is not supported")
+}
+
+fn pop(stack: &mut Vec<Dual>, x: f64) -> Result<Dual, CalError> {
+    stack.pop().ok_or(CalError::EvaluationFailed { x, reason: "Malformed expression".to_string() })
+}
+
+fn binary_func(name: &str, u: Dual, v: Dual) -> Result<Dual, CalError> {
+    let result: Dual = match name {
+        // log(b, x) = ln(x) / ln(b)
+        "log"   => {
+            let ln_u: Dual = u.chain(u.value.ln(), 1.0 / u.value);
+            let ln_v: Dual = v.chain(v.value.ln(), 1.0 / v.value);
+            ln_v / ln_u
+        },
+        "hypot" => {
+            let value: f64 = u.value.hypot(v.value);
+            Dual { value, slope: (u.value * u.slope + v.value * v.slope) / value }
+        },
+        "atan2" => Dual {
+            value: u.value.atan2(v.value),
+            slope: (v.value * u.slope - u.value * v.slope) / (u.value * u.value + v.value * v.value)
+        },
+        "mod"   => {
+            let floor: f64 = (u.value / v.value).floor();
+            Dual { value: u.value - v.value * floor, slope: u.slope - v.slope * floor }
+        },
+        _ => return Err(unsupported(name)),
+    };
+
+    Ok(result)
+}
+
+// f'(u) for single argument function in utils context
+fn derivative_factor(name: &str, u: f64) -> Option<f64> {
+    let factor: f64 = match name {
+        "sin"   => u.cos(),
+        "cos"   => -u.sin(),
+        "tan"   => 1.0 / (u.cos() * u.cos()),
+        "sec"   => u.tan() / u.cos(),
+        "csc"   => -1.0 / (u.sin() * u.tan()),
+        "cot"   => -1.0 / (u.sin() * u.sin()),
+
+        "asin"  => 1.0 / (1.0 - u * u).sqrt(),
+        "acos"  => -1.0 / (1.0 - u * u).sqrt(),
+        "atan"  => 1.0 / (1.0 + u * u),
+        "asec"  => 1.0 / (u.abs() * (u * u - 1.0).sqrt()),
+        "acsc"  => -1.0 / (u.abs() * (u * u - 1.0).sqrt()),
+        "acot"  => -1.0 / (1.0 + u * u),
+
+        "sinh"  => u.cosh(),
+        "cosh"  => u.sinh(),
+        "tanh"  => 1.0 / (u.cosh() * u.cosh()),
+        "sech"  => -u.tanh() / u.cosh(),
+        "csch"  => -1.0 / (u.sinh() * u.tanh()),
+        "coth"  => -1.0 / (u.sinh() * u.sinh()),
+
+        "asinh" => 1.0 / (u * u + 1.0).sqrt(),
+        "acosh" => 1.0 / (u * u - 1.0).sqrt(),
+        "atanh" => 1.0 / (1.0 - u * u),
+
+        "exp"   => u.exp(),
+        "ln"    => 1.0 / u,
+        "log10" => 1.0 / (u * consts::LN_10),
+        "log2"  => 1.0 / (u * consts::LN_2),
+        "sqrt"  => 0.5 / u.sqrt(),
+        "cbrt"  => 1.0 / (3.0 * u.cbrt() * u.cbrt()),
+        "abs"   => u.signum(),
+
+        "erf"   => 2.0 / consts::PI.sqrt() * (-u * u).exp(),
+        "erfc"  => -2.0 / consts::PI.sqrt() * (-u * u).exp(),
+
+        "j0"    => -special::bessel_j1(u),
+        "j1"    => special::bessel_j0(u) - special::bessel_j1(u) / u,
+        "y0"    => -special::bessel_y1(u),
+        "y1"    => special::bessel_y0(u) - special::bessel_y1(u) / u,
+
+        // limit at 0 is 0
+        "sinc"  => if u == 0.0 { 0.0 } else { (u * u.cos() - u.sin()) / (u * u) },
+
+        // piecewise constant, derivative is 0 almost everywhere
+        "floor" | "ceil" | "round" | "signum" | "heaviside" | "step" => 0.0,
+
+        _ => return None,
+    };
+
+    Some(factor)
+}
@@ -0,0 +1,247 @@
+// complex.rs
+use crate::utils;
+use crate::error::CalError;
+
+use serde::Serialize;
+
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use meval::{ContextProvider, Expr};
+use meval::tokenizer::{Operation, Token};
+
+
+
+// Duplication struct
+
+/*
+ * ---- Complex number for polynomial roots and Muller ----
+ *
+ *  Serialized as { re, im } so JS can print "re + im i" itself
+ *  Expressions are evaluated over Complex the same way autodiff walks the RPN,
+ *  a function without complex rule is only allowed on the real axis
+ */
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub struct Complex {
+    pub re: f64,
+    pub im: f64
+}
+
+
+
+// Add implement method
+
+impl Complex {
+    pub(crate) fn new(re: f64, im: f64) -> Self {
+        Complex { re, im }
+    }
+
+    pub(crate) fn real(re: f64) -> Self {
+        Complex { re, im: 0.0 }
+    }
+
+    // re^iθ
+    pub(crate) fn from_polar(r: f64, theta: f64) -> Self {
+        Complex { re: r * theta.cos(), im: r * theta.sin() }
+    }
+
+    pub(crate) fn abs(self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    pub(crate) fn is_finite(self) -> bool {
+        self.re.is_finite() && self.im.is_finite()
+    }
+
+    pub(crate) fn arg(self) -> f64 {
+        self.im.atan2(self.re)
+    }
+
+    // principal root, re >= 0
+    pub(crate) fn sqrt(self) -> Self {
+        Complex::from_polar(self.abs().sqrt(), self.arg() / 2.0)
+    }
+
+    pub(crate) fn exp(self) -> Self {
+        Complex::from_polar(self.re.exp(), self.im)
+    }
+
+    // principal branch, im in (-pi, pi]
+    pub(crate) fn ln(self) -> Self {
+        Complex { re: self.abs().ln(), im: self.arg() }
+    }
+
+    pub(crate) fn sin(self) -> Self {
+        Complex { re: self.re.sin() * self.im.cosh(), im: self.re.cos() * self.im.sinh() }
+    }
+
+    pub(crate) fn cos(self) -> Self {
+        Complex { re: self.re.cos() * self.im.cosh(), im: -self.re.sin() * self.im.sinh() }
+    }
+
+    pub(crate) fn sinh(self) -> Self {
+        Complex { re: self.re.sinh() * self.im.cos(), im: self.re.cosh() * self.im.sin() }
+    }
+
+    pub(crate) fn cosh(self) -> Self {
+        Complex { re: self.re.cosh() * self.im.cos(), im: self.re.sinh() * self.im.sin() }
+    }
+
+    // integer power keeps real input real, otherwise exp(w ln z)
+    fn pow(self, exponent: Complex) -> Self {
+        if exponent.im == 0.0 && exponent.re.fract() == 0.0 && exponent.re.abs() <= i32::MAX as f64 {
+            let n         : i32     = exponent.re as i32;
+            let mut k     : u32     = n.unsigned_abs();
+            let mut base  : Complex = self;
+            let mut power : Complex = Complex::real(1.0);
+
+            // square and multiply
+            while k > 0 {
+                if k & 1 == 1 {
+                    power = power * base;
+                }
+                base = base * base;
+                k >>= 1;
+            }
+
+            return if n < 0 { Complex::real(1.0) / power } else { power };
+        }
+        if self.im == 0.0 && exponent.im == 0.0 && self.re >= 0.0 {
+            return Complex::real(self.re.powf(exponent.re));
+        }
+        if self == Complex::real(0.0) {
+            return Complex::real(0.0);
+        }
+
+        (exponent * self.ln()).exp()
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+    fn add(self, other: Complex) -> Complex {
+        Complex { re: self.re + other.re, im: self.im + other.im }
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+    fn sub(self, other: Complex) -> Complex {
+        Complex { re: self.re - other.re, im: self.im - other.im }
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+    fn mul(self, other: Complex) -> Complex {
+        Complex {
+            re: self.re * other.re - self.im * other.im,
+            im: self.re * other.im + self.im * other.re
+        }
+    }
+}
+
+impl Div for Complex {
+    type Output = Complex;
+    fn div(self, other: Complex) -> Complex {
+        let denom: f64 = other.re * other.re + other.im * other.im;
+        Complex {
+            re: (self.re * other.re + self.im * other.im) / denom,
+            im: (self.im * other.re - self.re * other.im) / denom
+        }
+    }
+}
+
+impl Neg for Complex {
+    type Output = Complex;
+    fn neg(self) -> Complex {
+        Complex { re: -self.re, im: -self.im }
+    }
+}
+
+// f(z) where z is bound as "x"
+pub(crate) fn evaluate_complex(expr: &Expr, z: Complex) -> Result<Complex, CalError> {
+    let mut stack: Vec<Complex> = Vec::new();
+
+    for token in expr.iter() {
+        let value: Complex = match token {
+            Token::Number(value) => Complex::real(*value),
+            Token::Var(name) if name == "x" => z,
+            Token::Var(name)     => match utils::with_context(|ctx| ctx.get_var(name)) {
+                Some(value) => Complex::real(value),
+                None        => return Err(CalError::EvaluationFailed { x: z.re, reason: format!("Unknown variable: {}", name) }),
+            },
+            Token::Unary(Operation::Minus) => -pop(&mut stack, z)?,
+            Token::Unary(_)      => continue,
+            Token::Binary(op)    => {
+                let right : Complex = pop(&mut stack, z)?;
+                let left  : Complex = pop(&mut stack, z)?;
+
+                match op {
+                    Operation::Plus  => left + right,
+                    Operation::Minus => left - right,
+                    Operation::Times => left * right,
+                    Operation::Div   => left / right,
+                    Operation::Pow   => left.pow(right),
+                    Operation::Rem   => real_only(z, "%", &[left, right])?,
+                }
+            },
+            Token::Func(name, Some(n)) => {
+                let args: Vec<Complex> = (0..*n).map(|_| pop(&mut stack, z)).collect::<Result<Vec<_>, _>>()?.into_iter().rev().collect();
+
+                match (name.as_str(), args.as_slice()) {
+                    ("sqrt", [u]) => u.sqrt(),
+                    ("exp", [u])  => u.exp(),
+                    ("ln", [u])   => u.ln(),
+                    ("sin", [u])  => u.sin(),
+                    ("cos", [u])  => u.cos(),
+                    ("tan", [u])  => u.sin() / u.cos(),
+                    ("sinh", [u]) => u.sinh(),
+                    ("cosh", [u]) => u.cosh(),
+                    ("tanh", [u]) => u.sinh() / u.cosh(),
+                    ("abs", [u])  => Complex::real(u.abs()),
+                    _             => real_only(z, name, &args)?,
+                }
+            },
+            _ => return Err(CalError::EvaluationFailed { x: z.re, reason: "Malformed expression".to_string() }),
+        };
+
+        stack.push(value);
+    }
+
+    let result: Complex = match (stack.pop(), stack.is_empty()) {
+        (Some(result), true) => result,
+        _ => return Err(CalError::EvaluationFailed { x: z.re, reason: "Malformed expression".to_string() }),
+    };
+
+    if !result.is_finite() {
+        return Err(CalError::NonFiniteValue { x: z.re, value: if result.re.is_finite() { result.im } else { result.re } });
+    }
+
+    Ok(result)
+}
+
+
+
+// Calculations
+
+fn pop(stack: &mut Vec<Complex>, z: Complex) -> Result<Complex, CalError> {
+    stack.pop().ok_or(CalError::EvaluationFailed { x: z.re, reason: "Malformed expression".to_string() })
+}
+
+// function of utils context, defined for real arguments only
+fn real_only(z: Complex, name: &str, args: &[Complex]) -> Result<Complex, CalError> {
+    if args.iter().any(|arg| arg.im != 0.0) {
+        return Err(CalError::invalid_argument(name, "complex argument is not supported"));
+    }
+
+    let values : Vec<f64> = args.iter().map(|arg| arg.re).collect();
+    let result : f64      = match name {
+        "%" => values[0] % values[1],
+        _   => match utils::with_context(|ctx| ctx.eval_func(name, &values)) {
+            Ok(value) => value,
+            Err(e)    => return Err(CalError::EvaluationFailed { x: z.re, reason: format!("{}: {}", name, e) }),
+        },
+    };
+
+    Ok(Complex::real(result))
+}
@@ -0,0 +1,233 @@
+// convergence.rs
+use crate::utils;
+use crate::error::CalError;
+
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::{from_value, to_value};
+use wasm_bindgen::prelude::*;
+
+
+
+// Duplication struct
+
+/*
+ * ---- Stopping criteria shared by iterative methods ----
+ *
+ *  abs_tol      : |x_new - x_old| < abs_tol
+ *  rel_tol      : relative error (in percent, same unit as `error` field) < rel_tol
+ *  residual_tol : |f(x)| < residual_tol
+ *  max_iter     : iteration cap
+ *
+ *  tolerance with 0.0 is disabled (strict less than)
+ *  missing fields from JS are filled by default (rel_tol 1e-6 %, 100 iterations)
+ */
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+#[serde(default)]
+pub struct StoppingCriteria {
+    pub abs_tol: f64,
+    pub rel_tol: f64,
+    pub residual_tol: f64,
+    pub max_iter: u64
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub enum StopCriterion {
+    AbsoluteTolerance,
+    RelativeTolerance,
+    Residual,
+    MaxIterations
+}
+
+/*
+ *  Converged      : one of tolerance criteria is met
+ *  MaxIterations  : iteration cap is reached without converging
+ *  Diverged       : iterate is grown over DIVERGENCE_LIMIT
+ *  Stagnated      : iterate can't move anymore (same value, flat secant, ...)
+ *  DerivativeZero : derivative (slope) is 0 where dividing is needed
+ *  NonFinite      : iterate or f(x) became NaN / inf
+ */
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub enum Outcome {
+    Converged,
+    MaxIterations,
+    Diverged,
+    Stagnated,
+    DerivativeZero,
+    NonFinite
+}
+
+// stopped_by is None when the run is ended by abnormal outcome
+#[derive(Serialize)] // Serialize the struct
+pub struct IterativeResult<T> {
+    pub iterations: Vec<T>,
+    pub outcome: Outcome,
+    pub stopped_by: Option<StopCriterion>
+}
+
+const DIVERGENCE_LIMIT: f64 = 1e15;
+
+
+
+// wasm conversion JsValue
+
+// Default values for pre-filling the criteria inputs
+#[wasm_bindgen]
+pub fn default_stopping_criteria() -> JsValue {
+    to_value(&StoppingCriteria::default()).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+}
+
+
+
+impl Default for StoppingCriteria {
+    fn default() -> Self {
+        StoppingCriteria {
+            abs_tol: 0.0,
+            rel_tol: 1e-6,
+            residual_tol: 0.0,
+            max_iter: 100
+        }
+    }
+}
+
+impl StoppingCriteria {
+    // undefined | null from JS is counted as default criteria
+    pub(crate) fn from_js(value: JsValue) -> Result<Self, CalError> {
+        if value.is_undefined() || value.is_null() {
+            return Ok(StoppingCriteria::default());
+        }
+
+        let criteria: StoppingCriteria = match from_value(value) {
+            Ok(c)  => c,
+            Err(e) => return Err(CalError::invalid_argument("criteria", &e.to_string())),
+        };
+
+        criteria.validate()?;
+
+        Ok(criteria)
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), CalError> {
+        if self.max_iter == 0 {
+            return Err(CalError::invalid_argument("max_iter", "must be at least 1"));
+        }
+        if self.abs_tol < 0.0 || self.rel_tol < 0.0 || self.residual_tol < 0.0 {
+            return Err(CalError::invalid_argument("tolerance", "must not be negative"));
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn residual_met(&self, fx: f64) -> Option<StopCriterion> {
+        if fx.abs() < self.residual_tol {
+            return Some(StopCriterion::Residual);
+        }
+
+        None
+    }
+
+    pub(crate) fn step_met(&self, x_new: f64, x_old: f64) -> Option<StopCriterion> {
+        if (x_new - x_old).abs() < self.abs_tol {
+            return Some(StopCriterion::AbsoluteTolerance);
+        }
+        // sign of error_calc follows x_new, compare as magnitude
+        if utils::error_calc(x_new, x_old).abs() < self.rel_tol {
+            return Some(StopCriterion::RelativeTolerance);
+        }
+
+        None
+    }
+
+    // Norm form: step = ||dx||, size = ||x_new||, relative error is step / size in percent
+    pub(crate) fn norm_step_met(&self, step: f64, size: f64) -> Option<StopCriterion> {
+        if step <= self.abs_tol {
+            return Some(StopCriterion::AbsoluteTolerance);
+        }
+        if size > 0.0 && step / size * 100.0 <= self.rel_tol {
+            return Some(StopCriterion::RelativeTolerance);
+        }
+
+        None
+    }
+
+    // fx is the residual at the latest evaluated iterate
+    pub(crate) fn check(&self, x_new: f64, x_old: f64, fx: f64) -> Option<StopCriterion> {
+        self.residual_met(fx).or_else(|| self.step_met(x_new, x_old))
+    }
+
+    // Vector form with infinity norms: |dx| against abs_tol, |dx| / |x_new| against rel_tol
+    pub(crate) fn check_vec(&self, x_new: &[f64], x_old: &[f64], residual: f64) -> Option<StopCriterion> {
+        let step : f64 = x_new.iter().zip(x_old).fold(0.0_f64, |acc, (a, b)| acc.max((a - b).abs()));
+        let size : f64 = x_new.iter().fold(0.0_f64, |acc, a| acc.max(a.abs()));
+
+        self.residual_met(residual).or_else(|| self.norm_step_met(step, size))
+    }
+}
+
+impl Outcome {
+    // Called after criteria checked, None when the iterate is still usable
+    pub(crate) fn inspect(x_new: f64, x_old: f64, fx: f64) -> Option<Outcome> {
+        if !x_new.is_finite() || !fx.is_finite() {
+            return Some(Outcome::NonFinite);
+        }
+        if x_new.abs() > DIVERGENCE_LIMIT {
+            return Some(Outcome::Diverged);
+        }
+        if x_new == x_old {
+            return Some(Outcome::Stagnated);
+        }
+
+        None
+    }
+
+    pub(crate) fn inspect_vec(x: &[f64]) -> Option<Outcome> {
+        if x.iter().any(|value| !value.is_finite()) {
+            return Some(Outcome::NonFinite);
+        }
+        if x.iter().any(|value| value.abs() > DIVERGENCE_LIMIT) {
+            return Some(Outcome::Diverged);
+        }
+
+        None
+    }
+}
+
+impl<T> IterativeResult<T> {
+    // Initial state is counted as reaching the cap until stopped
+    pub(crate) fn new() -> Self {
+        IterativeResult {
+            iterations: Vec::new(),
+            outcome: Outcome::MaxIterations,
+            stopped_by: Some(StopCriterion::MaxIterations)
+        }
+    }
+
+    pub(crate) fn converge(&mut self, criterion: StopCriterion) {
+        self.outcome    = Outcome::Converged;
+        self.stopped_by = Some(criterion);
+    }
+
+    pub(crate) fn fail(&mut self, outcome: Outcome) {
+        self.outcome    = outcome;
+        self.stopped_by = None;
+    }
+}
+
+
+
+// Calculations
+
+/*
+ *  Order of convergence q from the last three step sizes |x_(n+1) - x_n|
+ *      q = ln(e_n / e_(n-1)) / ln(e_(n-1) / e_(n-2))
+ *  None until three steps exist or when a step is 0 (log undefined)
+ */
+pub(crate) fn estimate_order(steps: &[f64]) -> Option<f64> {
+    if steps.len() < 3 {
+        return None;
+    }
+
+    let e : &[f64] = &steps[steps.len() - 3..];
+    let q : f64    = (e[2] / e[1]).abs().ln() / (e[1] / e[0]).abs().ln();
+
+    if q.is_finite() { Some(q) } else { None }
+}
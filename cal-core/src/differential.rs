@@ -1,5 +1,7 @@
 // differential.rs
 use crate::utils;
+use crate::error::CalError;
+use crate::expression::parse_expr;
 
 use meval::Expr;
 use serde::Serialize;
@@ -9,10 +11,10 @@ use wasm_bindgen::prelude::*;
 
 
 #[derive(Serialize)] // Serialize the struct
-pub(crate) struct DerivativeResult {
-    pub(crate) true_value: f64,
-    pub(crate) result: f64,
-    pub(crate) error: f64
+pub struct DerivativeResult {
+    pub true_value: f64,
+    pub result: f64,
+    pub error: f64
 } 
 
 
@@ -35,12 +37,12 @@ pub fn derivative
         1 => derivative_core::<Forward>(equation, x, h, precision_type, diff_times, true_result),
         2 => derivative_core::<Backward>(equation, x, h, precision_type, diff_times, true_result),
         3 => derivative_core::<Central>(equation, x, h, precision_type, diff_times, true_result),
-        _ => Err("Method type is mismatch".to_string()),
+        _ => Err(CalError::invalid_argument("method_type", "must be 1 (forward), 2 (backward) or 3 (central)")),
     };
 
     match result {
         Ok(res) => to_value(&res).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
-        Err(e) => JsValue::from_str(&e),
+        Err(e) => e.into(),
     }
 }
 
@@ -92,52 +94,49 @@ pub fn derivative
  *          2          -2     24   -78    112  -78    24   -2           12
  *          3     7    -96    676  -1952  2730 -1952  676  -96    7     240
  * */
-pub(crate) struct Forward;
-pub(crate) struct Backward;
-pub(crate) struct Central;
-pub(crate) enum Precision {
+pub struct Forward;
+pub struct Backward;
+pub struct Central;
+pub enum Precision {
     First,
     Second,
     Third
 }
 
 // No cache for evaluate the function in single call
-pub(crate) trait Differential {
+pub trait Differential {
     fn new() -> Self where Self: Sized;
 
-    fn first_derivative(&self, equation: &str, x: f64, h: f64, precision: Precision) -> Result<f64, String>;
-    fn second_derivative(&self, equation: &str, x: f64, h: f64, precision: Precision) -> Result<f64, String>;
-    fn third_derivative(&self, equation: &str, x: f64, h: f64, precision: Precision) -> Result<f64, String>;
-    fn fourth_derivative(&self, equation: &str, x: f64, h: f64, precision: Precision) -> Result<f64, String>;
+    fn first_derivative(&self, equation: &str, x: f64, h: f64, precision: Precision) -> Result<f64, CalError>;
+    fn second_derivative(&self, equation: &str, x: f64, h: f64, precision: Precision) -> Result<f64, CalError>;
+    fn third_derivative(&self, equation: &str, x: f64, h: f64, precision: Precision) -> Result<f64, CalError>;
+    fn fourth_derivative(&self, equation: &str, x: f64, h: f64, precision: Precision) -> Result<f64, CalError>;
 }
 
 impl Differential for Forward {
     fn new() -> Self { Forward }
 
-    fn first_derivative(&self, equation: &str, x: f64, h: f64, precision: Precision) -> Result<f64, String> {
+    fn first_derivative(&self, equation: &str, x: f64, h: f64, precision: Precision) -> Result<f64, CalError> {
         
-        let expr: Expr = match equation.parse() {
-            Ok(e)  => e,
-            Err(_) => return Err("Invalid function".to_string()),
-        };
+        let expr: Expr = parse_expr(equation)?;
 
         let result: f64 = match precision {
             Precision::First  => {
-                let fx_h  : f64 = utils::evaluate_expr(&expr, x + h);
-                let fx    : f64 = utils::evaluate_expr(&expr, x);
+                let fx_h  : f64 = utils::evaluate_expr(&expr, x + h)?;
+                let fx    : f64 = utils::evaluate_expr(&expr, x)?;
                 (fx_h - fx) / h
             }
             Precision::Second => {
-                let fx_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.);
-                let fx_h  : f64 = utils::evaluate_expr(&expr, x + h);
-                let fx    : f64 = utils::evaluate_expr(&expr, x);
+                let fx_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.)?;
+                let fx_h  : f64 = utils::evaluate_expr(&expr, x + h)?;
+                let fx    : f64 = utils::evaluate_expr(&expr, x)?;
                 ((-1. * fx_h2) + (4. * fx_h) - (3. * fx)) / (2. * h) 
             }  
             Precision::Third  => {
-                let fx_h3 : f64 = utils::evaluate_expr(&expr, x + h * 3.);
-                let fx_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.);
-                let fx_h  : f64 = utils::evaluate_expr(&expr, x + h);
-                let fx    : f64 = utils::evaluate_expr(&expr, x);
+                let fx_h3 : f64 = utils::evaluate_expr(&expr, x + h * 3.)?;
+                let fx_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.)?;
+                let fx_h  : f64 = utils::evaluate_expr(&expr, x + h)?;
+                let fx    : f64 = utils::evaluate_expr(&expr, x)?;
                 ((2. * fx_h3) - (9. * fx_h2) + (18. * fx_h) - (11. * fx)) / (6. * h)
             } 
         };
@@ -145,33 +144,30 @@ impl Differential for Forward {
         Ok(result)
     }
 
-    fn second_derivative(&self, equation: &str, x: f64, h: f64, precision: Precision) -> Result<f64, String> {
+    fn second_derivative(&self, equation: &str, x: f64, h: f64, precision: Precision) -> Result<f64, CalError> {
         
-        let expr: Expr = match equation.parse() {
-            Ok(e)  => e,
-            Err(_) => return Err("Invalid function".to_string()),
-        };
+        let expr: Expr = parse_expr(equation)?;
 
         let result: f64 = match precision {
             Precision::First  => {
-                let fx_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.);
-                let fx_h  : f64 = utils::evaluate_expr(&expr, x + h);
-                let fx    : f64 = utils::evaluate_expr(&expr, x);
+                let fx_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.)?;
+                let fx_h  : f64 = utils::evaluate_expr(&expr, x + h)?;
+                let fx    : f64 = utils::evaluate_expr(&expr, x)?;
                 (fx_h2 - (2. * fx_h) + fx) / (h * h)
             }
             Precision::Second => {
-                let fx_h3 : f64 = utils::evaluate_expr(&expr, x + h * 3.);
-                let fx_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.);
-                let fx_h  : f64 = utils::evaluate_expr(&expr, x + h);
-                let fx    : f64 = utils::evaluate_expr(&expr, x);
+                let fx_h3 : f64 = utils::evaluate_expr(&expr, x + h * 3.)?;
+                let fx_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.)?;
+                let fx_h  : f64 = utils::evaluate_expr(&expr, x + h)?;
+                let fx    : f64 = utils::evaluate_expr(&expr, x)?;
                 ((-1. * fx_h3) + (4. * fx_h2) - (5. * fx_h) + (2. * fx)) / (h * h) 
             }  
             Precision::Third  => {
-                let fx_h4 : f64 = utils::evaluate_expr(&expr, x + h * 4.);
-                let fx_h3 : f64 = utils::evaluate_expr(&expr, x + h * 3.);
-                let fx_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.);
-                let fx_h  : f64 = utils::evaluate_expr(&expr, x + h);
-                let fx    : f64 = utils::evaluate_expr(&expr, x);
+                let fx_h4 : f64 = utils::evaluate_expr(&expr, x + h * 4.)?;
+                let fx_h3 : f64 = utils::evaluate_expr(&expr, x + h * 3.)?;
+                let fx_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.)?;
+                let fx_h  : f64 = utils::evaluate_expr(&expr, x + h)?;
+                let fx    : f64 = utils::evaluate_expr(&expr, x)?;
                 ((11. * fx_h4) - (56. * fx_h3) + (114. * fx_h2) - (104. * fx_h) + (35. * fx)) / (12. * h * h)
             } 
         };
@@ -179,36 +175,33 @@ impl Differential for Forward {
         Ok(result)
     }
 
-    fn third_derivative(&self, equation: &str, x: f64, h: f64, precision: Precision) -> Result<f64, String> {
+    fn third_derivative(&self, equation: &str, x: f64, h: f64, precision: Precision) -> Result<f64, CalError> {
         
-        let expr: Expr = match equation.parse() {
-            Ok(e)  => e,
-            Err(_) => return Err("Invalid function".to_string()),
-        };
+        let expr: Expr = parse_expr(equation)?;
 
         let result: f64 = match precision {
             Precision::First  => {
-                let fx_h3 : f64 = utils::evaluate_expr(&expr, x + h * 3.);
-                let fx_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.);
-                let fx_h  : f64 = utils::evaluate_expr(&expr, x + h);
-                let fx    : f64 = utils::evaluate_expr(&expr, x);
+                let fx_h3 : f64 = utils::evaluate_expr(&expr, x + h * 3.)?;
+                let fx_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.)?;
+                let fx_h  : f64 = utils::evaluate_expr(&expr, x + h)?;
+                let fx    : f64 = utils::evaluate_expr(&expr, x)?;
                 (fx_h3 - (3. * fx_h2) + (3. * fx_h) - fx) / (h.powi(3))
             }
             Precision::Second => {
-                let fx_h4 : f64 = utils::evaluate_expr(&expr, x + h * 4.);
-                let fx_h3 : f64 = utils::evaluate_expr(&expr, x + h * 3.);
-                let fx_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.);
-                let fx_h  : f64 = utils::evaluate_expr(&expr, x + h);
-                let fx    : f64 = utils::evaluate_expr(&expr, x);
+                let fx_h4 : f64 = utils::evaluate_expr(&expr, x + h * 4.)?;
+                let fx_h3 : f64 = utils::evaluate_expr(&expr, x + h * 3.)?;
+                let fx_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.)?;
+                let fx_h  : f64 = utils::evaluate_expr(&expr, x + h)?;
+                let fx    : f64 = utils::evaluate_expr(&expr, x)?;
                 ((-3. * fx_h4) + (14. * fx_h3) - (24. * fx_h2) + (18. * fx_h) - (5. * fx)) / (2. * h.powi(3))
             }  
             Precision::Third  => {
-                let fx_h5 : f64 = utils::evaluate_expr(&expr, x + h * 5.);
-                let fx_h4 : f64 = utils::evaluate_expr(&expr, x + h * 4.);
-                let fx_h3 : f64 = utils::evaluate_expr(&expr, x + h * 3.);
-                let fx_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.);
-                let fx_h  : f64 = utils::evaluate_expr(&expr, x + h);
-                let fx    : f64 = utils::evaluate_expr(&expr, x);
+                let fx_h5 : f64 = utils::evaluate_expr(&expr, x + h * 5.)?;
+                let fx_h4 : f64 = utils::evaluate_expr(&expr, x + h * 4.)?;
+                let fx_h3 : f64 = utils::evaluate_expr(&expr, x + h * 3.)?;
+                let fx_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.)?;
+                let fx_h  : f64 = utils::evaluate_expr(&expr, x + h)?;
+                let fx    : f64 = utils::evaluate_expr(&expr, x)?;
                 ((14. * fx_h5) - (82. * fx_h4) + (196. * fx_h3) - (236. * fx_h2) + (142. * fx_h) - (34. * fx)) / (8. * h.powi(3))
             } 
         };
@@ -216,39 +209,36 @@ impl Differential for Forward {
         Ok(result)
     }
 
-    fn fourth_derivative(&self, equation: &str, x: f64, h: f64, precision: Precision) -> Result<f64, String> {
+    fn fourth_derivative(&self, equation: &str, x: f64, h: f64, precision: Precision) -> Result<f64, CalError> {
         
-        let expr: Expr = match equation.parse() {
-            Ok(e)  => e,
-            Err(_) => return Err("Invalid function".to_string()),
-        };
+        let expr: Expr = parse_expr(equation)?;
 
         let result: f64 = match precision {
             Precision::First  => {
-                let fx_h4 : f64 = utils::evaluate_expr(&expr, x + h * 4.);
-                let fx_h3 : f64 = utils::evaluate_expr(&expr, x + h * 3.);
-                let fx_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.);
-                let fx_h  : f64 = utils::evaluate_expr(&expr, x + h);
-                let fx    : f64 = utils::evaluate_expr(&expr, x);
+                let fx_h4 : f64 = utils::evaluate_expr(&expr, x + h * 4.)?;
+                let fx_h3 : f64 = utils::evaluate_expr(&expr, x + h * 3.)?;
+                let fx_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.)?;
+                let fx_h  : f64 = utils::evaluate_expr(&expr, x + h)?;
+                let fx    : f64 = utils::evaluate_expr(&expr, x)?;
                 (fx_h4 - (4. * fx_h3) + (6. * fx_h2) - (4. * fx_h) + fx) / (h.powi(4))
             }
             Precision::Second => {
-                let fx_h5 : f64 = utils::evaluate_expr(&expr, x + h * 5.);
-                let fx_h4 : f64 = utils::evaluate_expr(&expr, x + h * 4.);
-                let fx_h3 : f64 = utils::evaluate_expr(&expr, x + h * 3.);
-                let fx_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.);
-                let fx_h  : f64 = utils::evaluate_expr(&expr, x + h);
-                let fx    : f64 = utils::evaluate_expr(&expr, x);
+                let fx_h5 : f64 = utils::evaluate_expr(&expr, x + h * 5.)?;
+                let fx_h4 : f64 = utils::evaluate_expr(&expr, x + h * 4.)?;
+                let fx_h3 : f64 = utils::evaluate_expr(&expr, x + h * 3.)?;
+                let fx_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.)?;
+                let fx_h  : f64 = utils::evaluate_expr(&expr, x + h)?;
+                let fx    : f64 = utils::evaluate_expr(&expr, x)?;
                 ((-2. * fx_h5) + (11. * fx_h4) - (24. * fx_h3) + (26. * fx_h2) - (14. * fx_h) + (3. * fx)) / (h.powi(4))
             }  
             Precision::Third  => {
-                let fx_h6 : f64 = utils::evaluate_expr(&expr, x + h * 6.);
-                let fx_h5 : f64 = utils::evaluate_expr(&expr, x + h * 5.);
-                let fx_h4 : f64 = utils::evaluate_expr(&expr, x + h * 4.);
-                let fx_h3 : f64 = utils::evaluate_expr(&expr, x + h * 3.);
-                let fx_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.);
-                let fx_h  : f64 = utils::evaluate_expr(&expr, x + h);
-                let fx    : f64 = utils::evaluate_expr(&expr, x);
+                let fx_h6 : f64 = utils::evaluate_expr(&expr, x + h * 6.)?;
+                let fx_h5 : f64 = utils::evaluate_expr(&expr, x + h * 5.)?;
+                let fx_h4 : f64 = utils::evaluate_expr(&expr, x + h * 4.)?;
+                let fx_h3 : f64 = utils::evaluate_expr(&expr, x + h * 3.)?;
+                let fx_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.)?;
+                let fx_h  : f64 = utils::evaluate_expr(&expr, x + h)?;
+                let fx    : f64 = utils::evaluate_expr(&expr, x)?;
                 ((34. * fx_h6) - (228. * fx_h5) + (642. * fx_h4) - (968. * fx_h3) + (822. * fx_h2) - (372. * fx_h) + (70. * fx)) / (12. * h.powi(4))
             } 
         };
@@ -260,30 +250,27 @@ impl Differential for Forward {
 impl Differential for Backward {
     fn new() -> Self { Backward }
 
-    fn first_derivative(&self, equation: &str, x: f64, h: f64, precision: Precision) -> Result<f64, String> {
+    fn first_derivative(&self, equation: &str, x: f64, h: f64, precision: Precision) -> Result<f64, CalError> {
         
-        let expr: Expr = match equation.parse() {
-            Ok(e)  => e,
-            Err(_) => return Err("Invalid function".to_string()),
-        };
+        let expr: Expr = parse_expr(equation)?;
 
         let result: f64 = match precision {
             Precision::First  => {
-                let fx_h  : f64 = utils::evaluate_expr(&expr, x - h);
-                let fx    : f64 = utils::evaluate_expr(&expr, x);
+                let fx_h  : f64 = utils::evaluate_expr(&expr, x - h)?;
+                let fx    : f64 = utils::evaluate_expr(&expr, x)?;
                 (fx - fx_h) / h
             }
             Precision::Second => {
-                let fx_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.);
-                let fx_h  : f64 = utils::evaluate_expr(&expr, x - h);
-                let fx    : f64 = utils::evaluate_expr(&expr, x);
+                let fx_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.)?;
+                let fx_h  : f64 = utils::evaluate_expr(&expr, x - h)?;
+                let fx    : f64 = utils::evaluate_expr(&expr, x)?;
                 (fx_h2 - (4. * fx_h) + (3. * fx)) / (2. * h) 
             }  
             Precision::Third  => {
-                let fx_h3 : f64 = utils::evaluate_expr(&expr, x - h * 3.);
-                let fx_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.);
-                let fx_h  : f64 = utils::evaluate_expr(&expr, x - h);
-                let fx    : f64 = utils::evaluate_expr(&expr, x);
+                let fx_h3 : f64 = utils::evaluate_expr(&expr, x - h * 3.)?;
+                let fx_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.)?;
+                let fx_h  : f64 = utils::evaluate_expr(&expr, x - h)?;
+                let fx    : f64 = utils::evaluate_expr(&expr, x)?;
                 ((-2. * fx_h3) + (9. * fx_h2) - (18. * fx_h) + (11. * fx)) / (6. * h)
             } 
         };
@@ -291,33 +278,30 @@ impl Differential for Backward {
         Ok(result)
     }
 
-    fn second_derivative(&self, equation: &str, x: f64, h: f64, precision: Precision) -> Result<f64, String> {
+    fn second_derivative(&self, equation: &str, x: f64, h: f64, precision: Precision) -> Result<f64, CalError> {
         
-        let expr: Expr = match equation.parse() {
-            Ok(e)  => e,
-            Err(_) => return Err("Invalid function".to_string()),
-        };
+        let expr: Expr = parse_expr(equation)?;
 
         let result: f64 = match precision {
             Precision::First  => {
-                let fx_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.);
-                let fx_h  : f64 = utils::evaluate_expr(&expr, x - h);
-                let fx    : f64 = utils::evaluate_expr(&expr, x);
+                let fx_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.)?;
+                let fx_h  : f64 = utils::evaluate_expr(&expr, x - h)?;
+                let fx    : f64 = utils::evaluate_expr(&expr, x)?;
                 (fx_h2 - (2. * fx_h) + fx) / (h * h)
             }
             Precision::Second => {
-                let fx_h3 : f64 = utils::evaluate_expr(&expr, x - h * 3.);
-                let fx_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.);
-                let fx_h  : f64 = utils::evaluate_expr(&expr, x - h);
-                let fx    : f64 = utils::evaluate_expr(&expr, x);
+                let fx_h3 : f64 = utils::evaluate_expr(&expr, x - h * 3.)?;
+                let fx_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.)?;
+                let fx_h  : f64 = utils::evaluate_expr(&expr, x - h)?;
+                let fx    : f64 = utils::evaluate_expr(&expr, x)?;
                 ((-1. * fx_h3) + (4. * fx_h2) - (5. * fx_h) + (2. * fx)) / (h * h) 
             }  
             Precision::Third  => {
-                let fx_h4 : f64 = utils::evaluate_expr(&expr, x - h * 4.);
-                let fx_h3 : f64 = utils::evaluate_expr(&expr, x - h * 3.);
-                let fx_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.);
-                let fx_h  : f64 = utils::evaluate_expr(&expr, x - h);
-                let fx    : f64 = utils::evaluate_expr(&expr, x);
+                let fx_h4 : f64 = utils::evaluate_expr(&expr, x - h * 4.)?;
+                let fx_h3 : f64 = utils::evaluate_expr(&expr, x - h * 3.)?;
+                let fx_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.)?;
+                let fx_h  : f64 = utils::evaluate_expr(&expr, x - h)?;
+                let fx    : f64 = utils::evaluate_expr(&expr, x)?;
                 ((11. * fx_h4) - (56. * fx_h3) + (114. * fx_h2) - (104. * fx_h) + (35. * fx)) / (12. * h * h)
             } 
         };
@@ -325,36 +309,33 @@ impl Differential for Backward {
         Ok(result)
     }
 
-    fn third_derivative(&self, equation: &str, x: f64, h: f64, precision: Precision) -> Result<f64, String> {
+    fn third_derivative(&self, equation: &str, x: f64, h: f64, precision: Precision) -> Result<f64, CalError> {
         
-        let expr: Expr = match equation.parse() {
-            Ok(e)  => e,
-            Err(_) => return Err("Invalid function".to_string()),
-        };
+        let expr: Expr = parse_expr(equation)?;
 
         let result: f64 = match precision {
             Precision::First  => {
-                let fx_h3 : f64 = utils::evaluate_expr(&expr, x - h * 3.);
-                let fx_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.);
-                let fx_h  : f64 = utils::evaluate_expr(&expr, x - h);
-                let fx    : f64 = utils::evaluate_expr(&expr, x);
+                let fx_h3 : f64 = utils::evaluate_expr(&expr, x - h * 3.)?;
+                let fx_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.)?;
+                let fx_h  : f64 = utils::evaluate_expr(&expr, x - h)?;
+                let fx    : f64 = utils::evaluate_expr(&expr, x)?;
                 ((-1. * fx_h3) + (3. * fx_h2) - (3. * fx_h) + fx) / (h.powi(3))
             }
             Precision::Second => {
-                let fx_h4 : f64 = utils::evaluate_expr(&expr, x - h * 4.);
-                let fx_h3 : f64 = utils::evaluate_expr(&expr, x - h * 3.);
-                let fx_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.);
-                let fx_h  : f64 = utils::evaluate_expr(&expr, x - h);
-                let fx    : f64 = utils::evaluate_expr(&expr, x);
+                let fx_h4 : f64 = utils::evaluate_expr(&expr, x - h * 4.)?;
+                let fx_h3 : f64 = utils::evaluate_expr(&expr, x - h * 3.)?;
+                let fx_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.)?;
+                let fx_h  : f64 = utils::evaluate_expr(&expr, x - h)?;
+                let fx    : f64 = utils::evaluate_expr(&expr, x)?;
                 ((3. * fx_h4) - (14. * fx_h3) + (24. * fx_h2) - (18. * fx_h) + (5. * fx)) / (2. * h.powi(3))
             }  
             Precision::Third  => {
-                let fx_h5 : f64 = utils::evaluate_expr(&expr, x - h * 5.);
-                let fx_h4 : f64 = utils::evaluate_expr(&expr, x - h * 4.);
-                let fx_h3 : f64 = utils::evaluate_expr(&expr, x - h * 3.);
-                let fx_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.);
-                let fx_h  : f64 = utils::evaluate_expr(&expr, x - h);
-                let fx    : f64 = utils::evaluate_expr(&expr, x);
+                let fx_h5 : f64 = utils::evaluate_expr(&expr, x - h * 5.)?;
+                let fx_h4 : f64 = utils::evaluate_expr(&expr, x - h * 4.)?;
+                let fx_h3 : f64 = utils::evaluate_expr(&expr, x - h * 3.)?;
+                let fx_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.)?;
+                let fx_h  : f64 = utils::evaluate_expr(&expr, x - h)?;
+                let fx    : f64 = utils::evaluate_expr(&expr, x)?;
                 ((-14. * fx_h5) + (82. * fx_h4) - (196. * fx_h3) + (236. * fx_h2) - (142. * fx_h) + (34. * fx)) / (8. * h.powi(3))
             } 
         };
@@ -362,39 +343,36 @@ impl Differential for Backward {
         Ok(result)
     }
 
-    fn fourth_derivative(&self, equation: &str, x: f64, h: f64, precision: Precision) -> Result<f64, String> {
+    fn fourth_derivative(&self, equation: &str, x: f64, h: f64, precision: Precision) -> Result<f64, CalError> {
         
-        let expr: Expr = match equation.parse() {
-            Ok(e)  => e,
-            Err(_) => return Err("Invalid function".to_string()),
-        };
+        let expr: Expr = parse_expr(equation)?;
 
         let result: f64 = match precision {
             Precision::First  => {
-                let fx_h4 : f64 = utils::evaluate_expr(&expr, x - h * 4.);
-                let fx_h3 : f64 = utils::evaluate_expr(&expr, x - h * 3.);
-                let fx_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.);
-                let fx_h  : f64 = utils::evaluate_expr(&expr, x - h);
-                let fx    : f64 = utils::evaluate_expr(&expr, x);
+                let fx_h4 : f64 = utils::evaluate_expr(&expr, x - h * 4.)?;
+                let fx_h3 : f64 = utils::evaluate_expr(&expr, x - h * 3.)?;
+                let fx_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.)?;
+                let fx_h  : f64 = utils::evaluate_expr(&expr, x - h)?;
+                let fx    : f64 = utils::evaluate_expr(&expr, x)?;
                 ((-1. * fx_h4) + (4. * fx_h3) - (6. * fx_h2) + (4. * fx_h) - fx) / (h.powi(4))
             }
             Precision::Second => {
-                let fx_h5 : f64 = utils::evaluate_expr(&expr, x - h * 5.);
-                let fx_h4 : f64 = utils::evaluate_expr(&expr, x - h * 4.);
-                let fx_h3 : f64 = utils::evaluate_expr(&expr, x - h * 3.);
-                let fx_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.);
-                let fx_h  : f64 = utils::evaluate_expr(&expr, x - h);
-                let fx    : f64 = utils::evaluate_expr(&expr, x);
+                let fx_h5 : f64 = utils::evaluate_expr(&expr, x - h * 5.)?;
+                let fx_h4 : f64 = utils::evaluate_expr(&expr, x - h * 4.)?;
+                let fx_h3 : f64 = utils::evaluate_expr(&expr, x - h * 3.)?;
+                let fx_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.)?;
+                let fx_h  : f64 = utils::evaluate_expr(&expr, x - h)?;
+                let fx    : f64 = utils::evaluate_expr(&expr, x)?;
                 ((2. * fx_h5) - (11. * fx_h4) + (24. * fx_h3) - (26. * fx_h2) + (14. * fx_h) - (3. * fx)) / (h.powi(4))
             }  
             Precision::Third  => {
-                let fx_h6 : f64 = utils::evaluate_expr(&expr, x - h * 6.);
-                let fx_h5 : f64 = utils::evaluate_expr(&expr, x - h * 5.);
-                let fx_h4 : f64 = utils::evaluate_expr(&expr, x - h * 4.);
-                let fx_h3 : f64 = utils::evaluate_expr(&expr, x - h * 3.);
-                let fx_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.);
-                let fx_h  : f64 = utils::evaluate_expr(&expr, x - h);
-                let fx    : f64 = utils::evaluate_expr(&expr, x);
+                let fx_h6 : f64 = utils::evaluate_expr(&expr, x - h * 6.)?;
+                let fx_h5 : f64 = utils::evaluate_expr(&expr, x - h * 5.)?;
+                let fx_h4 : f64 = utils::evaluate_expr(&expr, x - h * 4.)?;
+                let fx_h3 : f64 = utils::evaluate_expr(&expr, x - h * 3.)?;
+                let fx_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.)?;
+                let fx_h  : f64 = utils::evaluate_expr(&expr, x - h)?;
+                let fx    : f64 = utils::evaluate_expr(&expr, x)?;
                 ((-34. * fx_h6) + (228. * fx_h5) - (642. * fx_h4) + (968. * fx_h3) - (822. * fx_h2) + (372. * fx_h) - (70. * fx)) / (12. * h.powi(4))
             } 
         };
@@ -406,41 +384,38 @@ impl Differential for Backward {
 impl Differential for Central {
     fn new() -> Self { Central }
 
-    fn first_derivative(&self, equation: &str, x: f64, h: f64, precision: Precision) -> Result<f64, String> {
+    fn first_derivative(&self, equation: &str, x: f64, h: f64, precision: Precision) -> Result<f64, CalError> {
         
-        let expr: Expr = match equation.parse() {
-            Ok(e)  => e,
-            Err(_) => return Err("Invalid function".to_string()),
-        };
+        let expr: Expr = parse_expr(equation)?;
 
         let result: f64 = match precision {
             Precision::First  => {
                 // Positive h
-                let fx_p_h  : f64 = utils::evaluate_expr(&expr, x + h);
+                let fx_p_h  : f64 = utils::evaluate_expr(&expr, x + h)?;
                 // Negative h
-                let fx_n_h  : f64 = utils::evaluate_expr(&expr, x - h);
+                let fx_n_h  : f64 = utils::evaluate_expr(&expr, x - h)?;
 
                 (fx_p_h - fx_n_h) / (2. * h)
             }
             Precision::Second => {                
                 // Positive h
-                let fx_p_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.);
-                let fx_p_h  : f64 = utils::evaluate_expr(&expr, x + h);
+                let fx_p_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.)?;
+                let fx_p_h  : f64 = utils::evaluate_expr(&expr, x + h)?;
                 // Negative h
-                let fx_n_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.);
-                let fx_n_h  : f64 = utils::evaluate_expr(&expr, x - h);
+                let fx_n_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.)?;
+                let fx_n_h  : f64 = utils::evaluate_expr(&expr, x - h)?;
 
                 ((-1. * fx_p_h2) + (8. * fx_p_h) - (8. * fx_n_h) + fx_n_h2) / (12. * h)
             }  
             Precision::Third  => {
                 // Positive h
-                let fx_p_h3 : f64 = utils::evaluate_expr(&expr, x + h * 3.);
-                let fx_p_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.);
-                let fx_p_h  : f64 = utils::evaluate_expr(&expr, x + h);
+                let fx_p_h3 : f64 = utils::evaluate_expr(&expr, x + h * 3.)?;
+                let fx_p_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.)?;
+                let fx_p_h  : f64 = utils::evaluate_expr(&expr, x + h)?;
                 // Negative h
-                let fx_n_h3 : f64 = utils::evaluate_expr(&expr, x - h * 3.);
-                let fx_n_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.);
-                let fx_n_h  : f64 = utils::evaluate_expr(&expr, x - h);
+                let fx_n_h3 : f64 = utils::evaluate_expr(&expr, x - h * 3.)?;
+                let fx_n_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.)?;
+                let fx_n_h  : f64 = utils::evaluate_expr(&expr, x - h)?;
 
                 (   
                     fx_p_h3 + (-9. * fx_p_h2) + (45. * fx_p_h) -
@@ -452,33 +427,30 @@ impl Differential for Central {
         Ok(result)
     }
 
-    fn second_derivative(&self, equation: &str, x: f64, h: f64, precision: Precision) -> Result<f64, String> {
+    fn second_derivative(&self, equation: &str, x: f64, h: f64, precision: Precision) -> Result<f64, CalError> {
         
-        let expr: Expr = match equation.parse() {
-            Ok(e)  => e,
-            Err(_) => return Err("Invalid function".to_string()),
-        };
+        let expr: Expr = parse_expr(equation)?;
 
         let result: f64 = match precision {
             Precision::First  => {
                 // Positive h
-                let fx_p_h  : f64 = utils::evaluate_expr(&expr, x + h);
+                let fx_p_h  : f64 = utils::evaluate_expr(&expr, x + h)?;
                 // Negative h
-                let fx_n_h  : f64 = utils::evaluate_expr(&expr, x - h);
+                let fx_n_h  : f64 = utils::evaluate_expr(&expr, x - h)?;
                 // Expr x
-                let fx      : f64 = utils::evaluate_expr(&expr, x);
+                let fx      : f64 = utils::evaluate_expr(&expr, x)?;
 
                 (fx_p_h - (2. * fx) + fx_n_h) / (h * h)
             }
             Precision::Second => {                
                 // Positive h
-                let fx_p_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.);
-                let fx_p_h  : f64 = utils::evaluate_expr(&expr, x + h);
+                let fx_p_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.)?;
+                let fx_p_h  : f64 = utils::evaluate_expr(&expr, x + h)?;
                 // Negative h
-                let fx_n_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.);
-                let fx_n_h  : f64 = utils::evaluate_expr(&expr, x - h);
+                let fx_n_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.)?;
+                let fx_n_h  : f64 = utils::evaluate_expr(&expr, x - h)?;
                 // Expr x
-                let fx      : f64 = utils::evaluate_expr(&expr, x);
+                let fx      : f64 = utils::evaluate_expr(&expr, x)?;
 
                 (
                     (-1. * fx_p_h2) + (16. * fx_p_h) - (30. * fx) + 
@@ -487,15 +459,15 @@ impl Differential for Central {
             }  
             Precision::Third  => {
                 // Positive h
-                let fx_p_h3 : f64 = utils::evaluate_expr(&expr, x + h * 3.);
-                let fx_p_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.);
-                let fx_p_h  : f64 = utils::evaluate_expr(&expr, x + h);
+                let fx_p_h3 : f64 = utils::evaluate_expr(&expr, x + h * 3.)?;
+                let fx_p_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.)?;
+                let fx_p_h  : f64 = utils::evaluate_expr(&expr, x + h)?;
                 // Negative h
-                let fx_n_h3 : f64 = utils::evaluate_expr(&expr, x - h * 3.);
-                let fx_n_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.);
-                let fx_n_h  : f64 = utils::evaluate_expr(&expr, x - h);
+                let fx_n_h3 : f64 = utils::evaluate_expr(&expr, x - h * 3.)?;
+                let fx_n_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.)?;
+                let fx_n_h  : f64 = utils::evaluate_expr(&expr, x - h)?;
                 // Expr x
-                let fx      : f64 = utils::evaluate_expr(&expr, x);
+                let fx      : f64 = utils::evaluate_expr(&expr, x)?;
 
                 (
                     (2. * fx_p_h3) - (27. * fx_p_h2) + (270. * fx_p_h) - (490. * fx) + 
@@ -507,21 +479,18 @@ impl Differential for Central {
         Ok(result)
     }
 
-    fn third_derivative(&self, equation: &str, x: f64, h: f64, precision: Precision) -> Result<f64, String> {
+    fn third_derivative(&self, equation: &str, x: f64, h: f64, precision: Precision) -> Result<f64, CalError> {
         
-        let expr: Expr = match equation.parse() {
-            Ok(e)  => e,
-            Err(_) => return Err("Invalid function".to_string()),
-        };
+        let expr: Expr = parse_expr(equation)?;
 
         let result: f64 = match precision {
             Precision::First  => {
                 // Positive h
-                let fx_p_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.);
-                let fx_p_h  : f64 = utils::evaluate_expr(&expr, x + h);
+                let fx_p_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.)?;
+                let fx_p_h  : f64 = utils::evaluate_expr(&expr, x + h)?;
                 // Negative h
-                let fx_n_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.);
-                let fx_n_h  : f64 = utils::evaluate_expr(&expr, x - h);
+                let fx_n_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.)?;
+                let fx_n_h  : f64 = utils::evaluate_expr(&expr, x - h)?;
 
                 (
                     fx_p_h2 - (2. * fx_p_h) - 
@@ -530,13 +499,13 @@ impl Differential for Central {
             }
             Precision::Second => {                
                 // Positive h
-                let fx_p_h3 : f64 = utils::evaluate_expr(&expr, x + h * 3.);
-                let fx_p_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.);
-                let fx_p_h  : f64 = utils::evaluate_expr(&expr, x + h);
+                let fx_p_h3 : f64 = utils::evaluate_expr(&expr, x + h * 3.)?;
+                let fx_p_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.)?;
+                let fx_p_h  : f64 = utils::evaluate_expr(&expr, x + h)?;
                 // Negative h
-                let fx_n_h3 : f64 = utils::evaluate_expr(&expr, x - h * 3.);
-                let fx_n_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.);
-                let fx_n_h  : f64 = utils::evaluate_expr(&expr, x - h);
+                let fx_n_h3 : f64 = utils::evaluate_expr(&expr, x - h * 3.)?;
+                let fx_n_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.)?;
+                let fx_n_h  : f64 = utils::evaluate_expr(&expr, x - h)?;
 
                 (
                     fx_n_h3 - (8. * fx_n_h2) + (13. * fx_n_h) -
@@ -545,15 +514,15 @@ impl Differential for Central {
             }  
             Precision::Third  => {
                 // Positive h
-                let fx_p_h4 : f64 = utils::evaluate_expr(&expr, x + h * 4.);
-                let fx_p_h3 : f64 = utils::evaluate_expr(&expr, x + h * 3.);
-                let fx_p_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.);
-                let fx_p_h  : f64 = utils::evaluate_expr(&expr, x + h);
+                let fx_p_h4 : f64 = utils::evaluate_expr(&expr, x + h * 4.)?;
+                let fx_p_h3 : f64 = utils::evaluate_expr(&expr, x + h * 3.)?;
+                let fx_p_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.)?;
+                let fx_p_h  : f64 = utils::evaluate_expr(&expr, x + h)?;
                 // Negative h
-                let fx_n_h4 : f64 = utils::evaluate_expr(&expr, x - h * 4.);
-                let fx_n_h3 : f64 = utils::evaluate_expr(&expr, x - h * 3.);
-                let fx_n_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.);
-                let fx_n_h  : f64 = utils::evaluate_expr(&expr, x - h);
+                let fx_n_h4 : f64 = utils::evaluate_expr(&expr, x - h * 4.)?;
+                let fx_n_h3 : f64 = utils::evaluate_expr(&expr, x - h * 3.)?;
+                let fx_n_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.)?;
+                let fx_n_h  : f64 = utils::evaluate_expr(&expr, x - h)?;
 
                 (
                     (7. * fx_p_h4) - (72. * fx_p_h3) + (338. * fx_p_h2) - (488. * fx_p_h) -
@@ -565,23 +534,20 @@ impl Differential for Central {
         Ok(result)
     }
 
-    fn fourth_derivative(&self, equation: &str, x: f64, h: f64, precision: Precision) -> Result<f64, String> {
+    fn fourth_derivative(&self, equation: &str, x: f64, h: f64, precision: Precision) -> Result<f64, CalError> {
         
-        let expr: Expr = match equation.parse() {
-            Ok(e)  => e,
-            Err(_) => return Err("Invalid function".to_string()),
-        };
+        let expr: Expr = parse_expr(equation)?;
 
         let result: f64 = match precision {
             Precision::First  => {
                 // Positive h
-                let fx_p_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.);
-                let fx_p_h  : f64 = utils::evaluate_expr(&expr, x + h);
+                let fx_p_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.)?;
+                let fx_p_h  : f64 = utils::evaluate_expr(&expr, x + h)?;
                 // Negative h
-                let fx_n_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.);
-                let fx_n_h  : f64 = utils::evaluate_expr(&expr, x - h);
+                let fx_n_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.)?;
+                let fx_n_h  : f64 = utils::evaluate_expr(&expr, x - h)?;
                 // Expr x
-                let fx      : f64 = utils::evaluate_expr(&expr, x);
+                let fx      : f64 = utils::evaluate_expr(&expr, x)?;
 
                 (
                     fx_p_h2 - (4. * fx_p_h) + (6. * fx) + 
@@ -590,15 +556,15 @@ impl Differential for Central {
             }
             Precision::Second => {                
                 // Positive h
-                let fx_p_h3 : f64 = utils::evaluate_expr(&expr, x + h * 3.);
-                let fx_p_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.);
-                let fx_p_h  : f64 = utils::evaluate_expr(&expr, x + h);
+                let fx_p_h3 : f64 = utils::evaluate_expr(&expr, x + h * 3.)?;
+                let fx_p_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.)?;
+                let fx_p_h  : f64 = utils::evaluate_expr(&expr, x + h)?;
                 // Negative h
-                let fx_n_h3 : f64 = utils::evaluate_expr(&expr, x - h * 3.);
-                let fx_n_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.);
-                let fx_n_h  : f64 = utils::evaluate_expr(&expr, x - h);
+                let fx_n_h3 : f64 = utils::evaluate_expr(&expr, x - h * 3.)?;
+                let fx_n_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.)?;
+                let fx_n_h  : f64 = utils::evaluate_expr(&expr, x - h)?;
                 // Expr x
-                let fx      : f64 = utils::evaluate_expr(&expr, x);
+                let fx      : f64 = utils::evaluate_expr(&expr, x)?;
 
                 (
                     (-2. * fx_p_h3) + (24. * fx_p_h2) - (78. * fx_p_h) + (112. * fx) + 
@@ -607,17 +573,17 @@ impl Differential for Central {
             }  
             Precision::Third  => {
                 // Positive h
-                let fx_p_h4 : f64 = utils::evaluate_expr(&expr, x + h * 4.);
-                let fx_p_h3 : f64 = utils::evaluate_expr(&expr, x + h * 3.);
-                let fx_p_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.);
-                let fx_p_h  : f64 = utils::evaluate_expr(&expr, x + h);
+                let fx_p_h4 : f64 = utils::evaluate_expr(&expr, x + h * 4.)?;
+                let fx_p_h3 : f64 = utils::evaluate_expr(&expr, x + h * 3.)?;
+                let fx_p_h2 : f64 = utils::evaluate_expr(&expr, x + h * 2.)?;
+                let fx_p_h  : f64 = utils::evaluate_expr(&expr, x + h)?;
                 // Negative h
-                let fx_n_h4 : f64 = utils::evaluate_expr(&expr, x - h * 4.);
-                let fx_n_h3 : f64 = utils::evaluate_expr(&expr, x - h * 3.);
-                let fx_n_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.);
-                let fx_n_h  : f64 = utils::evaluate_expr(&expr, x - h);
+                let fx_n_h4 : f64 = utils::evaluate_expr(&expr, x - h * 4.)?;
+                let fx_n_h3 : f64 = utils::evaluate_expr(&expr, x - h * 3.)?;
+                let fx_n_h2 : f64 = utils::evaluate_expr(&expr, x - h * 2.)?;
+                let fx_n_h  : f64 = utils::evaluate_expr(&expr, x - h)?;
                 // Expr x
-                let fx      : f64 = utils::evaluate_expr(&expr, x);
+                let fx      : f64 = utils::evaluate_expr(&expr, x)?;
 
                 (
                     (7. * fx_p_h4) - (96. * fx_p_h3) + (676. * fx_p_h2) - (1952. * fx_p_h) + (2730. * fx) + 
@@ -630,12 +596,12 @@ impl Differential for Central {
     }
 }
 
-fn get_precision(count: u32) -> Result<Precision, String> {
+fn get_precision(count: u32) -> Result<Precision, CalError> {
     match count {
         1 => Ok(Precision::First),
         2 => Ok(Precision::Second),
         3 => Ok(Precision::Third),
-        _ => Err("Precision is mismatch".to_string())
+        _ => Err(CalError::invalid_argument("precision_type", "must be 1, 2 or 3"))
     }
 }
 
@@ -643,14 +609,14 @@ fn get_precision(count: u32) -> Result<Precision, String> {
 
 // function core
 
-pub(crate) fn derivative_core<T: Differential>(
+pub fn derivative_core<T: Differential>(
     equation: &str,
     x: f64,
     h: f64,
     precision_type: u32, 
     diff_times: u32, 
     true_result: f64
-) -> Result<DerivativeResult, String> {
+) -> Result<DerivativeResult, CalError> {
     let method = T::new();
 
     let precision: Precision = get_precision(precision_type)?;
@@ -660,7 +626,7 @@ pub(crate) fn derivative_core<T: Differential>(
         2 => method.second_derivative(equation, x, h, precision),
         3 => method.third_derivative(equation, x, h, precision),
         4 => method.fourth_derivative(equation, x, h, precision),
-        _ => return Err(format!("Not implemented where derivative times is {}", diff_times))
+        _ => return Err(CalError::invalid_argument("diff_times", &format!("not implemented where derivative times is {}", diff_times)))
     };
 
     result.map(|value| DerivativeResult {
@@ -0,0 +1,93 @@
+// error.rs
+use std::fmt;
+
+use serde::Serialize;
+use serde_wasm_bindgen::to_value;
+use wasm_bindgen::prelude::*;
+
+
+
+/*
+ * ---- Error shared by every _core function ----
+ *
+ *  Serialized to JS as object which has `kind` as variant name
+ *  and the variant fields as properties, e.g.
+ *      { kind: "DimensionMismatch", expected: 3, got: 2 }
+ *
+ *  position in ParseError is character offset of failing token (None if unknown)
+ */
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind")]
+pub enum CalError {
+    ParseError { position: Option<usize>, token: Option<String>, reason: String },
+    DimensionMismatch { expected: usize, got: usize },
+    NotSquare { rows: usize, cols: usize },
+    InsufficientData { required: usize, got: usize },
+    Singular,
+    NotPositiveDefinite,
+    Inconsistent,
+    ZeroDiagonal { index: usize },
+    OutOfDomain { value: f64, lower: f64, upper: f64 },
+    InvalidInterval { xl: f64, xr: f64 },
+    NoSignChange { xl: f64, xr: f64 },
+    InvalidArgument { name: String, reason: String },
+    UnknownVariable { name: String },
+    UnknownFunction { name: String },
+    EvaluationFailed { x: f64, reason: String },
+    NonFiniteValue { x: f64, value: f64 }
+}
+
+
+
+impl CalError {
+    pub(crate) fn invalid_argument(name: &str, reason: &str) -> Self {
+        CalError::InvalidArgument {
+            name: name.to_string(),
+            reason: reason.to_string()
+        }
+    }
+}
+
+impl fmt::Display for CalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CalError::ParseError { position: Some(pos), reason, .. } => write!(f, "Invalid function: {} at {}", reason, pos),
+            CalError::ParseError { position: None, reason, .. }      => write!(f, "Invalid function: {}", reason),
+            CalError::DimensionMismatch { expected, got }            => write!(f, "Dimension mismatch: expected {} but got {}", expected, got),
+            CalError::NotSquare { rows, cols }                       => write!(f, "Matrix is not square: {} * {}", rows, cols),
+            CalError::InsufficientData { required, got }             => write!(f, "Must have at least {} points, got {}", required, got),
+            CalError::Singular                                       => write!(f, "Matrix is singular (no unique solution)"),
+            CalError::NotPositiveDefinite                            => write!(f, "Matrix is not positive definite"),
+            CalError::Inconsistent                                   => write!(f, "Inconsistent system (no solution)"),
+            CalError::ZeroDiagonal { index }                         => write!(f, "Matrix's diagonal element at {} is 0", index),
+            CalError::OutOfDomain { value, lower, upper }            => write!(f, "Value {} is out of [{}, {}]", value, lower, upper),
+            CalError::InvalidInterval { xl, xr }                     => write!(f, "Invalid interval: xl: {} must be less than xr: {}", xl, xr),
+            CalError::NoSignChange { xl, xr }                        => write!(f, "f(x) has the same sign at xl: {} and xr: {}", xl, xr),
+            CalError::InvalidArgument { name, reason }               => write!(f, "Invalid {}: {}", name, reason),
+            CalError::UnknownVariable { name }                       => write!(f, "Unknown variable: {}", name),
+            CalError::UnknownFunction { name }                       => write!(f, "Unknown function: {}", name),
+            CalError::EvaluationFailed { x, reason }                 => write!(f, "Evaluation failed at x = {}: {}", x, reason),
+            CalError::NonFiniteValue { x, value }                    => write!(f, "Function is not finite at x = {}: {}", x, value),
+        }
+    }
+}
+
+impl std::error::Error for CalError {}
+
+// Position unknown here, use expression::parse_expr to locate the failing token
+impl From<meval::Error> for CalError {
+    fn from(e: meval::Error) -> Self {
+        CalError::ParseError {
+            position: None,
+            token: None,
+            reason: e.to_string()
+        }
+    }
+}
+
+// wasm conversion JsValue
+impl From<CalError> for JsValue {
+    fn from(e: CalError) -> Self {
+        to_value(&e).unwrap_or_else(|_| JsValue::from_str(&e.to_string()))
+    }
+}
@@ -0,0 +1,309 @@
+// expression.rs
+use crate::utils;
+use crate::error::CalError;
+
+use meval::{ContextProvider, Expr, FuncEvalError};
+use meval::tokenizer::{tokenize, ParseError};
+use serde::Serialize;
+use serde_wasm_bindgen::to_value;
+use wasm_bindgen::prelude::*;
+
+
+
+// Duplication struct
+
+#[derive(Serialize)] // Serialize the struct
+pub struct Symbol {
+    pub name: String,
+    pub position: usize
+}
+
+// position & token are pointed at the first parse error
+#[derive(Serialize)] // Serialize the struct
+pub struct ValidationResult {
+    pub valid: bool,
+    pub reason: Option<String>,
+    pub token: Option<String>,
+    pub position: Option<usize>,
+    pub unknown_variables: Vec<Symbol>,
+    pub unknown_functions: Vec<Symbol>
+}
+
+// value is Some for constant, params is empty for constant
+#[derive(Serialize)] // Serialize the struct
+pub struct Definition {
+    pub name: String,
+    pub params: Vec<String>,
+    pub body: String,
+    pub value: Option<f64>
+}
+
+// Lexeme of the source string by character offset
+struct Lexeme {
+    text: String,
+    position: usize,
+    is_func: bool
+}
+
+
+
+// wasm conversion JsValue
+
+#[wasm_bindgen]
+pub fn validate_expression(equation: &str, variables: Vec<String>) -> JsValue {
+    let result = validate_expression_core(equation, &variables);
+    to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+}
+
+#[wasm_bindgen]
+pub fn evaluate_expression(equation: &str, variables: Vec<String>, values: Vec<f64>) -> JsValue {
+    match evaluate_expression_core(equation, &variables, &values) {
+        Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
+        Err(e) => e.into(),
+    }
+}
+
+
+
+// "g = 9.81" or "f(t) = t^2 + 1", kept until clear_definitions (page reload)
+#[wasm_bindgen]
+pub fn define(definition: &str) -> JsValue {
+    match define_core(definition) {
+        Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
+        Err(e) => e.into(),
+    }
+}
+
+#[wasm_bindgen]
+pub fn clear_definitions() {
+    utils::reset_context();
+}
+
+
+
+// Add implement method
+
+// variables are names bound by the method (e.g. "x"), others are looked up in utils context
+pub fn validate_expression_core(equation: &str, variables: &[String]) -> ValidationResult {
+    let (reason, token, position) = match parse_expr(equation) {
+        Ok(_)  => (None, None, None),
+        Err(CalError::ParseError { position, token, reason }) => (Some(reason), token, position),
+        Err(e) => (Some(e.to_string()), None, None),
+    };
+
+    let mut unknown_variables : Vec<Symbol> = Vec::new();
+    let mut unknown_functions : Vec<Symbol> = Vec::new();
+
+    utils::with_context(|ctx| {
+        for lexeme in scan_identifiers(equation) {
+            if lexeme.is_func {
+                // wrong arguments count is still a known function
+                if let Err(FuncEvalError::UnknownFunction) = ctx.eval_func(&lexeme.text, &[0.0]) {
+                    unknown_functions.push(Symbol { name: lexeme.text, position: lexeme.position });
+                }
+            }
+            else if !variables.contains(&lexeme.text) && ctx.get_var(&lexeme.text).is_none() {
+                unknown_variables.push(Symbol { name: lexeme.text, position: lexeme.position });
+            }
+        }
+    });
+
+    ValidationResult {
+        valid: reason.is_none() && unknown_variables.is_empty() && unknown_functions.is_empty(),
+        reason,
+        token,
+        position,
+        unknown_variables,
+        unknown_functions
+    }
+}
+
+// values are bound to variables by index
+pub fn evaluate_expression_core(equation: &str, variables: &[String], values: &[f64]) -> Result<f64, CalError> {
+    if variables.len() != values.len() {
+        return Err(CalError::DimensionMismatch { expected: variables.len(), got: values.len() });
+    }
+
+    let expr   : Expr       = parse_expr(equation)?;
+    let vars   : Vec<&str>  = variables.iter().map(|var| var.as_str()).collect();
+    let f                   = utils::compile_expr(expr, &vars)?;
+    let result : f64        = f(values)?;
+
+    // same rule as evaluate_expr, reported at the first variable
+    if !result.is_finite() {
+        return Err(CalError::NonFiniteValue { x: values.first().copied().unwrap_or(f64::NAN), value: result });
+    }
+
+    Ok(result)
+}
+
+pub fn define_core(definition: &str) -> Result<Definition, CalError> {
+    let (head, body) = match definition.find('=') {
+        Some(index) => (&definition[..index], &definition[index + 1..]),
+        None        => return Err(CalError::invalid_argument("definition", "must be in form of `name = value` or `name(args) = expression`")),
+    };
+
+    let (name, params) = parse_definition_head(head)?;
+
+    // shift parse error position to the whole definition
+    let offset : usize = definition[..=head.len()].chars().count();
+    let expr   : Expr  = parse_expr(body).map_err(|e| match e {
+        CalError::ParseError { position, token, reason } => CalError::ParseError { position: position.map(|p| p + offset), token, reason },
+        e => e,
+    })?;
+
+    let value: Option<f64> = if params.is_empty() {
+        let value: f64 = utils::evaluate_expr_vars(&expr, &[])?;
+        utils::register_constant(&name, value)?;
+        Some(value)
+    }
+    else {
+        let vars: Vec<&str> = params.iter().map(|param| param.as_str()).collect();
+        utils::register_function(&name, &vars, expr)?;
+        None
+    };
+
+    Ok(Definition {
+        name,
+        params,
+        body: body.trim().to_string(),
+        value
+    })
+}
+
+// Parse with failing position (character offset) instead of plain "Invalid function"
+pub(crate) fn parse_expr(equation: &str) -> Result<Expr, CalError> {
+    let error = match tokenize(equation) {
+        Ok(_)  => match equation.parse::<Expr>() {
+            Ok(expr) => return Ok(expr),
+            Err(e)   => return Err(CalError::from(e)),
+        },
+        Err(e) => e,
+    };
+
+    let end: usize = equation.chars().count();
+
+    let (position, reason) = match error {
+        ParseError::UnexpectedToken(byte) => (equation.get(..byte).map_or(byte, |s| s.chars().count()), "Unexpected token".to_string()),
+        ParseError::MissingRParen(count)  => (end, format!("Missing {} right parenthesis", count)),
+        ParseError::MissingArgument       => (end, "Missing argument at the end of expression".to_string()),
+    };
+
+    Err(CalError::ParseError {
+        position: Some(position),
+        token: token_at(equation, position),
+        reason
+    })
+}
+
+
+
+// Calculations
+
+fn is_identifier(text: &str) -> bool {
+    let mut chars = text.chars();
+
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => chars.all(|c| c.is_ascii_alphanumeric() || c == '_'),
+        _ => false,
+    }
+}
+
+// "f(a, b)" => ("f", ["a", "b"]), "g" => ("g", [])
+fn parse_definition_head(head: &str) -> Result<(String, Vec<String>), CalError> {
+    let head: &str = head.trim();
+
+    let (name, params): (&str, Vec<String>) = match head.find('(') {
+        Some(index) => {
+            let args: &str = match head[index + 1..].strip_suffix(')') {
+                Some(args) => args,
+                None       => return Err(CalError::invalid_argument("definition", "missing right parenthesis after arguments")),
+            };
+            (head[..index].trim(), args.split(',').map(|arg| arg.trim().to_string()).collect())
+        },
+        None => (head, Vec::new()),
+    };
+
+    if !is_identifier(name) {
+        return Err(CalError::invalid_argument("definition", &format!("`{}` is not a valid name", name)));
+    }
+    for (i, param) in params.iter().enumerate() {
+        if !is_identifier(param) {
+            return Err(CalError::invalid_argument("definition", &format!("`{}` is not a valid argument name", param)));
+        }
+        if params[..i].contains(param) {
+            return Err(CalError::invalid_argument("definition", &format!("argument `{}` is repeated", param)));
+        }
+    }
+
+    Ok((name.to_string(), params))
+}
+
+// Text of the lexeme starting at character offset
+fn token_at(equation: &str, position: usize) -> Option<String> {
+    let rest: Vec<char> = equation.chars().skip(position).collect();
+    let first: char = *rest.first()?;
+
+    let text: String = if first.is_ascii_alphanumeric() || first == '_' || first == '.' {
+        rest.iter()
+            .take_while(|c| c.is_ascii_alphanumeric() || **c == '_' || **c == '.')
+            .collect()
+    }
+    else {
+        first.to_string()
+    };
+
+    Some(text)
+}
+
+// Identifier followed by "(" is counted as function (same rule as meval tokenizer)
+fn scan_identifiers(equation: &str) -> Vec<Lexeme> {
+    let chars      : Vec<char>   = equation.chars().collect();
+    let mut result : Vec<Lexeme> = Vec::new();
+    let mut i      : usize       = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if c.is_ascii_digit() || c == '.' {
+            // skip number including exponent part (1e-3)
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            if i < chars.len() && (chars[i] == 'e' || chars[i] == 'E') {
+                let mut j = i + 1;
+                if j < chars.len() && (chars[j] == '+' || chars[j] == '-') {
+                    j += 1;
+                }
+                if j < chars.len() && chars[j].is_ascii_digit() {
+                    i = j;
+                    while i < chars.len() && chars[i].is_ascii_digit() {
+                        i += 1;
+                    }
+                }
+            }
+        }
+        else if c.is_ascii_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+
+            let mut j = i;
+            while j < chars.len() && chars[j].is_whitespace() {
+                j += 1;
+            }
+
+            result.push(Lexeme {
+                text: chars[start..i].iter().collect(),
+                position: start,
+                is_func: j < chars.len() && chars[j] == '('
+            });
+        }
+        else {
+            i += 1;
+        }
+    }
+
+    result
+}
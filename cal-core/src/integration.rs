@@ -1,5 +1,7 @@
 // integration.rs
 use crate::utils;
+use crate::error::CalError;
+use crate::expression::parse_expr;
 
 use std::{f64::{self, consts::PI}};
 
@@ -13,25 +15,25 @@ use wasm_bindgen::prelude::*;
 // Duplication struct
 
 #[derive(Serialize)] // Serialize the struct
-pub(crate) struct IntegralResult {
-    pub(crate) true_result: f64,
-    pub(crate) result: f64,
-    pub(crate) error: f64
+pub struct IntegralResult {
+    pub true_result: f64,
+    pub result: f64,
+    pub error: f64
 }
 
 #[derive(Serialize)] // Serialize the struct
-pub(crate) struct RombergResult { 
-    pub(crate) result: Vec<Vec<f64>>,
-    pub(crate) error: Vec<Vec<f64>>
+pub struct RombergResult { 
+    pub result: Vec<Vec<f64>>,
+    pub error: Vec<Vec<f64>>
 }
 
 #[derive(Serialize)] // Serialize the struct
-pub(crate) struct GuassIntegralResult {
-    pub(crate) true_result: f64,
-    pub(crate) result: f64,
-    pub(crate) error: f64,
-    pub(crate) abscissas: Vec<f64>,
-    pub(crate) weight: Vec<f64>
+pub struct GuassIntegralResult {
+    pub true_result: f64,
+    pub result: f64,
+    pub error: f64,
+    pub abscissas: Vec<f64>,
+    pub weight: Vec<f64>
 }
 
 
@@ -42,7 +44,7 @@ pub(crate) struct GuassIntegralResult {
 pub fn trapezodial(equation: &str, bound_least: f64, bound_most: f64, trapezoid_count: usize, true_result: f64) -> JsValue {
     match trapezodial_core(equation, bound_least, bound_most, trapezoid_count, true_result) {
         Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
-        Err(e) => JsValue::from_str(&e),
+        Err(e) => e.into(),
     }
 }
 
@@ -50,7 +52,7 @@ pub fn trapezodial(equation: &str, bound_least: f64, bound_most: f64, trapezoid_
 pub fn simpson_1in3(equation: &str, bound_least: f64, bound_most: f64, trapezoid_count: usize, true_result: f64) -> JsValue {
     match simpson_1in3_core(equation, bound_least, bound_most, trapezoid_count, true_result) {
         Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
-        Err(e) => JsValue::from_str(&e),
+        Err(e) => e.into(),
     }
 }
 
@@ -58,7 +60,7 @@ pub fn simpson_1in3(equation: &str, bound_least: f64, bound_most: f64, trapezoid
 pub fn simpson_3in8(equation: &str, bound_least: f64, bound_most: f64, trapezoid_count: usize, true_result: f64) -> JsValue {
     match simpson_3in8_core(equation, bound_least, bound_most, trapezoid_count, true_result) {
         Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
-        Err(e) => JsValue::from_str(&e),
+        Err(e) => e.into(),
     }
 }
 
@@ -66,7 +68,7 @@ pub fn simpson_3in8(equation: &str, bound_least: f64, bound_most: f64, trapezoid
 pub fn romberg(equation: &str, bound_least: f64, bound_most: f64) -> JsValue {
     match romberg_core(equation, bound_least, bound_most) {
         Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
-        Err(e) => JsValue::from_str(&e),
+        Err(e) => e.into(),
     }
 }
 
@@ -74,7 +76,7 @@ pub fn romberg(equation: &str, bound_least: f64, bound_most: f64) -> JsValue {
 pub fn guass_integration(equation: &str, bound_least: f64, bound_most: f64, true_result: f64, points: usize) -> JsValue {
     match guass_integration_core(equation, bound_least, bound_most, true_result, points) {
         Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
-        Err(e) => JsValue::from_str(&e),
+        Err(e) => e.into(),
     }
 }
 
@@ -82,19 +84,16 @@ pub fn guass_integration(equation: &str, bound_least: f64, bound_most: f64, true
 
 // Add implement method
 
-pub(crate) fn trapezodial_core
+pub fn trapezodial_core
 (
     equation: &str,
     bound_least: f64, 
     bound_most: f64, 
     trapezoid_count: usize, 
     true_result: f64
-) -> Result<IntegralResult, String> {
+) -> Result<IntegralResult, CalError> {
 
-    let expr: Expr = match equation.parse() {
-        Ok(e)  => e,
-        Err(_) => return Err("Invalid function".to_string()),
-    };
+    let expr: Expr = parse_expr(equation)?;
 
     let each_bound : f64 = (bound_most - bound_least) / (trapezoid_count as f64);
     let mut result : f64 = 0.0;
@@ -103,10 +102,10 @@ pub(crate) fn trapezodial_core
         let curr_bound: f64 = bound_least + i as f64 * each_bound;
 
         if i == 0 || i == trapezoid_count {
-            result += utils::evaluate_expr(&expr, curr_bound);
+            result += utils::evaluate_expr(&expr, curr_bound)?;
         }
         else {
-            result += 2.0 * utils::evaluate_expr(&expr, curr_bound);
+            result += 2.0 * utils::evaluate_expr(&expr, curr_bound)?;
         }
     }
 
@@ -122,19 +121,16 @@ pub(crate) fn trapezodial_core
     })
 }
 
-pub(crate) fn simpson_1in3_core
+pub fn simpson_1in3_core
 (
     equation: &str,
     bound_least: f64, 
     bound_most: f64, 
     mut trapezoid_count: usize, 
     true_result: f64
-) -> Result<IntegralResult, String> {
+) -> Result<IntegralResult, CalError> {
 
-    let expr: Expr = match equation.parse() {
-        Ok(e)  => e,
-        Err(_) => return Err("Invalid function".to_string()),
-    };
+    let expr: Expr = parse_expr(equation)?;
 
     // Single count not permitted
     if trapezoid_count == 1 {
@@ -148,13 +144,13 @@ pub(crate) fn simpson_1in3_core
         let curr_bound: f64 = bound_least + i as f64 * each_bound;
 
         if i == 0 || i == trapezoid_count {
-            result += utils::evaluate_expr(&expr, curr_bound);
+            result += utils::evaluate_expr(&expr, curr_bound)?;
         }
         else if i % 2 == 1 {
-            result += 4.0 * utils::evaluate_expr(&expr, curr_bound);
+            result += 4.0 * utils::evaluate_expr(&expr, curr_bound)?;
         }
         else if i % 2 == 0 {
-            result += 2.0 * utils::evaluate_expr(&expr, curr_bound);
+            result += 2.0 * utils::evaluate_expr(&expr, curr_bound)?;
         }
     }
 
@@ -170,19 +166,16 @@ pub(crate) fn simpson_1in3_core
     })
 }
 
-pub(crate) fn simpson_3in8_core
+pub fn simpson_3in8_core
 (
     equation: &str,
     bound_least: f64, 
     bound_most: f64, 
     mut trapezoid_count: usize, 
     true_result: f64
-) -> Result<IntegralResult, String> {
+) -> Result<IntegralResult, CalError> {
 
-    let expr: Expr = match equation.parse() {
-        Ok(e)  => e,
-        Err(_) => return Err("Invalid function".to_string()),
-    };
+    let expr: Expr = parse_expr(equation)?;
 
     // Single count not permitted
     if trapezoid_count == 1 {
@@ -196,15 +189,15 @@ pub(crate) fn simpson_3in8_core
         let curr_bound: f64 = bound_least + i as f64 * each_bound;
 
         if i == 0 || i == trapezoid_count {
-            result += utils::evaluate_expr(&expr, curr_bound);
+            result += utils::evaluate_expr(&expr, curr_bound)?;
             continue;
         }
         
         if i % 3 == 0 {
-            result += 2.0 * utils::evaluate_expr(&expr, curr_bound);
+            result += 2.0 * utils::evaluate_expr(&expr, curr_bound)?;
         }
         else {
-            result += 3.0 * utils::evaluate_expr(&expr, curr_bound);
+            result += 3.0 * utils::evaluate_expr(&expr, curr_bound)?;
         }
     }
 
@@ -220,23 +213,20 @@ pub(crate) fn simpson_3in8_core
     })
 }
 
-pub(crate) fn romberg_core 
+pub fn romberg_core 
 (
     equation: &str,
     bound_least: f64, 
     bound_most: f64, 
-) -> Result<RombergResult, String> {
+) -> Result<RombergResult, CalError> {
 
-    let expr: Expr = match equation.parse() {
-        Ok(e)  => e,
-        Err(_) => return Err("Invalid function".to_string()),
-    };
+    let expr: Expr = parse_expr(equation)?;
 
     let mut result : Vec<Vec<f64>> = Vec::new();
     let mut error  : Vec<Vec<f64>> = Vec::new();
     let mut height : f64           = bound_most - bound_least;
     
-    result.push(vec![trapezodial_calc(expr.clone(), bound_least, bound_most, height)]);
+    result.push(vec![trapezodial_calc(expr.clone(), bound_least, bound_most, height)?]);
     error.push(vec![f64::NAN]);
 
     for i in 1..=10 {
@@ -245,7 +235,7 @@ pub(crate) fn romberg_core
         result.push(vec![0.0; i + 1]);
         error.push(vec![f64::NAN; i + 1]);
 
-        result[i][0] = trapezodial_calc(expr.clone(), bound_least, bound_most, height);
+        result[i][0] = trapezodial_calc(expr.clone(), bound_least, bound_most, height)?;
 
         for j in 1..=i {
             result[i][j] = {
@@ -270,19 +260,16 @@ pub(crate) fn romberg_core
                });
 }
 
-pub(crate) fn guass_integration_core 
+pub fn guass_integration_core 
 (
     equation: &str,
     bound_least: f64,
     bound_most: f64,
     true_result: f64,
     points: usize
-) -> Result<GuassIntegralResult, String> {
+) -> Result<GuassIntegralResult, CalError> {
     // Substitute function
-    let expr: Expr = match equation.parse() {
-        Ok(e)  => e,
-        Err(_) => return Err("Invalid function".to_string()),
-    };
+    let expr: Expr = parse_expr(equation)?;
 
     // Fetch the "x" as {(a+b) / 2 + (b-a) / 2 * zeta} To expand [-1, 1] to [b, a]
     let subs_bound : f64      = (bound_most - bound_least) / 2.0;
@@ -293,7 +280,7 @@ pub(crate) fn guass_integration_core
     let mut result = 0.0;
     for i in 0..points {
         let expression = mean_bound + subs_bound * abscissas[i];
-        result += weight[i] * utils::evaluate_expr(&expr, expression);
+        result += weight[i] * utils::evaluate_expr(&expr, expression)?;
     }
     result *= subs_bound;
 
@@ -318,21 +305,21 @@ fn trapezodial_calc(
     bound_least: f64, 
     bound_most: f64, 
     height: f64, 
-) -> f64 {
+) -> Result<f64, CalError> {
 
     let mut result : f64 = 0.0;
 
-    result += utils::evaluate_expr(&expr, bound_least);
-    result += utils::evaluate_expr(&expr, bound_most);
+    result += utils::evaluate_expr(&expr, bound_least)?;
+    result += utils::evaluate_expr(&expr, bound_most)?;
 
     let mut inner_bound = bound_least + height;
     while inner_bound < bound_most {
-        result += 2.0 * utils::evaluate_expr(&expr, inner_bound);
+        result += 2.0 * utils::evaluate_expr(&expr, inner_bound)?;
         inner_bound += height;
     }
 
     // trapezoidal calculation (h / 2 * sum)
-    height / 2.0 * result
+    Ok(height / 2.0 * result)
 }
 
 fn legendre_polynomial(n: usize, x: f64) -> f64 {
@@ -1,5 +1,6 @@
 // interpolation.rs
 use crate::utils::{self};
+use crate::error::CalError;
 
 use serde::Serialize;
 use serde_wasm_bindgen::to_value;
@@ -10,18 +11,18 @@ use wasm_bindgen::prelude::*;
 // Duplication struct
 
 #[derive(Serialize)] // Serialize the struct
-pub(crate) struct InterpolationResult {
-    pub(crate) coefficient: Vec<f64>,
-    pub(crate) target_y: f64,
-    pub(crate) error: f64
+pub struct InterpolationResult {
+    pub coefficient: Vec<f64>,
+    pub target_y: f64,
+    pub error: f64
 }
 
 #[derive(Serialize)]
-pub(crate) struct SplineResult {
-    pub(crate) equation: Vec<Vec<f64>>,
-    pub(crate) guass_result: Option<Vec<f64>>,
-    pub(crate) target_y: f64,
-    pub(crate) error: f64
+pub struct SplineResult {
+    pub equation: Vec<Vec<f64>>,
+    pub guass_result: Option<Vec<f64>>,
+    pub target_y: f64,
+    pub error: f64
 }
 
 
@@ -32,7 +33,7 @@ pub(crate) struct SplineResult {
 pub fn newton_divided(x: Vec<f64>, y: Vec<f64>, target_x: f64) -> JsValue {
     match newton_divided_core(x, y, target_x) {
         Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
-        Err(e) => JsValue::from_str(&e),
+        Err(e) => e.into(),
     }
 }
 
@@ -40,7 +41,7 @@ pub fn newton_divided(x: Vec<f64>, y: Vec<f64>, target_x: f64) -> JsValue {
 pub fn lagrange(x: Vec<f64>, y: Vec<f64>, target_x: f64) -> JsValue {
     match lagrange_core(x, y, target_x) {
         Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
-        Err(e) => JsValue::from_str(&e),
+        Err(e) => e.into(),
     }
 }
 
@@ -48,7 +49,7 @@ pub fn lagrange(x: Vec<f64>, y: Vec<f64>, target_x: f64) -> JsValue {
 pub fn spline(x: Vec<f64>, y: Vec<f64>, target_x: f64, degree: u32) -> JsValue {
     match spline_core(x, y, target_x, degree) {
         Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
-        Err(e) => JsValue::from_str(&e),
+        Err(e) => e.into(),
     }
 }
 
@@ -56,12 +57,12 @@ pub fn spline(x: Vec<f64>, y: Vec<f64>, target_x: f64, degree: u32) -> JsValue {
 
 // Add implement method (exact value calculated as linear interpolation)
 
-pub(crate) fn newton_divided_core(x: Vec<f64>, y: Vec<f64>, target_x: f64) -> Result<InterpolationResult, String> {
+pub fn newton_divided_core(x: Vec<f64>, y: Vec<f64>, target_x: f64) -> Result<InterpolationResult, CalError> {
     if x.len() != y.len() {
-        return Err("X & Y is not matches".to_string());
+        return Err(CalError::DimensionMismatch { expected: x.len(), got: y.len() });
     }
     if x.len() < 2 || y.len() < 2 {
-        return Err("Must have at least 2 points".to_string());
+        return Err(CalError::InsufficientData { required: 2, got: x.len() });
     }
 
     // Sorted by x for easier calculation
@@ -72,7 +73,7 @@ pub(crate) fn newton_divided_core(x: Vec<f64>, y: Vec<f64>, target_x: f64) -> Re
     
     // Terminal point
     if target_x < sorted_x[0] || target_x > sorted_x[degree] {
-        return Err("target value is outbounded".to_string());
+        return Err(CalError::OutOfDomain { value: target_x, lower: sorted_x[0], upper: sorted_x[degree] });
     }
      
     // Co-efficient calculation
@@ -104,12 +105,12 @@ pub(crate) fn newton_divided_core(x: Vec<f64>, y: Vec<f64>, target_x: f64) -> Re
     })
 }
 
-pub(crate) fn lagrange_core(x: Vec<f64>, y: Vec<f64>, target_x: f64) -> Result<InterpolationResult, String> {
+pub fn lagrange_core(x: Vec<f64>, y: Vec<f64>, target_x: f64) -> Result<InterpolationResult, CalError> {
     if x.len() != y.len() {
-        return Err("X & Y is not matches".to_string());
+        return Err(CalError::DimensionMismatch { expected: x.len(), got: y.len() });
     }
     if x.len() < 2 || y.len() < 2 {
-        return Err("Must have at least 2 points".to_string());
+        return Err(CalError::InsufficientData { required: 2, got: x.len() });
     }
 
     // Sorted by x for easier calculation
@@ -120,7 +121,7 @@ pub(crate) fn lagrange_core(x: Vec<f64>, y: Vec<f64>, target_x: f64) -> Result<I
     
     // Terminal point
     if target_x < sorted_x[0] || target_x > sorted_x[degree] {
-        return Err("target value is outbounded".to_string());
+        return Err(CalError::OutOfDomain { value: target_x, lower: sorted_x[0], upper: sorted_x[degree] });
     }
      
     // Co-efficient calculation
@@ -149,31 +150,25 @@ pub(crate) fn lagrange_core(x: Vec<f64>, y: Vec<f64>, target_x: f64) -> Result<I
     })
 }
 
-pub(crate) fn spline_core(x: Vec<f64>, y: Vec<f64>, target_x: f64, degree: u32) -> Result<SplineResult, String> {
+pub fn spline_core(x: Vec<f64>, y: Vec<f64>, target_x: f64, degree: u32) -> Result<SplineResult, CalError> {
     if x.len() != y.len() {
-        return Err("X & Y is not matches".to_string());
+        return Err(CalError::DimensionMismatch { expected: x.len(), got: y.len() });
     }
     if x.len() < 2 || y.len() < 2 {
-        return Err("Must have at least 2 points".to_string());
+        return Err(CalError::InsufficientData { required: 2, got: x.len() });
     }
 
     // Sorted by x for easier calculation
     let (sorted_x, sorted_y) = utils::pair_sort_asc(x.clone(), y.clone(), 1);
     
     if target_x < sorted_x[0] || target_x > sorted_x[sorted_x.len() - 1] {
-        return Err("target is out of bounded".to_string());
+        return Err(CalError::OutOfDomain { value: target_x, lower: sorted_x[0], upper: sorted_x[sorted_x.len() - 1] });
     }
 
     match degree {
-        1 => match spline_linear(&sorted_x, &sorted_y, target_x) {
-            Ok(result) => return Ok(result),
-            Err(e) => return Err(e),
-        }
-        2 | 3 => match spline_polynomial(&sorted_x, &sorted_y, degree, target_x) {
-            Ok(result) => return Ok(result),
-            Err(e) => return Err(e),
-        }
-        _ => return Err("Method type is mismatch".to_string()),
+        1     => spline_linear(&sorted_x, &sorted_y, target_x),
+        2 | 3 => spline_polynomial(&sorted_x, &sorted_y, degree, target_x),
+        _     => Err(CalError::invalid_argument("degree", "must be 1, 2 or 3")),
     }
 }
 
@@ -204,14 +199,14 @@ fn exact_linear_interpolation(x: &Vec<f64>, y: &Vec<f64>, target_x: f64) -> f64
     y0 + (y1 - y0) * (target_x - x0) / (x1 - x0)
 }
 
-fn spline_linear(x: &Vec<f64>, y: &Vec<f64>, target_x: f64) -> Result<SplineResult, String> {
+fn spline_linear(x: &Vec<f64>, y: &Vec<f64>, target_x: f64) -> Result<SplineResult, CalError> {
     // Sorted value will be using here
     let mut result: Vec<Vec<f64>> = Vec::new();
 
     for i in 1..x.len() {
         // guard condition
         if x[i] == x[i - 1] {
-            return Err(format!("Duplicate x value where x[{}] == x[{}]", i - 1, i));
+            return Err(CalError::invalid_argument("x", &format!("duplicate x value where x[{}] == x[{}]", i - 1, i)));
         }
 
         let m: f64 = (y[i] - y[i - 1]) / (x[i] - x[i - 1]);
@@ -242,7 +237,7 @@ fn spline_linear(x: &Vec<f64>, y: &Vec<f64>, target_x: f64) -> Result<SplineResu
     })
 }
 
-fn spline_polynomial(x: &Vec<f64>, y: &Vec<f64>, degree: u32, target_x: f64) -> Result<SplineResult, String> {
+fn spline_polynomial(x: &Vec<f64>, y: &Vec<f64>, degree: u32, target_x: f64) -> Result<SplineResult, CalError> {
    
     // size is counted by degree e.g. degree 2 result: a^2x + bx + c ( unknown is degree + 1 )
     let line_size = degree as usize + 1;
@@ -1,14 +1,31 @@
+mod autodiff;
+mod complex;
+mod convergence;
 mod differential;
+mod error;
+mod expression;
 mod integration;
 mod interpolation;
 mod linear_eq;
+mod matrix;
+mod nonlinear;
+mod polynomial;
 mod regression;
 mod root_eq;
+mod special;
+mod symbolic;
 mod utils;
 
+pub use convergence::*;
 pub use differential::*;
+pub use error::CalError;
+pub use expression::*;
 pub use integration::*;
 pub use interpolation::*;
 pub use linear_eq::*;
+pub use matrix::*;
+pub use nonlinear::*;
+pub use polynomial::*;
 pub use regression::*;
 pub use root_eq::*;
+pub use symbolic::*;
@@ -1,5 +1,8 @@
 // linear_eq.rs
 use crate::utils::{self};
+use crate::error::CalError;
+use crate::matrix::Matrix;
+use crate::convergence::{IterativeResult, Outcome, StopCriterion};
 
 use serde::Serialize;
 use serde_wasm_bindgen::to_value;
@@ -9,99 +12,231 @@ use wasm_bindgen::prelude::*;
 
 // Duplication struct
 
+/*
+ * ---- Direct solvers A X = B ----
+ *
+ *  ans is B flat row-major with ans_cols columns (1 for a single b)
+ *  value is X (rows * ans_cols), column c solves A x = b_c
+ */
+
+// det_iter[c][i] is det of A with column i replaced by b_c
+#[derive(Serialize)] // Serialize the struct
+pub struct CramerResult {
+    pub det_true: f64,
+    pub det_iter: Vec<Vec<f64>>,
+    pub value: Matrix
+}
+
+/*
+ *  Row operation of elimination, rows are 0-based indices of the augmented matrix
+ *      Swap     : R_row <-> R_other
+ *      Scale    : R_row = R_row / pivot
+ *      Subtract : R_row = R_row - ratio * R_source
+ *  Serialized with `kind` as variant name, e.g. { kind: "Swap", row: 0, other: 2 }
+ */
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(tag = "kind")]
+pub enum RowOperation {
+    Swap { row: usize, other: usize },
+    Scale { row: usize, pivot: f64 },
+    Subtract { row: usize, source: usize, ratio: f64 }
+}
+
+// matrix is the augmented matrix after the operation
 #[derive(Serialize)] // Serialize the struct
-pub(crate) struct CramerResult {
-    pub(crate) det_true: f64,
-    pub(crate) det_iter: Vec<f64>,
-    pub(crate) value: Vec<f64>
+pub struct EliminationStep {
+    pub step: usize,
+    pub operation: RowOperation,
+    pub matrix: Matrix
 }
 
+// steps is None when rows > MAX_TRACE_ROWS (snapshots grow as rows^4)
 #[derive(Serialize)] // Serialize the struct
-pub(crate) struct GuassResult {
-    pub(crate) value: Vec<f64>
+pub struct GuassResult {
+    pub steps: Option<Vec<EliminationStep>>,
+    pub value: Matrix
 } 
 
 #[derive(Serialize)] // Serialize the struct
-pub(crate) struct InverseResult {
-    pub(crate) inverse_mat: Vec<Vec<f64>>,
-    pub(crate) value: Vec<f64>
+pub struct InverseResult {
+    pub steps: Option<Vec<EliminationStep>>,
+    pub inverse_mat: Matrix,
+    pub value: Matrix
+}
+
+// permutation is None for Cholesky (no pivoting), forward_value column c is y of b_c
+#[derive(Serialize)] // Serialize the struct
+pub struct DecompositionResult {
+    pub(crate) permutation: Option<Vec<usize>>,
+    pub lower_mat: Matrix,
+    pub upper_mat: Matrix,
+    pub forward_value: Matrix,
+    pub backward_value: Matrix
 }
 
+/*
+ *  P A = L U with partial pivoting (Crout form: U has unit diagonal)
+ *  permutation[i] is the row of A moved to row i, permutation_mat is P
+ *  Factor once, then solve() for every right-hand side
+ */
 #[derive(Serialize)] // Serialize the struct
-pub(crate) struct DecompositionResult {
-    pub(crate) lower_mat: Vec<Vec<f64>>,
-    pub(crate) upper_mat: Vec<Vec<f64>>,
-    pub(crate) forward_value: Vec<f64>,
-    pub(crate) backward_value: Vec<f64>
+pub struct LuFactorization {
+    pub permutation: Vec<usize>,
+    pub permutation_mat: Matrix,
+    pub lower_mat: Matrix,
+    pub upper_mat: Matrix
 }
 
+// lu_solve result, column c is y / x of b_c
 #[derive(Serialize)] // Serialize the struct
-pub(crate) struct LinearIterationResult {
-    pub(crate) iteration: u64,
-    pub(crate) x: Vec<f64>,
-    pub(crate) error: f64
+pub struct LuSolveResult {
+    pub(crate) forward_value: Matrix,
+    pub backward_value: Matrix
+}
+
+// Householder : reflections I - 2 v v^T, ModifiedGramSchmidt : columns orthogonalized one by one
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub enum QrMethod {
+    Householder,
+    ModifiedGramSchmidt
 }
 
+/*
+ *  A = Q R for rows >= cols (thin form, both methods give the same factors)
+ *  Q is rows * cols with orthonormal columns, R is cols * cols upper triangular with r_ii > 0
+ */
 #[derive(Serialize)] // Serialize the struct
-pub(crate) struct ConjugateResult {
-    pub(crate) iteration: u64,
-    pub(crate) x: Vec<f64>,
-    pub(crate) residual: Vec<f64>,
-    pub(crate) direction: Vec<f64>,
-    pub(crate) error: f64,
-    pub(crate) lambda: f64,
-    pub(crate) alpha: f64
+pub struct QrFactorization {
+    pub method: QrMethod,
+    pub q_mat: Matrix,
+    pub r_mat: Matrix
 }
 
+// value minimizes ||A x - b||_2, residual_norm is that minimum
+#[derive(Serialize)] // Serialize the struct
+pub struct LeastSquaresResult {
+    pub method: QrMethod,
+    pub(crate) q_mat: Matrix,
+    pub(crate) r_mat: Matrix,
+    pub value: Vec<f64>,
+    pub residual_norm: f64
+}
+
+#[derive(Serialize)] // Serialize the struct
+pub struct LinearIterationResult {
+    pub iteration: u64,
+    pub x: Vec<f64>,
+    pub error: f64
+}
+
+#[derive(Serialize)] // Serialize the struct
+pub struct ConjugateResult {
+    pub iteration: u64,
+    pub x: Vec<f64>,
+    pub residual: Vec<f64>,
+    pub direction: Vec<f64>,
+    pub error: f64,
+    pub lambda: f64,
+    pub alpha: f64
+}
+
+// Largest system that keeps the elimination trace
+const MAX_TRACE_ROWS: usize = 20;
+
 
 
 // wasm conversion JsValue
 
 #[wasm_bindgen]
-pub fn cramer(mat: Vec<f64>, rows: usize, ans: Vec<f64>) -> JsValue {
-    match cramer_core(mat, rows, ans) {
+pub fn cramer(mat: Vec<f64>, rows: usize, ans: Vec<f64>, ans_cols: usize) -> JsValue {
+    match cramer_core(mat, rows, ans, ans_cols) {
+        Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
+        Err(e) => e.into(),
+    }
+}
+
+#[wasm_bindgen]
+pub fn guass_naive(mat: Vec<f64>, rows: usize, ans: Vec<f64>, ans_cols: usize) -> JsValue {
+    match guass_naive_core(mat, rows, ans, ans_cols) {
+        Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
+        Err(e) => e.into(),
+    }
+}
+
+#[wasm_bindgen]
+pub fn guass_jordan(mat: Vec<f64>, rows: usize, ans: Vec<f64>, ans_cols: usize) -> JsValue {
+    match guass_jordan_core(mat, rows, ans, ans_cols) {
         Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
-        Err(e) => JsValue::from_str(&e),
+        Err(e) => e.into(),
     }
 }
 
 #[wasm_bindgen]
-pub fn guass_naive(mat: Vec<f64>, rows: usize, ans: Vec<f64>) -> JsValue {
-    match guass_naive_core(mat, rows, ans) {
+pub fn inverse_matrix(mat: Vec<f64>, rows: usize, ans: Vec<f64>, ans_cols: usize) -> JsValue {
+    match inverse_matrix_core(mat, rows, ans, ans_cols) {
         Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
-        Err(e) => JsValue::from_str(&e),
+        Err(e) => e.into(),
     }
 }
 
 #[wasm_bindgen]
-pub fn guass_jordan(mat: Vec<f64>, rows: usize, ans: Vec<f64>) -> JsValue {
-    match guass_jordan_core(mat, rows, ans) {
+pub fn lu_decomposition(mat: Vec<f64>, rows: usize, ans: Vec<f64>, ans_cols: usize) -> JsValue {
+    match lu_decomposition_core(mat, rows, ans, ans_cols) {
         Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
-        Err(e) => JsValue::from_str(&e),
+        Err(e) => e.into(),
     }
 }
 
+// P, L, U and the permutation without solving
 #[wasm_bindgen]
-pub fn inverse_matrix(mat: Vec<f64>, rows: usize, ans: Vec<f64>) -> JsValue {
-    match inverse_matrix_core(mat, rows, ans) {
+pub fn lu_factorize(mat: Vec<f64>, rows: usize) -> JsValue {
+    match lu_factorize_core(mat, rows) {
         Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
-        Err(e) => JsValue::from_str(&e),
+        Err(e) => e.into(),
     }
 }
 
+// permutation, lower, upper of lu_factorize (L, U flat row-major), substitution only
 #[wasm_bindgen]
-pub fn lu_decomposition(mat: Vec<f64>, rows: usize, ans: Vec<f64>) -> JsValue {
-    match lu_decomposition_core(mat, rows, ans) {
+pub fn lu_solve(permutation: Vec<usize>, lower: Vec<f64>, upper: Vec<f64>, ans: Vec<f64>, ans_cols: usize) -> JsValue {
+    match lu_solve_core(permutation, lower, upper, ans, ans_cols) {
         Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
-        Err(e) => JsValue::from_str(&e),
+        Err(e) => e.into(),
     }
 }
 
 #[wasm_bindgen]
-pub fn cholesky(mat: Vec<f64>, rows: usize, ans: Vec<f64>) -> JsValue {
-    match cholesky_core(mat, rows, ans) {
+pub fn cholesky(mat: Vec<f64>, rows: usize, ans: Vec<f64>, ans_cols: usize) -> JsValue {
+    match cholesky_core(mat, rows, ans, ans_cols) {
         Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
-        Err(e) => JsValue::from_str(&e),
+        Err(e) => e.into(),
+    }
+}
+
+// mat is rows * (mat.len() / rows), rows >= cols
+#[wasm_bindgen]
+pub fn qr_decomposition(mat: Vec<f64>, rows: usize, method_type: u32) -> JsValue {
+    let method: QrMethod = match qr_method(method_type) {
+        Ok(method) => method,
+        Err(e)     => return e.into(),
+    };
+
+    match qr_decomposition_core(mat, rows, method) {
+        Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
+        Err(e) => e.into(),
+    }
+}
+
+#[wasm_bindgen]
+pub fn least_squares(mat: Vec<f64>, rows: usize, ans: Vec<f64>, method_type: u32) -> JsValue {
+    let method: QrMethod = match qr_method(method_type) {
+        Ok(method) => method,
+        Err(e)     => return e.into(),
+    };
+
+    match least_squares_core(mat, rows, ans, method) {
+        Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
+        Err(e) => e.into(),
     }
 }
 
@@ -109,7 +244,7 @@ pub fn cholesky(mat: Vec<f64>, rows: usize, ans: Vec<f64>) -> JsValue {
 pub fn jacobi(mat: Vec<f64>, rows: usize, ans: Vec<f64>, init: Vec<f64>) -> JsValue {
     match jacobi_core(mat, rows, ans, init) {
         Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
-        Err(e) => JsValue::from_str(&e),
+        Err(e) => e.into(),
     }
 }
 
@@ -117,7 +252,7 @@ pub fn jacobi(mat: Vec<f64>, rows: usize, ans: Vec<f64>, init: Vec<f64>) -> JsVa
 pub fn guass_seidel(mat: Vec<f64>, rows: usize, ans: Vec<f64>, init: Vec<f64>) -> JsValue {
     match guass_seidel_core(mat, rows, ans, init) {
         Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
-        Err(e) => JsValue::from_str(&e),
+        Err(e) => e.into(),
     }
 }
 
@@ -125,7 +260,7 @@ pub fn guass_seidel(mat: Vec<f64>, rows: usize, ans: Vec<f64>, init: Vec<f64>) -
 pub fn over_relaxation(mat: Vec<f64>, rows: usize, ans: Vec<f64>, init: Vec<f64>, omega: f64) -> JsValue {
     match over_relaxation_core(mat, rows, ans, init, omega) {
         Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
-        Err(e) => JsValue::from_str(&e),
+        Err(e) => e.into(),
     }
 }
 
@@ -133,7 +268,7 @@ pub fn over_relaxation(mat: Vec<f64>, rows: usize, ans: Vec<f64>, init: Vec<f64>
 pub fn conjugate_gradient(mat: Vec<f64>, rows: usize, ans: Vec<f64>, init: Vec<f64>) -> JsValue {
     match cg_core(mat, rows, ans, init) {
         Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
-        Err(e) => JsValue::from_str(&e),
+        Err(e) => e.into(),
     }
 }
 
@@ -141,285 +276,248 @@ pub fn conjugate_gradient(mat: Vec<f64>, rows: usize, ans: Vec<f64>, init: Vec<f
 
 // Add implement method
 
-pub(crate) fn cramer_core(mat: Vec<f64>, rows: usize, ans: Vec<f64>) -> Result<CramerResult, String> {
+pub fn cramer_core(mat: Vec<f64>, rows: usize, ans: Vec<f64>, ans_cols: usize) -> Result<CramerResult, CalError> {
 
-    let cols: usize = mat.len() / rows;
+    let matrix: Matrix = Matrix::square_from_flat(mat, rows)?;
 
-    if rows != cols {
-        return Err(format!("Matrix is not square: {} * {}", rows, cols));
-    }
+    let answer: Matrix = answer_matrix(ans, rows, ans_cols)?;
 
-    let matrix      : Vec<Vec<f64>> = utils::mat_conv2d(&mat, rows);
-    let det_true    : f64           = utils::det(&matrix);
-    let mut det_iter: Vec<f64>      = Vec::new();
-    let mut answer  : Vec<f64>      = Vec::new();
+    let det_true    : f64           = matrix.det();
+    let mut det_iter: Vec<Vec<f64>> = Vec::new();
+    let mut value   : Matrix        = Matrix::zeros(rows, ans_cols);
+    let mut mod_mat : Matrix        = matrix.clone();
 
 
     if det_true.abs() < 1e-12 {
-        return Err("The determinant of true matrix is 0".to_string());
+        return Err(CalError::Singular);
     }
     /*
      * rows is named as size  
      * iter is start when passed at true det
      */
-    for i in 0..rows {
-        let mut mod_mat = matrix.clone();
-        for j in 0..rows {
-            mod_mat[j][i] = ans[j];
-        }
+    for c in 0..ans_cols {
+        let b        : Vec<f64> = answer.column(c);
+        let mut dets : Vec<f64> = Vec::new();
 
-        det_iter.push(utils::det(&mod_mat));
-    }
+        for i in 0..rows {
+            mod_mat.copy_from(&matrix);
+            mod_mat.set_column(i, &b);
+
+            dets.push(mod_mat.det_in_place());
+        }
 
-    for det in &det_iter {
-        answer.push(det / det_true);
+        for (i, det) in dets.iter().enumerate() {
+            value[(i, c)] = det / det_true;
+        }
+        det_iter.push(dets);
     }
 
 
     let result: CramerResult = CramerResult{
         det_true,
         det_iter,
-        value: answer
+        value
     };
 
     Ok(result)
 }
 
-pub(crate) fn guass_naive_core(mat: Vec<f64>, rows: usize, ans: Vec<f64>) -> Result<GuassResult, String> {
-
-    let cols: usize = mat.len() / rows;
+pub fn guass_naive_core(mat: Vec<f64>, rows: usize, ans: Vec<f64>, ans_cols: usize) -> Result<GuassResult, CalError> {
 
-    if rows != cols {
-        return Err(format!("Matrix is not square: {} * {}", rows, cols));
-    }
+    let matrix: Matrix = Matrix::square_from_flat(mat, rows)?;
 
-    let matrix          : Vec<Vec<f64>> = utils::mat_conv2d(&mat, rows);
-    let mut aug_matrix  : Vec<Vec<f64>> = matrix.clone();
-    let size            : usize         = matrix.len();
+    let answer: Matrix = answer_matrix(ans, rows, ans_cols)?;
 
-    for i in 0..rows {
-        aug_matrix[i].push(ans[i]);
-    }
+    let mut aug_matrix  : Matrix           = matrix.augment(&answer)?;
+    let mut trace       : EliminationTrace = EliminationTrace::new(rows);
+    let size            : usize            = rows;
 
     for i in 0..size {
-        let mut max_row: usize = i;
-        for j in i+1 ..size {
-            if aug_matrix[j][i].abs() >aug_matrix[max_row][i].abs() {
-                max_row = j;
-            }
-        }
+        let max_row: usize = aug_matrix.pivot_row(i, i);
 
         if max_row != i {
-            aug_matrix.swap(i, max_row);
+            trace.swap(&mut aug_matrix, i, max_row);
         }
 
-        if aug_matrix[i][i].abs() < 1e-12 {
-            return Err("Matrix is no unique solution".to_string());
+        if aug_matrix[(i, i)].abs() < 1e-12 {
+            return Err(CalError::Singular);
         }
 
 
         for j in i + 1..size {
-            let ratio: f64 = aug_matrix[j][i] / aug_matrix[i][i];
-            for k in i..=size {
-                aug_matrix[j][k] -= ratio * aug_matrix[i][k];
-            }
+            let ratio: f64 = aug_matrix[(j, i)] / aug_matrix[(i, i)];
+            trace.subtract(&mut aug_matrix, j, i, ratio, i);
         }
     }
 
-    let mut solution: Vec<f64> = vec![0.0; rows];
-    for i in (0..size).rev() {
-        solution[i] = aug_matrix[i][size] / aug_matrix[i][i];
-        for j in (0..i).rev() {
-            aug_matrix[j][size] -= aug_matrix[j][i] * solution[i];
+    // back substitution per right-hand side (column size + c)
+    let mut solution: Matrix = Matrix::zeros(rows, ans_cols);
+    for c in 0..ans_cols {
+        let col: usize = size + c;
+        for i in (0..size).rev() {
+            solution[(i, c)] = aug_matrix[(i, col)] / aug_matrix[(i, i)];
+            for j in (0..i).rev() {
+                aug_matrix[(j, col)] -= aug_matrix[(j, i)] * solution[(i, c)];
+            }
         }
     }
 
     Ok(GuassResult {
+        steps: trace.steps,
         value: solution
     })
 }
 
-pub(crate) fn guass_jordan_core(mat: Vec<f64>, rows: usize, ans: Vec<f64>) -> Result<GuassResult, String> {
+pub fn guass_jordan_core(mat: Vec<f64>, rows: usize, ans: Vec<f64>, ans_cols: usize) -> Result<GuassResult, CalError> {
 
-    let cols: usize = mat.len() / rows;
+    let matrix: Matrix = Matrix::square_from_flat(mat, rows)?;
 
-    if rows != cols {
-        return Err(format!("Matrix is not square: {} * {}", rows, cols));
-    }
+    let answer: Matrix = answer_matrix(ans, rows, ans_cols)?;
 
     const EPSILON: f64 = 1e-32;
 
-    let matrix          : Vec<Vec<f64>> = utils::mat_conv2d(&mat, rows);
-    let mut aug_matrix  : Vec<Vec<f64>> = matrix.clone();
-    let size            : usize         = matrix.len();
-
-    for i in 0..rows {
-        aug_matrix[i].push(ans[i]);
-    }
+    let mut aug_matrix  : Matrix           = matrix.augment(&answer)?;
+    let mut trace       : EliminationTrace = EliminationTrace::new(rows);
+    let size            : usize            = rows;
 
     for i in 0..size {
-        let mut max_row: usize = i;
-        for j in i+1 ..size {
-            if aug_matrix[j][i].abs() >aug_matrix[max_row][i].abs() {
-                max_row = j;
-            }
-        }
+        let max_row: usize = aug_matrix.pivot_row(i, i);
 
         if max_row != i {
-            aug_matrix.swap(i, max_row);
+            trace.swap(&mut aug_matrix, i, max_row);
         }
 
-        if aug_matrix[i][i].abs() < EPSILON {
+        if aug_matrix[(i, i)].abs() < EPSILON {
             continue;
         }
 
-        let pivot: f64 = aug_matrix[i][i];
-        for j in 0..=size {
-            aug_matrix[i][j] /= pivot;
-        }
+        let pivot: f64 = aug_matrix[(i, i)];
+        trace.scale(&mut aug_matrix, i, pivot);
 
         for j in 0..size {
             if j != i {
-                let ratio: f64 = aug_matrix[j][i];
-                for k in 0..=size {
-                    aug_matrix[j][k] -= ratio * aug_matrix[i][k];
-                }
+                let ratio: f64 = aug_matrix[(j, i)];
+                trace.subtract(&mut aug_matrix, j, i, ratio, 0);
             }
         }
     }
 
-    for row in &aug_matrix {
+    for row in aug_matrix.row_iter() {
         let lhs_zero = row[..size].iter().all(|&v| v.abs() < EPSILON);
-        let rhs_nonzero = row[size].abs() >= EPSILON;
+        let rhs_nonzero = row[size..].iter().any(|&v| v.abs() >= EPSILON);
         if lhs_zero && rhs_nonzero {
-            return Err("Inconsistent system (no solution)".to_string());
+            return Err(CalError::Inconsistent);
         }
     }
 
-    let solution: Vec<f64> = aug_matrix.iter().map(|row| row[size]).collect();
+    let solution: Matrix = aug_matrix.view(0, size, size, ans_cols).to_matrix();
 
     Ok(GuassResult {
+        steps: trace.steps,
         value: solution
     })
 }
 
-pub(crate) fn inverse_matrix_core(mat: Vec<f64>, rows: usize, ans: Vec<f64>) -> Result<InverseResult, String> {
-    
-    let cols: usize = mat.len() / rows;
+pub fn inverse_matrix_core(mat: Vec<f64>, rows: usize, ans: Vec<f64>, ans_cols: usize) -> Result<InverseResult, CalError> {
 
-    if rows != cols {
-        return Err(format!("Matrix is not square: {} * {}", rows, cols));
-    }
+    let matrix: Matrix = Matrix::square_from_flat(mat, rows)?;
 
-    let matrix          : Vec<Vec<f64>> = utils::mat_conv2d(&mat, rows);
-    let mut aug_matrix  : Vec<Vec<f64>> = matrix.clone();
-    let size            : usize         = matrix.len();
+    let answer: Matrix = answer_matrix(ans, rows, ans_cols)?;
 
-    if utils::det(&matrix).abs() < 1e-12 {
-        return Err("Determinant is 0. Inverse matrix doesn't exist".to_string())
+    let size            : usize  = rows;
+
+    if matrix.det().abs() < 1e-12 {
+        return Err(CalError::Singular)
     }
 
     // Add inverse matrix (I)
-    for i in 0..size {
-        aug_matrix[i].extend((0..size).map(|j| if i == j { 1.0 } else { 0.0 }));
-    }
+    let mut aug_matrix  : Matrix           = matrix.augment(&Matrix::identity(size))?;
+    let mut trace       : EliminationTrace = EliminationTrace::new(rows);
 
     for i in 0..size {
-        let mut max_row: usize = i;
-        for j in i+1 ..size {
-            if aug_matrix[j][i].abs() >aug_matrix[max_row][i].abs() {
-                max_row = j;
-            }
-        }
+        let max_row: usize = aug_matrix.pivot_row(i, i);
 
         if max_row != i {
-            aug_matrix.swap(i, max_row);
+            trace.swap(&mut aug_matrix, i, max_row);
         }
 
-        if aug_matrix[i][i].abs() < 1e-12 {
-            return Err("Matrix is no unique solution".to_string());
+        if aug_matrix[(i, i)].abs() < 1e-12 {
+            return Err(CalError::Singular);
         }
 
-        let pivot: f64 = aug_matrix[i][i];
-        for j in 0..aug_matrix[i].len() {
-            aug_matrix[i][j] /= pivot;
-        }
+        let pivot: f64 = aug_matrix[(i, i)];
+        trace.scale(&mut aug_matrix, i, pivot);
 
         for j in 0..size {
             if j != i {
-                let ratio: f64 = aug_matrix[j][i];
-                for k in 0..aug_matrix[i].len() {
-                    aug_matrix[j][k] -= ratio * aug_matrix[i][k];
-                }
+                let ratio: f64 = aug_matrix[(j, i)];
+                trace.subtract(&mut aug_matrix, j, i, ratio, 0);
             }
         }
     }
 
-    let mut inverse_mat: Vec<Vec<f64>> = vec![vec![0.0; size]; size];
-    for i in 0..size {
-        for j in 0..size {
-            inverse_mat[i][j] = aug_matrix[i][j + size] / aug_matrix[i][i];
-        }
-    }
-
-    let value: Vec<f64> = utils::mat_imul_vec(&inverse_mat, &ans);
+    // right half is I^-1 once the left half is reduced to I
+    let inverse_mat : Matrix = aug_matrix.view(0, size, size, size).to_matrix();
+    let value       : Matrix = inverse_mat.mul(&answer)?;
 
     Ok(InverseResult {
+        steps: trace.steps,
         inverse_mat,
         value
     })
 }
 
-pub(crate) fn lu_decomposition_core(mat: Vec<f64>, rows: usize, ans: Vec<f64>) -> Result<DecompositionResult, String> {
+pub fn lu_factorize_core(mat: Vec<f64>, rows: usize) -> Result<LuFactorization, CalError> {
+    let matrix: Matrix = Matrix::square_from_flat(mat, rows)?;
 
-    let cols: usize = mat.len() / rows;
+    LuFactorization::factor(&matrix)
+}
 
-    if rows != cols {
-        return Err(format!("Matrix is not square: {} * {}", rows, cols));
-    }
+pub fn lu_solve_core(permutation: Vec<usize>, lower: Vec<f64>, upper: Vec<f64>, ans: Vec<f64>, ans_cols: usize) -> Result<LuSolveResult, CalError> {
+    let rows  : usize  = permutation.len();
+    let lower : Matrix = Matrix::square_from_flat(lower, rows)?;
+    let upper : Matrix = Matrix::square_from_flat(upper, rows)?;
 
-    let matrix : Vec<Vec<f64>> = utils::mat_conv2d(&mat, rows);
-    let (lower, upper)  = lu_decomposition_generate(matrix);
+    let answer : Matrix          = answer_matrix(ans, rows, ans_cols)?;
+    let lu     : LuFactorization = LuFactorization::from_parts(permutation, lower, upper)?;
 
-    let lower_result: Vec<f64>;
-    // forward substitution
-    match utils::guass(&lower, &ans) {
-        Ok(result) => lower_result = result,
-        Err(e) => return Err(format!("guassian calculation error: {}", e)),
-    }
+    let (forward_value, backward_value) = lu.solve_matrix(&answer)?;
 
-    let upper_result: Vec<f64>;
-    // backward substitution
-    match utils::guass(&upper, &lower_result) {
-        Ok(result) => upper_result = result,
-        Err(e) => return Err(format!("guassian calculation error: {}", e)),
-    }
+    Ok(LuSolveResult {
+        forward_value,
+        backward_value
+    })
+}
+
+pub fn lu_decomposition_core(mat: Vec<f64>, rows: usize, ans: Vec<f64>, ans_cols: usize) -> Result<DecompositionResult, CalError> {
+
+    let matrix: Matrix = Matrix::square_from_flat(mat, rows)?;
+
+    let answer: Matrix = answer_matrix(ans, rows, ans_cols)?;
+
+    let lu = LuFactorization::factor(&matrix)?;
+
+    let (lower_result, upper_result) = lu.solve_matrix(&answer)?;
 
     Ok(DecompositionResult {
-        lower_mat: lower,
-        upper_mat: upper,
+        permutation: Some(lu.permutation),
+        lower_mat: lu.lower_mat,
+        upper_mat: lu.upper_mat,
         forward_value: lower_result,
         backward_value: upper_result
     })
 }
 
-pub(crate) fn cholesky_core(mat: Vec<f64>, rows: usize, ans: Vec<f64>) -> Result<DecompositionResult, String> {
-    
-    let cols: usize = mat.len() / rows;
-
-    if rows != cols {
-        return Err(format!("Matrix is not square: {} * {}", rows, cols));
-    }
+pub fn cholesky_core(mat: Vec<f64>, rows: usize, ans: Vec<f64>, ans_cols: usize) -> Result<DecompositionResult, CalError> {
 
-    let matrix : Vec<Vec<f64>> = utils::mat_conv2d(&mat, rows);
+    let matrix: Matrix = Matrix::square_from_flat(mat, rows)?;
 
-    if !utils::is_positive_definite(&matrix) {
-        return Err("Matrix is not positive definite".to_string());
-    }
+    let answer: Matrix = answer_matrix(ans, rows, ans_cols)?;
 
+    // not symmetric positive definite is reported by the factorization
     let (lower, upper);
 
-    match cholesky_generate(matrix) {
+    match cholesky_generate(&matrix) {
         Ok(result) => {
             lower = result.0;
             upper = result.1;
@@ -427,21 +525,19 @@ pub(crate) fn cholesky_core(mat: Vec<f64>, rows: usize, ans: Vec<f64>) -> Result
         Err(e) => return Err(e),
     }
 
-    let lower_result: Vec<f64>;
-    // forward substitution
-    match utils::guass(&lower, &ans) {
-        Ok(result) => lower_result = result,
-        Err(e) => return Err(format!("guassian calculation error: {}", e)),
-    }
+    let mut lower_result : Matrix = Matrix::zeros(rows, ans_cols);
+    let mut upper_result : Matrix = Matrix::zeros(rows, ans_cols);
+    for c in 0..ans_cols {
+        // forward substitution
+        let forward: Vec<f64> = forward_substitution(&lower, &answer.column(c));
 
-    let upper_result: Vec<f64>;
-    // backward substitution
-    match utils::guass(&upper, &lower_result) {
-        Ok(result) => upper_result = result,
-        Err(e) => return Err(format!("guassian calculation error: {}", e)),
+        // backward substitution
+        upper_result.set_column(c, &backward_substitution(&upper, &forward));
+        lower_result.set_column(c, &forward);
     }
 
     Ok(DecompositionResult {
+        permutation: None,
         lower_mat: lower,
         upper_mat: upper,
         forward_value: lower_result, 
@@ -449,23 +545,58 @@ pub(crate) fn cholesky_core(mat: Vec<f64>, rows: usize, ans: Vec<f64>) -> Result
     })
 }
 
-pub(crate) fn jacobi_core(mat: Vec<f64>, rows: usize, ans: Vec<f64>, init: Vec<f64>) -> Result<Vec<LinearIterationResult>, String> {
-    
-    let cols: usize = mat.len() / rows;
+pub fn qr_decomposition_core(mat: Vec<f64>, rows: usize, method: QrMethod) -> Result<QrFactorization, CalError> {
+    let matrix: Matrix = Matrix::from_flat(mat, rows)?;
+
+    QrFactorization::factor(&matrix, method)
+}
 
-    if rows != cols {
-        return Err(format!("Matrix is not square: {} * {}", rows, cols));
+/*
+ *  Over-determined A x = b (rows >= cols) without normal equations
+ *      A = Q R  -->  R x = Q^T b (backward substitution)
+ */
+pub fn least_squares_core(mat: Vec<f64>, rows: usize, ans: Vec<f64>, method: QrMethod) -> Result<LeastSquaresResult, CalError> {
+    let matrix: Matrix = Matrix::from_flat(mat, rows)?;
+
+    if ans.len() != rows {
+        return Err(CalError::DimensionMismatch { expected: rows, got: ans.len() });
     }
 
-    let matrix : Vec<Vec<f64>> = utils::mat_conv2d(&mat, rows);
-    let x_size : usize         = ans.len();
+    let qr    : QrFactorization = QrFactorization::factor(&matrix, method)?;
+    let value : Vec<f64>        = qr.solve(&ans)?;
+
+    let residual      : Vec<f64> = utils::vec_sub(&matrix.mul_vec(&value)?, &ans);
+    let residual_norm : f64      = utils::dot_prod_self(&residual).sqrt();
+
+    Ok(LeastSquaresResult {
+        method,
+        q_mat: qr.q_mat,
+        r_mat: qr.r_mat,
+        value,
+        residual_norm
+    })
+}
+
+pub fn jacobi_core(mat: Vec<f64>, rows: usize, ans: Vec<f64>, init: Vec<f64>) -> Result<IterativeResult<LinearIterationResult>, CalError> {
+
+    let matrix: Matrix = Matrix::square_from_flat(mat, rows)?;
+
+    if ans.len() != rows {
+        return Err(CalError::DimensionMismatch { expected: rows, got: ans.len() });
+    }
+
+    if init.len() != rows {
+        return Err(CalError::DimensionMismatch { expected: rows, got: init.len() });
+    }
+
+    let x_size : usize  = ans.len();
 
     let mut x_old  : Vec<f64>   = init.clone();
     let mut x_new  : Vec<f64>   = vec![0.0; x_size];
-    let mut result : Vec<LinearIterationResult> = Vec::new();
+    let mut result : IterativeResult<LinearIterationResult> = IterativeResult::new();
 
     // init
-    result.push(LinearIterationResult {
+    result.iterations.push(LinearIterationResult {
         iteration: 0,
         x: x_old.clone(),
         error: 100.0
@@ -477,51 +608,59 @@ pub(crate) fn jacobi_core(mat: Vec<f64>, rows: usize, ans: Vec<f64>, init: Vec<f
             let mut sum: f64 = 0.0;
             for j in 0..x_size {
                 if j != i {
-                    sum += matrix[i][j] * x_old[j];
+                    sum += matrix[(i, j)] * x_old[j];
                 }
             }
 
-            if matrix[i][i].abs() < 1e-12 {
-                return Err("Matrix's diagonal elems is 0".to_string());
+            if matrix[(i, i)].abs() < 1e-12 {
+                return Err(CalError::ZeroDiagonal { index: i });
             }
 
-            x_new[i] = (ans[i] - sum) / matrix[i][i];
+            x_new[i] = (ans[i] - sum) / matrix[(i, i)];
         }
 
         let error: f64 = utils::error_calc(x_new[0], x_old[0]);
 
-        result.push(LinearIterationResult {
+        result.iterations.push(LinearIterationResult {
             iteration: iter + 1,
             x: x_new.clone(),
             error
         });
 
-        if error < 1e-6 {
+        if error.abs() < 1e-6 {
+            result.converge(StopCriterion::RelativeTolerance);
+            break;
+        }
+        if let Some(outcome) = Outcome::inspect_vec(&x_new) {
+            result.fail(outcome);
             break;
         }
 
-        x_old = x_new.clone();
+        x_old.copy_from_slice(&x_new);
     }
 
     Ok(result)
 }
 
-pub(crate) fn guass_seidel_core(mat: Vec<f64>, rows: usize, ans: Vec<f64>, init: Vec<f64>) -> Result<Vec<LinearIterationResult>, String> {
-    
-    let cols: usize = mat.len() / rows;
+pub fn guass_seidel_core(mat: Vec<f64>, rows: usize, ans: Vec<f64>, init: Vec<f64>) -> Result<IterativeResult<LinearIterationResult>, CalError> {
+
+    let matrix: Matrix = Matrix::square_from_flat(mat, rows)?;
 
-    if rows != cols {
-        return Err(format!("Matrix is not square: {} * {}", rows, cols));
+    if ans.len() != rows {
+        return Err(CalError::DimensionMismatch { expected: rows, got: ans.len() });
     }
 
-    let matrix : Vec<Vec<f64>> = utils::mat_conv2d(&mat, rows);
-    let x_size : usize         = ans.len();
+    if init.len() != rows {
+        return Err(CalError::DimensionMismatch { expected: rows, got: init.len() });
+    }
+
+    let x_size : usize  = ans.len();
 
     let mut x  : Vec<f64>   = init.clone();
-    let mut result : Vec<LinearIterationResult> = Vec::new();
+    let mut result : IterativeResult<LinearIterationResult> = IterativeResult::new();
 
     // init
-    result.push(LinearIterationResult {
+    result.iterations.push(LinearIterationResult {
         iteration: 0,
         x: x.clone(),
         error: 100.0
@@ -536,26 +675,31 @@ pub(crate) fn guass_seidel_core(mat: Vec<f64>, rows: usize, ans: Vec<f64>, init:
             let mut sum: f64 = 0.0;
             for j in 0..x_size {
                 if j != i {
-                    sum += matrix[i][j] * x[j];
+                    sum += matrix[(i, j)] * x[j];
                 }
             }
 
-            if matrix[i][i].abs() < 1e-12 {
-                return Err("Matrix's diagonal elems is 0".to_string());
+            if matrix[(i, i)].abs() < 1e-12 {
+                return Err(CalError::ZeroDiagonal { index: i });
             }
 
-            x[i] = (ans[i] - sum) / matrix[i][i];
+            x[i] = (ans[i] - sum) / matrix[(i, i)];
         }
 
         let error: f64 = utils::error_calc(x[0], prev_x);
 
-        result.push(LinearIterationResult {
+        result.iterations.push(LinearIterationResult {
             iteration: iter + 1,
             x: x.clone(),
             error
         });
 
-        if error < 1e-6 {
+        if error.abs() < 1e-6 {
+            result.converge(StopCriterion::RelativeTolerance);
+            break;
+        }
+        if let Some(outcome) = Outcome::inspect_vec(&x) {
+            result.fail(outcome);
             break;
         }
     }
@@ -563,26 +707,29 @@ pub(crate) fn guass_seidel_core(mat: Vec<f64>, rows: usize, ans: Vec<f64>, init:
     Ok(result)
 }
 
-pub(crate) fn over_relaxation_core(mat: Vec<f64>, rows: usize, ans: Vec<f64>, init: Vec<f64>, omega: f64) -> Result<Vec<LinearIterationResult>, String> {
+pub fn over_relaxation_core(mat: Vec<f64>, rows: usize, ans: Vec<f64>, init: Vec<f64>, omega: f64) -> Result<IterativeResult<LinearIterationResult>, CalError> {
 
     if omega <= 0.0 || omega >= 2.0 {
-        return Err("Relaxation factor omega must be in (0, 2)".to_string());
+        return Err(CalError::invalid_argument("omega", "relaxation factor must be in (0, 2)"));
     }
-    
-    let cols: usize = mat.len() / rows;
 
-    if rows != cols {
-        return Err(format!("Matrix is not square: {} * {}", rows, cols));
+    let matrix: Matrix = Matrix::square_from_flat(mat, rows)?;
+
+    if ans.len() != rows {
+        return Err(CalError::DimensionMismatch { expected: rows, got: ans.len() });
     }
 
-    let matrix : Vec<Vec<f64>> = utils::mat_conv2d(&mat, rows);
-    let x_size : usize         = ans.len();
+    if init.len() != rows {
+        return Err(CalError::DimensionMismatch { expected: rows, got: init.len() });
+    }
+
+    let x_size : usize  = ans.len();
 
     let mut x  : Vec<f64>   = init.clone();
-    let mut result : Vec<LinearIterationResult> = Vec::new();
+    let mut result : IterativeResult<LinearIterationResult> = IterativeResult::new();
 
     // init
-    result.push(LinearIterationResult {
+    result.iterations.push(LinearIterationResult {
         iteration: 0,
         x: x.clone(),
         error: 100.0
@@ -596,31 +743,36 @@ pub(crate) fn over_relaxation_core(mat: Vec<f64>, rows: usize, ans: Vec<f64>, in
             let mut sum: f64 = 0.0;
             for j in 0..x_size {
                 if j != i {
-                    sum += matrix[i][j] * x[j];
+                    sum += matrix[(i, j)] * x[j];
                 }
             }
 
-            if matrix[i][i].abs() < 1e-12 {
-                return Err("Matrix's diagonal elems is 0".to_string());
+            if matrix[(i, i)].abs() < 1e-12 {
+                return Err(CalError::ZeroDiagonal { index: i });
             }
 
             let old_val = x[i];
-            let new_val = ((ans[i] - sum) / matrix[i][i] * omega) + ((1.0 - omega) * x[i]);
+            let new_val = ((ans[i] - sum) / matrix[(i, i)] * omega) + ((1.0 - omega) * x[i]);
             x[i] = new_val;
 
-            let err = utils::error_calc(new_val, old_val);
+            let err = utils::error_calc(new_val, old_val).abs();
             if err > max_err {
                 max_err = err;
             }
         }
 
-        result.push(LinearIterationResult {
+        result.iterations.push(LinearIterationResult {
             iteration: iter + 1,
             x: x.clone(),
             error: max_err,
         });
 
         if max_err < 1e-6 {
+            result.converge(StopCriterion::RelativeTolerance);
+            break;
+        }
+        if let Some(outcome) = Outcome::inspect_vec(&x) {
+            result.fail(outcome);
             break;
         }
     }
@@ -628,30 +780,32 @@ pub(crate) fn over_relaxation_core(mat: Vec<f64>, rows: usize, ans: Vec<f64>, in
     Ok(result)
 }
 
-pub(crate) fn cg_core(mat: Vec<f64>, rows: usize, ans: Vec<f64>, init: Vec<f64>) -> Result<Vec<ConjugateResult>, String> {
-    
-    let cols: usize = mat.len() / rows;
+pub fn cg_core(mat: Vec<f64>, rows: usize, ans: Vec<f64>, init: Vec<f64>) -> Result<IterativeResult<ConjugateResult>, CalError> {
 
-    if rows != cols {
-        return Err(format!("Matrix is not square: {} * {}", rows, cols));
+    let matrix: Matrix = Matrix::square_from_flat(mat, rows)?;
+
+    if ans.len() != rows {
+        return Err(CalError::DimensionMismatch { expected: rows, got: ans.len() });
     }
 
-    let matrix : Vec<Vec<f64>> = utils::mat_conv2d(&mat, rows);
+    if init.len() != rows {
+        return Err(CalError::DimensionMismatch { expected: rows, got: init.len() });
+    }
 
-    if !utils::is_positive_definite(&matrix) {
-        return Err("Matrix is not positive definite".to_string());
+    if !matrix.is_positive_definite() {
+        return Err(CalError::NotPositiveDefinite);
     }
 
-    let mut result : Vec<ConjugateResult> = Vec::new();
+    let mut result : IterativeResult<ConjugateResult> = IterativeResult::new();
     
     // Initialize
     let mut x         : Vec<f64> = init.clone();
-    let mut residual  : Vec<f64> = utils::vec_sub(&utils::mat_imul_vec(&matrix, &x), &ans);
+    let mut residual  : Vec<f64> = utils::vec_sub(&matrix.mul_vec(&x)?, &ans);
     let mut direction : Vec<f64> = utils::scalar_mult(&residual, -1.0);
     let mut alpha     : f64 = 0.0;
     let mut error     : f64 = utils::dot_prod_self(&residual).sqrt();
 
-    result.push(ConjugateResult {
+    result.iterations.push(ConjugateResult {
         iteration: 0,
         x: x.clone(),
         residual: residual.clone(),
@@ -662,25 +816,30 @@ pub(crate) fn cg_core(mat: Vec<f64>, rows: usize, ans: Vec<f64>, init: Vec<f64>)
     });
 
     for iter in 0..100 {
-        let a_imul_d = utils::mat_imul_vec(&matrix, &direction);
+        let a_imul_d = matrix.mul_vec(&direction)?;
 
         // |D^t| * r
         let lambda_numerator = utils::dot_prod(&direction, &residual);
         // |D^t| * AD
         let lambda_denominator = utils::dot_prod(&direction, &a_imul_d);
 
-        // zero-division
+        // zero-division (zero direction is counted as exact solution)
         if lambda_denominator.abs() < 1e-12 {
+            if error < 1e-12 {
+                result.converge(StopCriterion::Residual);
+            } else {
+                result.fail(Outcome::Stagnated);
+            }
             break;
         }
 
         // new lambda --> x --> residual
         let lambda   = lambda_numerator / lambda_denominator * -1.0;
         x        = utils::vec_add(&x, &utils::scalar_mult(&direction, lambda));
-        residual = utils::vec_sub(&utils::mat_imul_vec(&matrix, &x), &ans);
+        residual = utils::vec_sub(&matrix.mul_vec(&x)?, &ans);
         error    = (utils::dot_prod_self(&residual)).sqrt();
 
-        result.push(ConjugateResult {
+        result.iterations.push(ConjugateResult {
             iteration: iter+1,
             x: x.clone(),
             residual: residual.clone(),
@@ -692,6 +851,11 @@ pub(crate) fn cg_core(mat: Vec<f64>, rows: usize, ans: Vec<f64>, init: Vec<f64>)
 
 
         if error < 1e-12 {
+            result.converge(StopCriterion::Residual);
+            break;
+        }
+        if let Some(outcome) = Outcome::inspect_vec(&x) {
+            result.fail(outcome);
             break;
         }
 
@@ -710,72 +874,347 @@ pub(crate) fn cg_core(mat: Vec<f64>, rows: usize, ans: Vec<f64>, init: Vec<f64>)
 
 // Calculations
 
-// Create lower-Upper matrix
-fn lu_decomposition_generate(mat: Vec<Vec<f64>>) -> (Vec<Vec<f64>>, Vec<Vec<f64>>) {
-    let n: usize = mat.len();
-    let mut lower: Vec<Vec<f64>> = vec![vec![0.0; n]; n];
-    let mut upper: Vec<Vec<f64>> = vec![vec![0.0; n]; n];
+impl LuFactorization {
+    // Gaussian elimination with row swaps, then the diagonal of U is moved into L
+    pub(crate) fn factor(mat: &Matrix) -> Result<Self, CalError> {
+        let n               : usize      = mat.require_square()?;
+        let mut work        : Matrix     = mat.clone();
+        let mut permutation : Vec<usize> = (0..n).collect();
 
-    for i in 0..n {
+        for k in 0..n {
+            let max_row: usize = work.pivot_row(k, k);
+
+            if work[(max_row, k)].abs() < 1e-12 {
+                return Err(CalError::Singular);
+            }
 
-        // diagonal (init)
-        upper[i][i] = 1.0;
+            if max_row != k {
+                work.swap_rows(k, max_row);
+                permutation.swap(k, max_row);
+            }
 
-        // Lower calc
-        for j in 0..=i {
-            let mut sum = 0.0;
-            for k in 0..j {
-                sum += lower[i][k] * upper[k][j];
+            // multipliers are kept below the diagonal
+            for i in k + 1..n {
+                let ratio: f64 = work[(i, k)] / work[(k, k)];
+                work.sub_row(i, k, ratio, k + 1);
+                work[(i, k)] = ratio;
             }
-            lower[i][j] = mat[i][j] - sum;
         }
 
-        // Upper
-        for j in i + 1..n {
-            let mut sum = 0.0;
-            for k in 0..i {
-                sum += lower[i][k] * upper[k][j];
+        let mut lower           : Matrix = Matrix::zeros(n, n);
+        let mut upper           : Matrix = Matrix::identity(n);
+        let mut permutation_mat : Matrix = Matrix::zeros(n, n);
+
+        for i in 0..n {
+            permutation_mat[(i, permutation[i])] = 1.0;
+
+            // l_ij = m_ij * u_jj, u_ij = u_ij / u_ii (Crout)
+            for j in 0..i {
+                lower[(i, j)] = work[(i, j)] * work[(j, j)];
+            }
+            lower[(i, i)] = work[(i, i)];
+            for j in i + 1..n {
+                upper[(i, j)] = work[(i, j)] / work[(i, i)];
             }
-            upper[i][j] = (mat[i][j] - sum) / lower[i][i];
         }
+
+        Ok(LuFactorization {
+            permutation,
+            permutation_mat,
+            lower_mat: lower,
+            upper_mat: upper
+        })
+    }
+
+    // (y, x) of L y = P b and U x = y
+    pub fn solve(&self, ans: &[f64]) -> Result<(Vec<f64>, Vec<f64>), CalError> {
+        if ans.len() != self.permutation.len() {
+            return Err(CalError::DimensionMismatch { expected: self.permutation.len(), got: ans.len() });
+        }
+
+        let permuted : Vec<f64> = self.permutation.iter().map(|&i| ans[i]).collect();
+        let forward  : Vec<f64> = forward_substitution(&self.lower_mat, &permuted);
+        let backward : Vec<f64> = backward_substitution(&self.upper_mat, &forward);
+
+        Ok((forward, backward))
     }
 
-    (lower, upper)
+    // forward (L y = P b) --> backward (U x = y), same factorization for every column of B
+    pub(crate) fn solve_matrix(&self, answer: &Matrix) -> Result<(Matrix, Matrix), CalError> {
+        let mut forward_value  : Matrix = Matrix::zeros(answer.rows(), answer.cols());
+        let mut backward_value : Matrix = Matrix::zeros(answer.rows(), answer.cols());
+
+        for c in 0..answer.cols() {
+            let (forward, backward) = self.solve(&answer.column(c))?;
+            forward_value.set_column(c, &forward);
+            backward_value.set_column(c, &backward);
+        }
+
+        Ok((forward_value, backward_value))
+    }
+
+    // Factorization sent back from JS, P is rebuilt from the permutation
+    pub(crate) fn from_parts(permutation: Vec<usize>, lower_mat: Matrix, upper_mat: Matrix) -> Result<Self, CalError> {
+        let n: usize = permutation.len();
+
+        for mat in [&lower_mat, &upper_mat].iter() {
+            if mat.require_square()? != n {
+                return Err(CalError::DimensionMismatch { expected: n, got: mat.rows() });
+            }
+        }
+
+        let mut sorted: Vec<usize> = permutation.clone();
+        sorted.sort_unstable();
+        if sorted.iter().enumerate().any(|(i, &row)| i != row) {
+            return Err(CalError::invalid_argument("permutation", "must be a reordering of 0 .. rows - 1"));
+        }
+
+        // substitution divides by both diagonals
+        if (0..n).any(|i| lower_mat[(i, i)].abs() < 1e-12 || upper_mat[(i, i)].abs() < 1e-12) {
+            return Err(CalError::Singular);
+        }
+
+        let mut permutation_mat: Matrix = Matrix::zeros(n, n);
+        for (i, &row) in permutation.iter().enumerate() {
+            permutation_mat[(i, row)] = 1.0;
+        }
+
+        Ok(LuFactorization {
+            permutation,
+            permutation_mat,
+            lower_mat,
+            upper_mat
+        })
+    }
 }
 
-// Create Lower-Upper matrix
-fn cholesky_generate(mat: Vec<Vec<f64>>) -> Result< ( Vec<Vec<f64>>, Vec<Vec<f64>> ), String> {
-    let n = mat.len();
+impl QrFactorization {
+    pub(crate) fn factor(mat: &Matrix, method: QrMethod) -> Result<Self, CalError> {
+        if mat.rows() < mat.cols() {
+            return Err(CalError::InsufficientData { required: mat.cols(), got: mat.rows() });
+        }
 
-    let mut lower = vec![vec![0.0; n]; n];
+        let (mut q_mat, mut r_mat) = match method {
+            QrMethod::Householder         => householder_qr(mat),
+            QrMethod::ModifiedGramSchmidt => gram_schmidt_qr(mat),
+        };
 
-    for i in 0..n {
-        for j in 0..=i {
-            let mut sum = 0.0;
+        // rank deficient, relative to the size of A
+        let tolerance: f64 = 1e-12 * mat.norm_frobenius();
+        for k in 0..r_mat.rows() {
+            if r_mat[(k, k)].abs() <= tolerance {
+                return Err(CalError::Singular);
+            }
 
-            // diagonal
-            if j == i {
-                for k in 0..j {
-                    sum += lower[j][k] * lower[j][k];
+            // r_kk > 0 makes Q, R unique
+            if r_mat[(k, k)] < 0.0 {
+                for value in r_mat.row_mut(k) {
+                    *value = -*value;
                 }
-                let diag_value = mat[j][j] - sum;
-                if diag_value <= 0.0 {
-                    return Err(format!("Diagonal elements is not positive definite at {}{}", j, j));
+                for i in 0..q_mat.rows() {
+                    q_mat[(i, k)] = -q_mat[(i, k)];
                 }
-
-                lower[j][j] = (diag_value).sqrt();
             }
-            else {
+        }
 
-                for k in 0..j {
-                    sum += lower[i][k] * lower[j][k];
-                }
-                lower[i][j] = (mat[i][j] - sum) / lower[j][j];
+        Ok(QrFactorization { method, q_mat, r_mat })
+    }
+
+    // R x = Q^T b
+    pub(crate) fn solve(&self, ans: &[f64]) -> Result<Vec<f64>, CalError> {
+        let qt_b: Vec<f64> = self.q_mat.transpose().mul_vec(ans)?;
+
+        Ok(backward_substitution(&self.r_mat, &qt_b))
+    }
+
+    // Minimum norm x of A^T x = b (under-determined): R^T z = b, x = Q z
+    pub(crate) fn solve_transposed(&self, ans: &[f64]) -> Result<Vec<f64>, CalError> {
+        if ans.len() != self.r_mat.rows() {
+            return Err(CalError::DimensionMismatch { expected: self.r_mat.rows(), got: ans.len() });
+        }
+
+        let z: Vec<f64> = forward_substitution(&self.r_mat.transpose(), ans);
+
+        self.q_mat.mul_vec(&z)
+    }
+}
+
+// v_k from column k below the diagonal, Q is built by applying H_k .. H_1 to the first columns of I
+fn householder_qr(mat: &Matrix) -> (Matrix, Matrix) {
+    let (m, n)      : (usize, usize) = (mat.rows(), mat.cols());
+    let mut work    : Matrix         = mat.clone();
+    let mut vectors : Vec<Vec<f64>>  = Vec::with_capacity(n);
+
+    for k in 0..n {
+        let mut v  : Vec<f64> = (k..m).map(|i| work[(i, k)]).collect();
+        let norm_x : f64      = utils::dot_prod_self(&v).sqrt();
+
+        // alpha takes the sign opposite to x_0 to avoid cancellation
+        let alpha: f64 = if v[0] >= 0.0 { -norm_x } else { norm_x };
+        v[0] -= alpha;
+
+        let norm_v: f64 = utils::dot_prod_self(&v).sqrt();
+        if norm_v > 0.0 {
+            v = utils::scalar_mult(&v, 1.0 / norm_v);
+            apply_reflection(&mut work, &v, k, k);
+        }
+
+        vectors.push(v);
+    }
+
+    let mut q_mat: Matrix = Matrix::zeros(m, n);
+    for k in 0..n {
+        q_mat[(k, k)] = 1.0;
+    }
+    for (k, v) in vectors.iter().enumerate().rev() {
+        apply_reflection(&mut q_mat, v, k, 0);
+    }
+
+    let mut r_mat: Matrix = Matrix::zeros(n, n);
+    for i in 0..n {
+        for j in i..n {
+            r_mat[(i, j)] = work[(i, j)];
+        }
+    }
+
+    (q_mat, r_mat)
+}
+
+// rows row.. of mat = (I - 2 v v^T) rows row.., only columns col.. are touched
+fn apply_reflection(mat: &mut Matrix, v: &[f64], row: usize, col: usize) {
+    for j in col..mat.cols() {
+        let dot: f64 = v.iter().enumerate().map(|(i, vi)| vi * mat[(row + i, j)]).sum();
+        for (i, vi) in v.iter().enumerate() {
+            mat[(row + i, j)] -= 2.0 * vi * dot;
+        }
+    }
+}
+
+// q_k is normalized first, then removed from every later column (modified, not classical)
+fn gram_schmidt_qr(mat: &Matrix) -> (Matrix, Matrix) {
+    let (m, n)    : (usize, usize) = (mat.rows(), mat.cols());
+    let mut q_mat : Matrix         = mat.clone();
+    let mut r_mat : Matrix         = Matrix::zeros(n, n);
+
+    for k in 0..n {
+        let norm: f64 = (0..m).map(|i| q_mat[(i, k)] * q_mat[(i, k)]).sum::<f64>().sqrt();
+        r_mat[(k, k)] = norm;
+
+        // dependent column, reported by factor()
+        if norm == 0.0 {
+            continue;
+        }
+
+        for i in 0..m {
+            q_mat[(i, k)] /= norm;
+        }
+
+        for j in k + 1..n {
+            let dot: f64 = (0..m).map(|i| q_mat[(i, k)] * q_mat[(i, j)]).sum();
+            r_mat[(k, j)] = dot;
+            for i in 0..m {
+                q_mat[(i, j)] -= dot * q_mat[(i, k)];
             }
         }
     }
 
-    let upper = utils::mat_transpose(&lower);
+    (q_mat, r_mat)
+}
+
+fn qr_method(method_type: u32) -> Result<QrMethod, CalError> {
+    match method_type {
+        1 => Ok(QrMethod::Householder),
+        2 => Ok(QrMethod::ModifiedGramSchmidt),
+        _ => Err(CalError::invalid_argument("method_type", "must be 1 (Householder) or 2 (modified Gram-Schmidt)")),
+    }
+}
+
+// Applies row operations to the augmented matrix and records them with a snapshot
+struct EliminationTrace {
+    steps: Option<Vec<EliminationStep>>
+}
+
+impl EliminationTrace {
+    fn new(rows: usize) -> Self {
+        EliminationTrace {
+            steps: if rows <= MAX_TRACE_ROWS { Some(Vec::new()) } else { None }
+        }
+    }
+
+    fn swap(&mut self, aug_matrix: &mut Matrix, row: usize, other: usize) {
+        aug_matrix.swap_rows(row, other);
+        self.record(RowOperation::Swap { row, other }, aug_matrix);
+    }
+
+    // dividing by 1 is not shown
+    fn scale(&mut self, aug_matrix: &mut Matrix, row: usize, pivot: f64) {
+        for value in aug_matrix.row_mut(row) {
+            *value /= pivot;
+        }
+
+        if pivot != 1.0 {
+            self.record(RowOperation::Scale { row, pivot }, aug_matrix);
+        }
+    }
+
+    // subtracting 0 * row is not shown
+    fn subtract(&mut self, aug_matrix: &mut Matrix, row: usize, source: usize, ratio: f64, from: usize) {
+        aug_matrix.sub_row(row, source, ratio, from);
+
+        if ratio != 0.0 {
+            self.record(RowOperation::Subtract { row, source, ratio }, aug_matrix);
+        }
+    }
+
+    fn record(&mut self, operation: RowOperation, aug_matrix: &Matrix) {
+        if let Some(steps) = &mut self.steps {
+            steps.push(EliminationStep {
+                step: steps.len() + 1,
+                operation,
+                matrix: aug_matrix.clone()
+            });
+        }
+    }
+}
+
+// B of the direct solvers, rows * ans_cols
+fn answer_matrix(ans: Vec<f64>, rows: usize, ans_cols: usize) -> Result<Matrix, CalError> {
+    if ans_cols == 0 {
+        return Err(CalError::invalid_argument("ans_cols", "must be at least 1"));
+    }
+
+    Matrix::new(rows, ans_cols, ans)
+}
+
+// L y = b, L is lower triangular with non-zero diagonal
+fn forward_substitution(lower: &Matrix, ans: &[f64]) -> Vec<f64> {
+    let mut result: Vec<f64> = vec![0.0; ans.len()];
+
+    for i in 0..ans.len() {
+        let sum: f64 = (0..i).map(|j| lower[(i, j)] * result[j]).sum();
+        result[i] = (ans[i] - sum) / lower[(i, i)];
+    }
+
+    result
+}
+
+// U x = y, U is upper triangular with non-zero diagonal
+fn backward_substitution(upper: &Matrix, ans: &[f64]) -> Vec<f64> {
+    let n          : usize    = ans.len();
+    let mut result : Vec<f64> = vec![0.0; n];
+
+    for i in (0..n).rev() {
+        let sum: f64 = (i + 1..n).map(|j| upper[(i, j)] * result[j]).sum();
+        result[i] = (ans[i] - sum) / upper[(i, i)];
+    }
+
+    result
+}
+
+// Create Lower-Upper matrix
+fn cholesky_generate(mat: &Matrix) -> Result< ( Matrix, Matrix ), CalError> {
+    let lower: Matrix = mat.cholesky_lower()?;
+    let upper: Matrix = lower.transpose();
 
     Ok((lower, upper))
 }
\ No newline at end of file
@@ -0,0 +1,486 @@
+// matrix.rs
+use crate::error::CalError;
+
+use std::ops::{Index, IndexMut};
+
+use serde::{Serialize, Serializer};
+use serde_wasm_bindgen::to_value;
+use wasm_bindgen::prelude::*;
+
+
+
+// Duplication struct
+
+/*
+ * ---- Dense matrix, contiguous row-major ----
+ *
+ *  data[i * cols + j] = a_ij, same layout as the flat `mat: Vec<f64>` sent from JS
+ *  so the input is moved in without copying
+ *
+ *  Serialized as Vec<Vec<f64>> (row by row), JS tables read it as before
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Matrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<f64>
+}
+
+// Block of a Matrix borrowed without copying, row(i) is a slice of the parent row
+pub(crate) struct MatrixView<'a> {
+    matrix: &'a Matrix,
+    row: usize,
+    col: usize,
+    rows: usize,
+    cols: usize
+}
+
+// One : max column sum, Infinity : max row sum, Frobenius : sqrt of sum of squares
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub enum MatrixNorm {
+    One,
+    Infinity,
+    Frobenius
+}
+
+// minimum pivot that counted as 0.0
+const PIVOT_EPSILON: f64 = 1e-12;
+
+
+
+// wasm conversion JsValue
+
+#[wasm_bindgen]
+pub fn matrix_det(mat: Vec<f64>, rows: usize) -> JsValue {
+    match matrix_det_core(mat, rows) {
+        Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
+        Err(e) => e.into(),
+    }
+}
+
+// a is a_rows * (a.len() / a_rows), b likewise
+#[wasm_bindgen]
+pub fn matrix_multiply(a: Vec<f64>, a_rows: usize, b: Vec<f64>, b_rows: usize) -> JsValue {
+    match matrix_multiply_core(a, a_rows, b, b_rows) {
+        Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
+        Err(e) => e.into(),
+    }
+}
+
+#[wasm_bindgen]
+pub fn matrix_transpose(mat: Vec<f64>, rows: usize) -> JsValue {
+    match matrix_transpose_core(mat, rows) {
+        Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
+        Err(e) => e.into(),
+    }
+}
+
+#[wasm_bindgen]
+pub fn matrix_norm(mat: Vec<f64>, rows: usize, norm_type: u32) -> JsValue {
+    let norm: MatrixNorm = match norm_type {
+        1 => MatrixNorm::One,
+        2 => MatrixNorm::Infinity,
+        3 => MatrixNorm::Frobenius,
+        _ => return CalError::invalid_argument("norm_type", "must be 1 (one), 2 (infinity) or 3 (frobenius)").into(),
+    };
+
+    match matrix_norm_core(mat, rows, norm) {
+        Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
+        Err(e) => e.into(),
+    }
+}
+
+
+
+// Add implement method
+
+pub fn matrix_det_core(mat: Vec<f64>, rows: usize) -> Result<f64, CalError> {
+    let matrix: Matrix = Matrix::square_from_flat(mat, rows)?;
+
+    Ok(matrix.det())
+}
+
+pub fn matrix_multiply_core(a: Vec<f64>, a_rows: usize, b: Vec<f64>, b_rows: usize) -> Result<Matrix, CalError> {
+    let a: Matrix = Matrix::from_flat(a, a_rows)?;
+    let b: Matrix = Matrix::from_flat(b, b_rows)?;
+
+    a.mul(&b)
+}
+
+pub fn matrix_transpose_core(mat: Vec<f64>, rows: usize) -> Result<Matrix, CalError> {
+    Ok(Matrix::from_flat(mat, rows)?.transpose())
+}
+
+pub fn matrix_norm_core(mat: Vec<f64>, rows: usize, norm: MatrixNorm) -> Result<f64, CalError> {
+    let matrix: Matrix = Matrix::from_flat(mat, rows)?;
+
+    Ok(match norm {
+        MatrixNorm::One       => matrix.norm_one(),
+        MatrixNorm::Infinity  => matrix.norm_inf(),
+        MatrixNorm::Frobenius => matrix.norm_frobenius(),
+    })
+}
+
+impl Matrix {
+    pub fn new(rows: usize, cols: usize, data: Vec<f64>) -> Result<Self, CalError> {
+        if data.len() != rows * cols {
+            return Err(CalError::DimensionMismatch { expected: rows * cols, got: data.len() });
+        }
+
+        Ok(Matrix { rows, cols, data })
+    }
+
+    // cols is taken from data.len() / rows, a partial last row is a mismatch
+    pub(crate) fn from_flat(data: Vec<f64>, rows: usize) -> Result<Self, CalError> {
+        if rows == 0 {
+            return Err(CalError::InsufficientData { required: 1, got: 0 });
+        }
+
+        let cols: usize = data.len() / rows;
+        Matrix::new(rows, cols, data)
+    }
+
+    // Input of every square solver
+    pub(crate) fn square_from_flat(data: Vec<f64>, rows: usize) -> Result<Self, CalError> {
+        let matrix: Matrix = Matrix::from_flat(data, rows)?;
+        matrix.require_square()?;
+
+        Ok(matrix)
+    }
+
+    pub(crate) fn from_rows(rows: &[Vec<f64>]) -> Result<Self, CalError> {
+        let cols : usize = rows.first().map_or(0, |row| row.len());
+        let data : Vec<f64> = rows.iter().flatten().copied().collect();
+
+        if let Some(row) = rows.iter().find(|row| row.len() != cols) {
+            return Err(CalError::DimensionMismatch { expected: cols, got: row.len() });
+        }
+
+        Matrix::new(rows.len(), cols, data)
+    }
+
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Matrix { rows, cols, data: vec![0.0; rows * cols] }
+    }
+
+    pub(crate) fn identity(size: usize) -> Self {
+        let mut matrix: Matrix = Matrix::zeros(size, size);
+        for i in 0..size {
+            matrix[(i, i)] = 1.0;
+        }
+
+        matrix
+    }
+
+    pub(crate) fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub(crate) fn cols(&self) -> usize {
+        self.cols
+    }
+
+    // size of a square matrix
+    pub(crate) fn require_square(&self) -> Result<usize, CalError> {
+        if self.rows != self.cols {
+            return Err(CalError::NotSquare { rows: self.rows, cols: self.cols });
+        }
+
+        Ok(self.rows)
+    }
+
+    pub fn row(&self, i: usize) -> &[f64] {
+        &self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    pub(crate) fn row_mut(&mut self, i: usize) -> &mut [f64] {
+        &mut self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    pub fn row_iter(&self) -> impl Iterator<Item = &[f64]> {
+        (0..self.rows).map(move |i| self.row(i))
+    }
+
+    pub fn column(&self, j: usize) -> Vec<f64> {
+        (0..self.rows).map(|i| self[(i, j)]).collect()
+    }
+
+    pub(crate) fn set_column(&mut self, j: usize, values: &[f64]) {
+        for (i, &value) in values.iter().enumerate() {
+            self[(i, j)] = value;
+        }
+    }
+
+    pub(crate) fn swap_rows(&mut self, i: usize, j: usize) {
+        if i == j {
+            return;
+        }
+
+        let (lo, hi) : (usize, usize) = (i.min(j), i.max(j));
+        let (head, tail) = self.data.split_at_mut(hi * self.cols);
+        head[lo * self.cols..(lo + 1) * self.cols].swap_with_slice(&mut tail[..self.cols]);
+    }
+
+    // same shape, reuses the allocation
+    pub(crate) fn copy_from(&mut self, other: &Matrix) {
+        self.data.copy_from_slice(&other.data);
+    }
+
+    // rows * cols block starting at (row, col)
+    pub(crate) fn view(&self, row: usize, col: usize, rows: usize, cols: usize) -> MatrixView<'_> {
+        assert!(row + rows <= self.rows && col + cols <= self.cols, "view is out of matrix");
+
+        MatrixView { matrix: self, row, col, rows, cols }
+    }
+
+    // [A | B], rows must match
+    pub(crate) fn augment(&self, other: &Matrix) -> Result<Matrix, CalError> {
+        if other.rows != self.rows {
+            return Err(CalError::DimensionMismatch { expected: self.rows, got: other.rows });
+        }
+
+        let cols     : usize    = self.cols + other.cols;
+        let mut data : Vec<f64> = Vec::with_capacity(self.rows * cols);
+        for i in 0..self.rows {
+            data.extend_from_slice(self.row(i));
+            data.extend_from_slice(other.row(i));
+        }
+
+        Ok(Matrix { rows: self.rows, cols, data })
+    }
+
+    pub fn transpose(&self) -> Matrix {
+        let mut transposed: Matrix = Matrix::zeros(self.cols, self.rows);
+        for i in 0..self.rows {
+            for j in 0..self.cols {
+                transposed[(j, i)] = self[(i, j)];
+            }
+        }
+
+        transposed
+    }
+
+    pub fn mul(&self, other: &Matrix) -> Result<Matrix, CalError> {
+        if self.cols != other.rows {
+            return Err(CalError::DimensionMismatch { expected: self.cols, got: other.rows });
+        }
+
+        // i-k-j order walks both operands row by row
+        let mut result: Matrix = Matrix::zeros(self.rows, other.cols);
+        for i in 0..self.rows {
+            for k in 0..self.cols {
+                let a_ik: f64 = self[(i, k)];
+                for (r, &b) in result.row_mut(i).iter_mut().zip(other.row(k)) {
+                    *r += a_ik * b;
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    pub(crate) fn mul_vec(&self, vec: &[f64]) -> Result<Vec<f64>, CalError> {
+        if self.cols != vec.len() {
+            return Err(CalError::DimensionMismatch { expected: self.cols, got: vec.len() });
+        }
+
+        Ok(self.row_iter().map(|row| row.iter().zip(vec).map(|(a, b)| a * b).sum()).collect())
+    }
+
+    pub(crate) fn norm_one(&self) -> f64 {
+        (0..self.cols)
+            .map(|j| (0..self.rows).map(|i| self[(i, j)].abs()).sum::<f64>())
+            .fold(0.0, f64::max)
+    }
+
+    pub(crate) fn norm_inf(&self) -> f64 {
+        self.row_iter()
+            .map(|row| row.iter().map(|a| a.abs()).sum::<f64>())
+            .fold(0.0, f64::max)
+    }
+
+    pub(crate) fn norm_frobenius(&self) -> f64 {
+        self.data.iter().map(|a| a * a).sum::<f64>().sqrt()
+    }
+
+    pub(crate) fn is_symmetric(&self) -> bool {
+        self.rows == self.cols && (0..self.rows).all(|i| (0..i).all(|j| self[(i, j)] == self[(j, i)]))
+    }
+
+    // strictly positive definite, x^T A x > 0 for x != 0 (a zero matrix is not)
+    pub fn is_positive_definite(&self) -> bool {
+        self.cholesky_lower().is_ok()
+    }
+
+    /*
+     *  A = L L^T, fails on the first diagonal l_jj^2 <= 0
+     *      l_jj = sqrt(a_jj - sum_(k < j) l_jk^2)
+     *      l_ij = (a_ij - sum_(k < j) l_ik l_jk) / l_jj
+     */
+    pub fn cholesky_lower(&self) -> Result<Matrix, CalError> {
+        if !self.is_symmetric() {
+            return Err(CalError::NotPositiveDefinite);
+        }
+
+        let n         : usize  = self.rows;
+        let mut lower : Matrix = Matrix::zeros(n, n);
+
+        for i in 0..n {
+            for j in 0..=i {
+                let sum: f64 = (0..j).map(|k| lower[(i, k)] * lower[(j, k)]).sum();
+
+                if i == j {
+                    let diag_value: f64 = self[(j, j)] - sum;
+                    if diag_value <= 0.0 {
+                        return Err(CalError::NotPositiveDefinite);
+                    }
+                    lower[(j, j)] = diag_value.sqrt();
+                }
+                else {
+                    lower[(i, j)] = (self[(i, j)] - sum) / lower[(j, j)];
+                }
+            }
+        }
+
+        Ok(lower)
+    }
+
+    // Using RREF
+    pub(crate) fn det(&self) -> f64 {
+        self.clone().det_in_place()
+    }
+
+    // Eliminates self into upper triangular form, for reusing one buffer (Cramer)
+    pub(crate) fn det_in_place(&mut self) -> f64 {
+        let size    : usize = self.rows;
+        let mut det : f64   = 1.0;
+
+        for i in 0..size {
+            let max_row: usize = self.pivot_row(i, i);
+
+            if self[(max_row, i)].abs() < PIVOT_EPSILON {
+                return 0.0;
+            }
+
+            // Swap flag **MUST
+            if max_row != i {
+                self.swap_rows(i, max_row);
+                det = -det;
+            }
+
+            for j in i + 1..size {
+                let ratio: f64 = self[(j, i)] / self[(i, i)];
+                self.sub_row(j, i, ratio, i);
+            }
+        }
+
+        // Det calc
+        for i in 0..size {
+            det *= self[(i, i)];
+        }
+
+        det
+    }
+
+    // Gauss-Jordan on [A | b], singular A with consistent b gives 0 for the free entries
+    pub(crate) fn solve(&self, ans: &[f64]) -> Result<Vec<f64>, CalError> {
+        const EPSILON: f64 = 1e-32;
+
+        let size: usize = self.require_square()?;
+
+        if ans.len() != size {
+            return Err(CalError::DimensionMismatch { expected: size, got: ans.len() });
+        }
+
+        let mut aug_matrix: Matrix = self.augment(&Matrix { rows: size, cols: 1, data: ans.to_vec() })?;
+
+        for i in 0..size {
+            let max_row: usize = aug_matrix.pivot_row(i, i);
+            aug_matrix.swap_rows(i, max_row);
+
+            if aug_matrix[(i, i)].abs() < EPSILON {
+                continue;
+            }
+
+            let pivot: f64 = aug_matrix[(i, i)];
+            for value in aug_matrix.row_mut(i) {
+                *value /= pivot;
+            }
+
+            for j in 0..size {
+                if j != i {
+                    let ratio: f64 = aug_matrix[(j, i)];
+                    aug_matrix.sub_row(j, i, ratio, 0);
+                }
+            }
+        }
+
+        for row in aug_matrix.row_iter() {
+            let lhs_zero    = row[..size].iter().all(|&v| v.abs() < EPSILON);
+            let rhs_nonzero = row[size].abs() >= EPSILON;
+            if lhs_zero && rhs_nonzero {
+                return Err(CalError::Inconsistent);
+            }
+        }
+
+        Ok(aug_matrix.column(size))
+    }
+}
+
+impl<'a> MatrixView<'a> {
+    pub(crate) fn row(&self, i: usize) -> &'a [f64] {
+        &self.matrix.row(self.row + i)[self.col..self.col + self.cols]
+    }
+
+    pub(crate) fn to_matrix(&self) -> Matrix {
+        let data: Vec<f64> = (0..self.rows).flat_map(|i| self.row(i).iter().copied()).collect();
+
+        Matrix { rows: self.rows, cols: self.cols, data }
+    }
+}
+
+impl Index<(usize, usize)> for Matrix {
+    type Output = f64;
+    fn index(&self, (i, j): (usize, usize)) -> &f64 {
+        &self.data[i * self.cols + j]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Matrix {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut f64 {
+        &mut self.data[i * self.cols + j]
+    }
+}
+
+impl Serialize for Matrix {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.row_iter())
+    }
+}
+
+
+
+// Calculations
+
+impl Matrix {
+    // row of the largest |a_ij| for i >= from in column col (partial pivoting)
+    pub(crate) fn pivot_row(&self, from: usize, col: usize) -> usize {
+        let mut max_row: usize = from;
+        for j in from + 1..self.rows {
+            if self[(j, col)].abs() > self[(max_row, col)].abs() {
+                max_row = j;
+            }
+        }
+
+        max_row
+    }
+
+    // row_target -= ratio * row_source, from column `from` to the end
+    pub(crate) fn sub_row(&mut self, target: usize, source: usize, ratio: f64, from: usize) {
+        let cols: usize = self.cols;
+        for k in from..cols {
+            let value: f64 = self.data[source * cols + k];
+            self.data[target * cols + k] -= ratio * value;
+        }
+    }
+}
@@ -0,0 +1,248 @@
+// nonlinear.rs
+use crate::utils;
+use crate::error::CalError;
+use crate::expression::parse_expr;
+use crate::convergence::{IterativeResult, Outcome, StopCriterion, StoppingCriteria};
+
+use meval::Expr;
+use serde::Serialize;
+use serde_wasm_bindgen::to_value;
+use wasm_bindgen::prelude::*;
+
+
+
+// Duplication struct
+
+/*
+ * ---- Systems of nonlinear equations F(x) = 0 ----
+ *
+ *  equations[i] is f_i over the named variables, x is bound by variables order
+ *  jacobian (if supplied) is flat row-major: jacobian[i * n + j] = d f_i / d x_j
+ */
+
+// x is after the step, residual is ||F(x)||_2 there, jacobian is the one used for the step
+#[derive(Serialize)] // Serialize the struct
+pub struct NewtonSystemStep {
+    pub iteration: u64,
+    pub x: Vec<f64>,
+    pub residual: f64,
+    pub jacobian: Vec<Vec<f64>>,
+    pub error: f64
+}
+
+// Supplied : jacobian expressions are given, FiniteDifference : central difference per column
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub enum JacobianSource {
+    Supplied,
+    FiniteDifference
+}
+
+#[derive(Serialize)] // Serialize the struct
+pub struct NewtonSystemResult {
+    pub jacobian_source: JacobianSource,
+    pub iterations: Vec<NewtonSystemStep>,
+    pub outcome: Outcome,
+    pub stopped_by: Option<StopCriterion>
+}
+
+
+
+// wasm conversion JsValue
+
+// jacobian is empty for finite difference
+#[wasm_bindgen]
+pub fn newton_system(equations: Vec<String>, variables: Vec<String>, initial: Vec<f64>, jacobian: Vec<String>, criteria: JsValue) -> JsValue {
+    let criteria: StoppingCriteria = match StoppingCriteria::from_js(criteria) {
+        Ok(c)  => c,
+        Err(e) => return e.into(),
+    };
+
+    match newton_system_core(&equations, &variables, &initial, &jacobian, &criteria) {
+        Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
+        Err(e) => e.into(),
+    }
+}
+
+
+
+// Add implement method
+
+/*
+ *  Newton-Raphson for systems
+ *      J(x_k) dx = -F(x_k),  x_(k+1) = x_k + dx
+ *  dx is solved by utils::guass, singular J stops with DerivativeZero
+ */
+pub fn newton_system_core(equations: &[String], variables: &[String], initial: &[f64], jacobian: &[String], criteria: &StoppingCriteria) -> Result<NewtonSystemResult, CalError> {
+    let system = System::new(equations, variables, initial)?;
+    let n      : usize = system.size();
+
+    let source: JacobianSource = if jacobian.is_empty() {
+        JacobianSource::FiniteDifference
+    }
+    else if jacobian.len() != n * n {
+        return Err(CalError::DimensionMismatch { expected: n * n, got: jacobian.len() });
+    }
+    else {
+        JacobianSource::Supplied
+    };
+    let jacobian_exprs: Vec<Expr> = jacobian.iter().map(|equation| parse_expr(equation)).collect::<Result<_, _>>()?;
+
+    criteria.validate()?;
+
+    let mut result : IterativeResult<NewtonSystemStep> = IterativeResult::new();
+    let mut x      : Vec<f64>                          = initial.to_vec();
+    let mut fx     : Vec<f64>                          = system.evaluate(&x)?;
+
+    if fx.iter().any(|v| !v.is_finite()) {
+        return Err(CalError::invalid_argument("initial", "F(x) is not finite at the initial guess"));
+    }
+
+    for iter in 0..criteria.max_iter {
+        let jacobian: Vec<Vec<f64>> = match source {
+            JacobianSource::Supplied         => system.evaluate_jacobian(&jacobian_exprs, &x)?,
+            JacobianSource::FiniteDifference => system.finite_jacobian(&x)?,
+        };
+
+        if jacobian.iter().flatten().any(|v| !v.is_finite()) {
+            result.fail(Outcome::NonFinite);
+            break;
+        }
+
+        let neg_fx: Vec<f64> = fx.iter().map(|v| -v).collect();
+        let dx: Vec<f64> = match utils::guass(&jacobian, &neg_fx) {
+            Ok(dx) if dx.iter().all(|v| v.is_finite()) => dx,
+            _ => {
+                result.fail(Outcome::DerivativeZero);
+                break;
+            },
+        };
+
+        let x_prev : Vec<f64> = x.clone();
+        for (xi, dxi) in x.iter_mut().zip(&dx) {
+            *xi += dxi;
+        }
+
+        if let Some(outcome) = Outcome::inspect_vec(&x) {
+            result.fail(outcome);
+            break;
+        }
+
+        fx = system.evaluate(&x)?;
+        let residual: f64 = norm(&fx);
+
+        result.iterations.push(NewtonSystemStep {
+            iteration: iter + 1,
+            x: x.clone(),
+            residual,
+            jacobian,
+            error: relative_error(&x, &x_prev)
+        });
+
+        if let Some(criterion) = criteria.check_vec(&x, &x_prev, residual) {
+            result.converge(criterion);
+            break;
+        }
+        if !residual.is_finite() {
+            result.fail(Outcome::NonFinite);
+            break;
+        }
+    }
+
+    Ok(NewtonSystemResult {
+        jacobian_source: source,
+        iterations: result.iterations,
+        outcome: result.outcome,
+        stopped_by: result.stopped_by
+    })
+}
+
+
+
+// Calculations
+
+// Parsed equations with variable names, shared by the system solvers (also root_eq::broyden_core)
+pub(crate) struct System {
+    exprs: Vec<Expr>,
+    variables: Vec<String>
+}
+
+impl System {
+    pub(crate) fn new(equations: &[String], variables: &[String], initial: &[f64]) -> Result<Self, CalError> {
+        if equations.is_empty() {
+            return Err(CalError::InsufficientData { required: 1, got: 0 });
+        }
+        if variables.len() != equations.len() {
+            return Err(CalError::DimensionMismatch { expected: equations.len(), got: variables.len() });
+        }
+        if initial.len() != equations.len() {
+            return Err(CalError::DimensionMismatch { expected: equations.len(), got: initial.len() });
+        }
+        for (i, var) in variables.iter().enumerate() {
+            if variables[..i].contains(var) {
+                return Err(CalError::invalid_argument("variables", &format!("`{}` is repeated", var)));
+            }
+        }
+
+        let exprs: Vec<Expr> = equations.iter().map(|equation| parse_expr(equation)).collect::<Result<_, _>>()?;
+
+        Ok(System { exprs, variables: variables.to_vec() })
+    }
+
+    pub(crate) fn size(&self) -> usize {
+        self.exprs.len()
+    }
+
+    fn bindings<'a>(&'a self, x: &[f64]) -> Vec<(&'a str, f64)> {
+        self.variables.iter().map(|var| var.as_str()).zip(x.iter().copied()).collect()
+    }
+
+    // NaN / inf is kept as value, checked by the caller against the whole vector
+    fn evaluate_one(&self, expr: &Expr, x: &[f64]) -> Result<f64, CalError> {
+        utils::evaluate_expr_vars(expr, &self.bindings(x))
+    }
+
+    // F(x)
+    pub(crate) fn evaluate(&self, x: &[f64]) -> Result<Vec<f64>, CalError> {
+        self.exprs.iter().map(|expr| self.evaluate_one(expr, x)).collect()
+    }
+
+    fn evaluate_jacobian(&self, jacobian: &[Expr], x: &[f64]) -> Result<Vec<Vec<f64>>, CalError> {
+        jacobian.chunks(self.size())
+            .map(|row| row.iter().map(|expr| self.evaluate_one(expr, x)).collect())
+            .collect()
+    }
+
+    // central difference, h = cbrt(eps) * max(|x_j|, 1)
+    pub(crate) fn finite_jacobian(&self, x: &[f64]) -> Result<Vec<Vec<f64>>, CalError> {
+        let n            : usize         = self.size();
+        let mut jacobian : Vec<Vec<f64>> = vec![vec![0.0; n]; n];
+
+        for j in 0..n {
+            let h           : f64      = f64::EPSILON.cbrt() * x[j].abs().max(1.0);
+            let mut x_plus  : Vec<f64> = x.to_vec();
+            let mut x_minus : Vec<f64> = x.to_vec();
+            x_plus[j]  += h;
+            x_minus[j] -= h;
+
+            let f_plus  : Vec<f64> = self.evaluate(&x_plus)?;
+            let f_minus : Vec<f64> = self.evaluate(&x_minus)?;
+            for i in 0..n {
+                jacobian[i][j] = (f_plus[i] - f_minus[i]) / (2.0 * h);
+            }
+        }
+
+        Ok(jacobian)
+    }
+}
+
+pub(crate) fn norm(v: &[f64]) -> f64 {
+    v.iter().map(|a| a * a).sum::<f64>().sqrt()
+}
+
+// percent, largest change against largest component (same unit as `error` of scalar methods)
+pub(crate) fn relative_error(x_new: &[f64], x_old: &[f64]) -> f64 {
+    let step : f64 = x_new.iter().zip(x_old).fold(0.0_f64, |acc, (a, b)| acc.max((a - b).abs()));
+    let size : f64 = x_new.iter().fold(0.0_f64, |acc, a| acc.max(a.abs()));
+
+    if size == 0.0 { 0.0 } else { step / size * 100.0 }
+}
@@ -0,0 +1,467 @@
+// polynomial.rs
+use crate::utils;
+use crate::complex::Complex;
+use crate::error::CalError;
+use crate::expression::parse_expr;
+use crate::symbolic::Node;
+use crate::convergence::{IterativeResult, Outcome, StopCriterion, StoppingCriteria};
+
+use std::f64::consts;
+
+use serde::Serialize;
+use serde_wasm_bindgen::to_value;
+use wasm_bindgen::prelude::*;
+
+
+
+// Duplication struct
+
+/*
+ * ---- Polynomial roots ----
+ *
+ *  Coefficients are in ascending order [a0, a1, ..., an] for a0 + a1 x + ... + an x^n,
+ *  same order as `answer` of lsq_regression
+ *
+ *  Aberth-Ehrlich : all roots at once, cubic convergence for simple roots
+ *  Bairstow       : quadratic factors x^2 - r x - s one by one with deflation
+ */
+#[derive(Serialize)] // Serialize the struct
+pub struct AberthResult {
+    pub iteration: u64,
+    pub(crate) roots: Vec<Complex>,
+    pub error: f64
+}
+
+// roots are sorted by real part then imaginary part
+#[derive(Serialize)] // Serialize the struct
+pub struct PolynomialRootsResult {
+    pub roots: Vec<Complex>,
+    pub iterations: Vec<AberthResult>,
+    pub outcome: Outcome,
+    pub stopped_by: Option<StopCriterion>
+}
+
+// factor counts the quadratic factor being searched (1 for the first deflation)
+#[derive(Serialize)] // Serialize the struct
+pub struct BairstowStep {
+    pub factor: usize,
+    pub iteration: u64,
+    pub r: f64,
+    pub s: f64,
+    pub error_r: f64,
+    pub error_s: f64
+}
+
+// x^2 - r x - s
+#[derive(Serialize)] // Serialize the struct
+pub struct QuadraticFactor {
+    pub r: f64,
+    pub s: f64,
+    pub(crate) roots: Vec<Complex>
+}
+
+// factors are the converged quadratics, remaining linear / quadratic quotient is solved directly
+// missing_roots counts the roots left in the quotient when a factor fails to converge
+#[derive(Serialize)] // Serialize the struct
+pub struct BairstowResult {
+    pub factors: Vec<QuadraticFactor>,
+    pub roots: Vec<Complex>,
+    pub missing_roots: usize,
+    pub iterations: Vec<BairstowStep>,
+    pub outcome: Outcome,
+    pub stopped_by: Option<StopCriterion>
+}
+
+const MAX_DEGREE: usize = 100;
+
+// |im| below this (relative to |z|) is printed as real root
+const REAL_TOL: f64 = 1e-12;
+
+
+
+// wasm conversion JsValue
+
+// "(x - 1)^2 * (x + 2)" => [2, -3, 0, 1]
+#[wasm_bindgen]
+pub fn polynomial_coefficients(equation: &str) -> JsValue {
+    match polynomial_coefficients_core(equation) {
+        Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
+        Err(e) => e.into(),
+    }
+}
+
+#[wasm_bindgen]
+pub fn polynomial_roots(coefficients: Vec<f64>, criteria: JsValue) -> JsValue {
+    let criteria: StoppingCriteria = match StoppingCriteria::from_js(criteria) {
+        Ok(c)  => c,
+        Err(e) => return e.into(),
+    };
+
+    match polynomial_roots_core(&coefficients, &criteria) {
+        Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
+        Err(e) => e.into(),
+    }
+}
+
+// r, s are the initial guess of x^2 - r x - s (e.g. -1, -1)
+#[wasm_bindgen]
+pub fn bairstow(coefficients: Vec<f64>, r: f64, s: f64, criteria: JsValue) -> JsValue {
+    let criteria: StoppingCriteria = match StoppingCriteria::from_js(criteria) {
+        Ok(c)  => c,
+        Err(e) => return e.into(),
+    };
+
+    match bairstow_core(&coefficients, r, s, &criteria) {
+        Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
+        Err(e) => e.into(),
+    }
+}
+
+
+
+// Add implement method
+
+// Expand expression of x into ascending coefficients, other names must be constants
+pub fn polynomial_coefficients_core(equation: &str) -> Result<Vec<f64>, CalError> {
+    let node: Node = Node::from_expr(&parse_expr(equation)?)?;
+    let mut coefficients: Vec<f64> = expand(&node)?;
+
+    if coefficients.iter().any(|a| !a.is_finite()) {
+        return Err(CalError::invalid_argument("equation", "coefficients must be finite"));
+    }
+
+    while coefficients.len() > 1 && coefficients[coefficients.len() - 1] == 0.0 {
+        coefficients.pop();
+    }
+
+    Ok(coefficients)
+}
+
+/*
+ *  Aberth-Ehrlich, with w_k = p(z_k) / p'(z_k)
+ *      z_k = z_k - w_k / (1 - w_k * sum_(j != k) 1 / (z_k - z_j))
+ *  Initial roots are spread on a circle of Cauchy bound radius (rotated off the real axis)
+ *  Zero roots (a0 = 0) are factored out first, so they are exact
+ */
+pub fn polynomial_roots_core(coefficients: &[f64], criteria: &StoppingCriteria) -> Result<PolynomialRootsResult, CalError> {
+    let (zeros, poly) = normalize(coefficients)?;
+
+    criteria.validate()?;
+
+    let degree     : usize        = poly.len() - 1;
+    let bound      : f64          = 1.0 + poly[..degree].iter().fold(0.0_f64, |acc, a| acc.max(a.abs()));
+    let mut roots  : Vec<Complex> = (0..degree)
+        .map(|k| Complex::from_polar(bound, 2.0 * consts::PI * k as f64 / degree as f64 + 0.4))
+        .collect();
+    let mut result : IterativeResult<AberthResult> = IterativeResult::new();
+
+    // a0 + a1 x with leading 1 needs no iteration
+    if degree == 1 {
+        roots[0] = Complex::real(-poly[0]);
+        result.converge(StopCriterion::Residual);
+    }
+
+    for iter in 0..criteria.max_iter {
+        if degree <= 1 {
+            break;
+        }
+
+        let mut step     : f64 = 0.0;
+        let mut residual : f64 = 0.0;
+
+        for k in 0..degree {
+            let (p, dp) = horner_complex(&poly, roots[k]);
+            residual = residual.max(p.abs());
+
+            if p.abs() == 0.0 {
+                continue;
+            }
+
+            let w   : Complex = p / dp;
+            let sum : Complex = (0..degree)
+                .filter(|&j| j != k)
+                .fold(Complex::real(0.0), |acc, j| acc + Complex::real(1.0) / (roots[k] - roots[j]));
+            let correction: Complex = w / (Complex::real(1.0) - w * sum);
+
+            roots[k] = roots[k] - correction;
+            step = step.max(correction.abs());
+        }
+
+        let size: f64 = roots.iter().fold(0.0_f64, |acc, z| acc.max(z.abs()));
+
+        result.iterations.push(AberthResult {
+            iteration: iter + 1,
+            roots: roots.clone(),
+            error: if size > 0.0 { step / size * 100.0 } else { 0.0 }
+        });
+
+        if roots.iter().any(|z| !z.is_finite()) {
+            result.fail(Outcome::NonFinite);
+            break;
+        }
+        // largest correction against largest root (magnitude)
+        if let Some(criterion) = criteria.residual_met(residual).or_else(|| criteria.norm_step_met(step, size)) {
+            result.converge(criterion);
+            break;
+        }
+    }
+
+    roots.extend(vec![Complex::real(0.0); zeros]);
+
+    Ok(PolynomialRootsResult {
+        roots: sort_roots(roots),
+        iterations: result.iterations,
+        outcome: result.outcome,
+        stopped_by: result.stopped_by
+    })
+}
+
+/*
+ *  Bairstow (synthetic division by x^2 - r x - s)
+ *      b_n = a_n, b_(n-1) = a_(n-1) + r b_n, b_i = a_i + r b_(i+1) + s b_(i+2)
+ *      c_n = b_n, c_(n-1) = b_(n-1) + r c_n, c_i = b_i + r c_(i+1) + s c_(i+2)
+ *      c_2 dr + c_3 ds = -b_1
+ *      c_1 dr + c_2 ds = -b_0
+ *  After each factor the quotient b_2..b_n is the next polynomial, starting from the last r, s
+ */
+pub fn bairstow_core(coefficients: &[f64], mut r: f64, mut s: f64, criteria: &StoppingCriteria) -> Result<BairstowResult, CalError> {
+    let (zeros, mut poly) = normalize(coefficients)?;
+
+    criteria.validate()?;
+
+    let degree        : usize                          = zeros + poly.len() - 1;
+    let mut factors   : Vec<QuadraticFactor>           = Vec::new();
+    let mut roots     : Vec<Complex>                   = vec![Complex::real(0.0); zeros];
+    let mut result    : IterativeResult<BairstowStep>  = IterativeResult::new();
+    let mut criterion : StopCriterion                  = StopCriterion::Residual;
+
+    while poly.len() > 3 {
+        let n        : usize                          = poly.len() - 1;
+        let mut b    : Vec<f64>                       = vec![0.0; n + 1];
+        let mut stop : Result<StopCriterion, Outcome> = Err(Outcome::MaxIterations);
+
+        for iter in 0..criteria.max_iter {
+            let mut c: Vec<f64> = vec![0.0; n + 1];
+
+            b[n]     = poly[n];
+            b[n - 1] = poly[n - 1] + r * b[n];
+            c[n]     = b[n];
+            c[n - 1] = b[n - 1] + r * c[n];
+            for i in (0..n - 1).rev() {
+                b[i] = poly[i] + r * b[i + 1] + s * b[i + 2];
+                c[i] = b[i] + r * c[i + 1] + s * c[i + 2];
+            }
+
+            let det: f64 = c[2] * c[2] - c[3] * c[1];
+            if det == 0.0 {
+                stop = Err(Outcome::DerivativeZero);
+                break;
+            }
+
+            let dr : f64 = (-b[1] * c[2] + b[0] * c[3]) / det;
+            let ds : f64 = (-b[0] * c[2] + b[1] * c[1]) / det;
+            let (r_prev, s_prev) = (r, s);
+            r += dr;
+            s += ds;
+
+            result.iterations.push(BairstowStep {
+                factor: factors.len() + 1,
+                iteration: iter + 1,
+                r,
+                s,
+                error_r: utils::error_calc(r, r_prev),
+                error_s: utils::error_calc(s, s_prev)
+            });
+
+            if !r.is_finite() || !s.is_finite() {
+                stop = Err(Outcome::NonFinite);
+                break;
+            }
+            // residual is the remainder b_1 x + b_0 of the previous r, s, step needs both r and s settled
+            let step_met: Option<StopCriterion> = criteria.step_met(r, r_prev).filter(|_| criteria.step_met(s, s_prev).is_some());
+            if let Some(met) = criteria.residual_met(b[0].abs().max(b[1].abs())).or(step_met) {
+                stop = Ok(met);
+                break;
+            }
+        }
+
+        match stop {
+            Ok(met) => criterion = met,
+            Err(outcome) => {
+                result.fail(outcome);
+                break;
+            },
+        }
+
+        let pair: Vec<Complex> = quadratic_roots(1.0, -r, -s);
+        roots.extend(pair.iter().copied());
+        factors.push(QuadraticFactor { r, s, roots: pair });
+
+        // quotient of the converged factor
+        poly = b[2..].to_vec();
+    }
+
+    // remaining quotient of degree 1 or 2 is solved directly
+    if poly.len() <= 3 {
+        match poly.len() {
+            3 => roots.extend(quadratic_roots(poly[2], poly[1], poly[0])),
+            _ => roots.push(Complex::real(-poly[0] / poly[1])),
+        }
+        result.converge(criterion);
+    }
+
+    Ok(BairstowResult {
+        factors,
+        missing_roots: degree - roots.len(),
+        roots: sort_roots(roots),
+        iterations: result.iterations,
+        outcome: result.outcome,
+        stopped_by: result.stopped_by
+    })
+}
+
+
+
+// Calculations
+
+// (number of zero roots, monic polynomial without them)
+fn normalize(coefficients: &[f64]) -> Result<(usize, Vec<f64>), CalError> {
+    if coefficients.iter().any(|a| !a.is_finite()) {
+        return Err(CalError::invalid_argument("coefficients", "must be finite"));
+    }
+
+    let mut poly: Vec<f64> = coefficients.to_vec();
+    while poly.last() == Some(&0.0) {
+        poly.pop();
+    }
+
+    if poly.len() < 2 {
+        return Err(CalError::invalid_argument("coefficients", "polynomial must be of degree 1 or more"));
+    }
+    if poly.len() - 1 > MAX_DEGREE {
+        return Err(CalError::invalid_argument("coefficients", &format!("degree must be at most {}", MAX_DEGREE)));
+    }
+
+    let zeros : usize = poly.iter().take_while(|&&a| a == 0.0).count();
+    let lead  : f64   = poly[poly.len() - 1];
+
+    Ok((zeros, poly[zeros..].iter().map(|a| a / lead).collect()))
+}
+
+// (p(z), p'(z))
+fn horner_complex(poly: &[f64], z: Complex) -> (Complex, Complex) {
+    let mut p  : Complex = Complex::real(0.0);
+    let mut dp : Complex = Complex::real(0.0);
+
+    for &a in poly.iter().rev() {
+        dp = dp * z + p;
+        p  = p * z + Complex::real(a);
+    }
+
+    (p, dp)
+}
+
+// a x^2 + b x + c, cancellation-free form
+fn quadratic_roots(a: f64, b: f64, c: f64) -> Vec<Complex> {
+    let disc: f64 = b * b - 4.0 * a * c;
+
+    if disc < 0.0 {
+        let re: f64 = -b / (2.0 * a);
+        let im: f64 = (-disc).sqrt() / (2.0 * a);
+        return vec![Complex::new(re, -im.abs()), Complex::new(re, im.abs())];
+    }
+
+    let q: f64 = -0.5 * (b + b.signum() * disc.sqrt());
+    if q == 0.0 {
+        return vec![Complex::real(0.0), Complex::real(0.0)];
+    }
+
+    vec![Complex::real(q / a), Complex::real(c / q)]
+}
+
+// snap tiny real / imaginary part to 0, then order by (re, im)
+// re is compared at REAL_TOL resolution so a conjugate pair stays together
+fn sort_roots(mut roots: Vec<Complex>) -> Vec<Complex> {
+    for z in roots.iter_mut() {
+        let tol: f64 = REAL_TOL * z.abs().max(1.0);
+        if z.im.abs() <= tol {
+            z.im = 0.0;
+        }
+        if z.re.abs() <= tol {
+            z.re = 0.0;
+        }
+    }
+
+    let key = |z: &Complex| ((z.re / REAL_TOL).round(), z.im);
+    roots.sort_by(|a, b| key(a).partial_cmp(&key(b)).unwrap_or(std::cmp::Ordering::Equal));
+
+    roots
+}
+
+// Ascending coefficients of node, subtree without x is evaluated as constant
+fn expand(node: &Node) -> Result<Vec<f64>, CalError> {
+    if !node.depends_on("x") {
+        let value: f64 = utils::evaluate_expr_vars(&parse_expr(&node.to_string())?, &[])?;
+        return Ok(vec![value]);
+    }
+
+    let result: Vec<f64> = match node {
+        Node::Var(_)    => vec![0.0, 1.0],
+        Node::Neg(a)    => expand(a)?.iter().map(|v| -v).collect(),
+        Node::Add(a, b) => add_poly(&expand(a)?, &expand(b)?, 1.0),
+        Node::Sub(a, b) => add_poly(&expand(a)?, &expand(b)?, -1.0),
+        Node::Mul(a, b) => mul_poly(&expand(a)?, &expand(b)?)?,
+        Node::Div(a, b) if !b.depends_on("x") => {
+            let divisor: f64 = expand(b)?[0];
+            expand(a)?.iter().map(|v| v / divisor).collect()
+        },
+        Node::Pow(a, b) if !b.depends_on("x") => {
+            let exponent: f64 = expand(b)?[0];
+            if exponent < 0.0 || exponent.fract() != 0.0 || exponent > MAX_DEGREE as f64 {
+                return Err(not_polynomial());
+            }
+
+            let base: Vec<f64> = expand(a)?;
+            let mut power: Vec<f64> = vec![1.0];
+            for _ in 0..exponent as usize {
+                power = mul_poly(&power, &base)?;
+            }
+            power
+        },
+        _ => return Err(not_polynomial()),
+    };
+
+    Ok(result)
+}
+
+fn not_polynomial() -> CalError {
+    CalError::invalid_argument("equation", "must be a polynomial in x (non-negative integer powers only)")
+}
+
+// a + sign * b
+fn add_poly(a: &[f64], b: &[f64], sign: f64) -> Vec<f64> {
+    let mut result: Vec<f64> = vec![0.0; a.len().max(b.len())];
+    for (i, v) in a.iter().enumerate() {
+        result[i] += v;
+    }
+    for (i, v) in b.iter().enumerate() {
+        result[i] += sign * v;
+    }
+
+    result
+}
+
+fn mul_poly(a: &[f64], b: &[f64]) -> Result<Vec<f64>, CalError> {
+    if a.len() + b.len() - 2 > MAX_DEGREE {
+        return Err(CalError::invalid_argument("equation", &format!("degree must be at most {}", MAX_DEGREE)));
+    }
+
+    let mut result: Vec<f64> = vec![0.0; a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            result[i + j] += x * y;
+        }
+    }
+
+    Ok(result)
+}
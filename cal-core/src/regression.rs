@@ -1,5 +1,8 @@
 // regression.rs
 use crate::utils::{self};
+use crate::error::CalError;
+use crate::matrix::Matrix;
+use crate::linear_eq::{QrFactorization, QrMethod};
 
 use serde::Serialize;
 use serde_wasm_bindgen::to_value;
@@ -9,11 +12,12 @@ use wasm_bindgen::prelude::*;
 
 // Duplication struct
 
+// matrix & solution are the normal equations (display), answer is solved from the design matrix by QR
 #[derive(Serialize)] // Serialize the struct
-pub(crate) struct RegressionResult {
-    pub(crate) matrix: Vec<Vec<f64>>,
-    pub(crate) solution: Vec<f64>,
-    pub(crate) answer: Vec<f64>
+pub struct RegressionResult {
+    pub matrix: Vec<Vec<f64>>,
+    pub solution: Vec<f64>,
+    pub answer: Vec<f64>
 }
 
 
@@ -24,7 +28,7 @@ pub(crate) struct RegressionResult {
 pub fn lsq_regression(x: Vec<f64>, y: Vec<f64>, degree: u32) -> JsValue {
     match lsq_regression_core(x, y, degree) {
         Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
-        Err(e) => JsValue::from_str(&e),
+        Err(e) => e.into(),
     }
 }
 
@@ -32,7 +36,7 @@ pub fn lsq_regression(x: Vec<f64>, y: Vec<f64>, degree: u32) -> JsValue {
 pub fn mult_lsq_regression(x: Vec<f64>, y: Vec<f64>, degree: Vec<u32>) -> JsValue {
     match mult_lsq_regression_core(x, y, degree) {
         Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
-        Err(e) => JsValue::from_str(&e),
+        Err(e) => e.into(),
     }
 }
 
@@ -46,10 +50,10 @@ pub fn combinations_regression(sizes: Vec<u32>) -> JsValue {
 
 // Calculations
 
-pub(crate) fn lsq_regression_core(x: Vec<f64>, y: Vec<f64>, degree: u32) -> Result<RegressionResult, String> {
+pub fn lsq_regression_core(x: Vec<f64>, y: Vec<f64>, degree: u32) -> Result<RegressionResult, CalError> {
 
     if x.len() != y.len() {
-        return Err("X & Y is not matches".to_string());
+        return Err(CalError::DimensionMismatch { expected: x.len(), got: y.len() });
     }
 
     // Sorted by x for easier calculation
@@ -77,28 +81,29 @@ pub(crate) fn lsq_regression_core(x: Vec<f64>, y: Vec<f64>, degree: u32) -> Resu
                                 .sum();
     }
 
+    // row : [1, x, x^2 .. x^degree]
+    let design: Vec<Vec<f64>> = sorted_x.iter()
+        .map(|&value| (0..mat_size).map(|i| value.powi(i as i32)).collect())
+        .collect();
+
     
-    match utils::guass(&mat, &ans) {
-        Ok(result) => {
-            
-            Ok(RegressionResult {
-                matrix: mat,
-                solution: ans,
-                answer: result 
-            })
-        },
-        Err(e) => return Err(e),
-    }
+    let result: Vec<f64> = qr_regression(&design, &sorted_y)?;
+
+    Ok(RegressionResult {
+        matrix: mat,
+        solution: ans,
+        answer: result
+    })
 }
 
-pub(crate) fn mult_lsq_regression_core(x: Vec<f64>, y: Vec<f64>, degree: Vec<u32>) -> Result<RegressionResult, String> {
+pub fn mult_lsq_regression_core(x: Vec<f64>, y: Vec<f64>, degree: Vec<u32>) -> Result<RegressionResult, CalError> {
     
     if x.len() % degree.len() != 0 {
-        return Err("Datas incomplete".to_string());
+        return Err(CalError::invalid_argument("x", "data length must be a multiple of variable count"));
     }
     
     if x.len() / degree.len() != y.len() {
-        return Err("X & Y is not matches".to_string());
+        return Err(CalError::DimensionMismatch { expected: x.len() / degree.len(), got: y.len() });
     }
 
     // no sorting cause of the y if always be matches for every row at x
@@ -116,7 +121,7 @@ pub(crate) fn mult_lsq_regression_core(x: Vec<f64>, y: Vec<f64>, degree: Vec<u32
 
 // Calculations
 
-fn mult_linear_lsq_calc(x: Vec<f64>, y: Vec<f64>, degree: Vec<u32>) -> Result<RegressionResult, String>{
+fn mult_linear_lsq_calc(x: Vec<f64>, y: Vec<f64>, degree: Vec<u32>) -> Result<RegressionResult, CalError>{
     let mat_size: usize = degree.len() + 1;
 
     let mut mat: Vec<Vec<f64>> = vec![vec![0.0; mat_size]; mat_size];
@@ -152,20 +157,21 @@ fn mult_linear_lsq_calc(x: Vec<f64>, y: Vec<f64>, degree: Vec<u32>) -> Result<Re
             }
     }
 
-    match utils::guass(&mat, &ans) {
-        Ok(result) => {
-            
-            Ok(RegressionResult {
-                matrix: mat,
-                solution: ans,
-                answer: result 
-            })
-        },
-        Err(e) => return Err(e),
-    }
+    // row : [1, x1, x2 .. xn]
+    let design: Vec<Vec<f64>> = (0..y.len())
+        .map(|row| std::iter::once(1.0).chain(datas.iter().map(|data| data[row])).collect())
+        .collect();
+
+    let result: Vec<f64> = qr_regression(&design, &y)?;
+
+    Ok(RegressionResult {
+        matrix: mat,
+        solution: ans,
+        answer: result
+    })
 }
 
-fn mult_polynomial_lsq_calc(x: Vec<f64>, y: Vec<f64>, degree: Vec<u32>) -> Result<RegressionResult, String>{
+fn mult_polynomial_lsq_calc(x: Vec<f64>, y: Vec<f64>, degree: Vec<u32>) -> Result<RegressionResult, CalError>{
 
     // combinations include the 0 deg.
     let combinations = generate_combinations(degree.iter().map(|&deg| deg+1).collect());
@@ -206,7 +212,7 @@ fn mult_polynomial_lsq_calc(x: Vec<f64>, y: Vec<f64>, degree: Vec<u32>) -> Resul
                     }
 
                     if term.is_empty() || term[0].is_empty() {
-                        return Err(format!("Invalid term matrix generated at {} {}", i, j));
+                        return Err(CalError::invalid_argument("degree", &format!("invalid term matrix generated at {} {}", i, j)));
                     }
 
                     let result_term = (0..term[0].len())
@@ -245,21 +251,52 @@ fn mult_polynomial_lsq_calc(x: Vec<f64>, y: Vec<f64>, degree: Vec<u32>) -> Resul
         
     }
 
-    match utils::guass(&mat, &ans) {
-        Ok(result) => {
-            
-            Ok(RegressionResult {
-                matrix: mat,
-                solution: ans,
-                answer: result 
-            })
-        },
-        Err(e) => return Err(e),
+    // row : every combination as x1^c1 * x2^c2 .. xn^cn
+    let design: Vec<Vec<f64>> = (0..y.len())
+        .map(|row| combinations.iter()
+            .map(|comb| comb.iter().zip(&datas).map(|(&power, data)| data[row].powi(power as i32)).product())
+            .collect())
+        .collect();
+
+    let result: Vec<f64> = qr_regression(&design, &y)?;
+
+    Ok(RegressionResult {
+        matrix: mat,
+        solution: ans,
+        answer: result
+    })
+}
+
+/*
+ *  Least squares on the design matrix X (no normal equations, condition number is not squared)
+ *  Columns are scaled to unit norm first (x^k grows fast), answer is scaled back
+ *  More terms than points: minimum norm answer (of the scaled terms) from X^T = Q R
+ *  Dependent columns (rows) are reported as Singular
+ */
+fn qr_regression(design: &[Vec<f64>], y: &[f64]) -> Result<Vec<f64>, CalError> {
+    let mut design_mat : Matrix   = Matrix::from_rows(design)?;
+    let scales         : Vec<f64> = (0..design_mat.cols()).map(|j| utils::dot_prod_self(&design_mat.column(j)).sqrt()).collect();
+
+    if scales.contains(&0.0) {
+        return Err(CalError::Singular);
     }
+    for (j, &scale) in scales.iter().enumerate() {
+        let column: Vec<f64> = design_mat.column(j).iter().map(|a| a / scale).collect();
+        design_mat.set_column(j, &column);
+    }
+
+    let scaled: Vec<f64> = if design_mat.rows() < design_mat.cols() {
+        QrFactorization::factor(&design_mat.transpose(), QrMethod::Householder)?.solve_transposed(y)?
+    }
+    else {
+        QrFactorization::factor(&design_mat, QrMethod::Householder)?.solve(y)?
+    };
+
+    Ok(scaled.iter().zip(&scales).map(|(a, scale)| a / scale).collect())
 }
 
 // NOTE: Generate deg + 1 row this ctx.
-pub(crate) fn generate_combinations(sizes: Vec<u32>) -> Vec<Vec<u32>> {
+pub fn generate_combinations(sizes: Vec<u32>) -> Vec<Vec<u32>> {
     // FALLBACK: Empty Vec
     if sizes.is_empty() || sizes.iter().any(|&s| s == 0) {
         return Vec::new();
@@ -1,5 +1,14 @@
 // root_eq.rs
 use crate::utils;
+use crate::autodiff;
+use crate::complex::{self, Complex};
+use crate::error::CalError;
+use crate::expression::parse_expr;
+use crate::symbolic::symbolic_derivatives_core;
+use crate::nonlinear::{self, System};
+use crate::matrix::Matrix;
+use crate::linear_eq::LuFactorization;
+use crate::convergence::{estimate_order, IterativeResult, Outcome, StopCriterion, StoppingCriteria};
 
 use serde::Serialize;
 use serde_wasm_bindgen::to_value;
@@ -11,78 +20,425 @@ use wasm_bindgen::prelude::*;
 // Duplication struct
 
 #[derive(Serialize)] // Serialize the struct
-pub(crate) struct BisectionResult {
-    pub(crate) iteration: u64,
-    pub(crate) xl: f64,
-    pub(crate) xr: f64,
-    pub(crate) xm: f64,
-    pub(crate) error: f64
+pub struct BisectionResult {
+    pub iteration: u64,
+    pub xl: f64,
+    pub xr: f64,
+    pub xm: f64,
+    pub error: f64
 }
 
+// scaled is the endpoint whose f(x) was multiplied by factor after this step (variants only)
 #[derive(Serialize)] // Serialize the struct
-pub(crate) struct FalsePositionResult {
-    pub(crate) iteration: u64,
-    pub(crate) xl: f64,
-    pub(crate) xr: f64,
-    pub(crate) xm: f64,
-    pub(crate) error: f64
+pub struct FalsePositionResult {
+    pub iteration: u64,
+    pub xl: f64,
+    pub xr: f64,
+    pub xm: f64,
+    pub error: f64,
+    pub scaled: Option<Endpoint>,
+    pub factor: Option<f64>
 }
 
+/*
+ *  When the same endpoint is kept twice in a row, its f(x) is scaled by m
+ *  so the next chord moves it (plain regula falsi never does and converges linearly)
+ *      RegulaFalsi     : no scaling
+ *      Illinois        : m = 1/2
+ *      Pegasus         : m = f_old / (f_old + f_new)
+ *      AndersonBjorck  : m = 1 - f_new / f_old, or 1/2 when m <= 0
+ *  f_old is f(x) of the replaced endpoint, f_new is f(xm)
+ */
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub enum FalsePositionVariant {
+    RegulaFalsi,
+    Illinois,
+    Pegasus,
+    AndersonBjorck
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub enum Endpoint {
+    Left,
+    Right
+}
+
+// xl, xr are the bracket before the step, x is the new best estimate
+#[derive(Serialize)] // Serialize the struct
+pub struct BrentResult {
+    pub iteration: u64,
+    pub xl: f64,
+    pub xr: f64,
+    pub x: f64,
+    pub step: BrentStep,
+    pub error: f64
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub enum BrentStep {
+    Bisection,
+    Secant,
+    InverseQuadratic
+}
+
+// xm is midpoint of [xl, xr], x is the exponential-corrected estimate
+#[derive(Serialize)] // Serialize the struct
+pub struct RiddersResult {
+    pub iteration: u64,
+    pub xl: f64,
+    pub xr: f64,
+    pub xm: f64,
+    pub x: f64,
+    pub error: f64
+}
+
+#[derive(Serialize)] // Serialize the struct
+pub struct FixedPointResult {
+    pub iteration: u64,
+    pub x: f64,
+    pub error: f64
+}
+
+/*
+ *  Plain      : x = g(x)
+ *  Aitken     : raw x_n = g(x_(n-1)) as Plain, accelerated from the last three raw iterates
+ *                   x^ = x0 - (x1 - x0)^2 / (x2 - 2 x1 + x0)
+ *  Steffensen : same formula, but the next x0 is the accelerated x^ (restarts from it)
+ */
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub enum FixedPointMode {
+    Plain,
+    Aitken,
+    Steffensen
+}
+
+// x1 = g(x0), x2 = g(x1) are raw iterates, x is the accelerated one
+#[derive(Serialize)] // Serialize the struct
+pub struct AcceleratedResult {
+    pub iteration: u64,
+    pub x0: f64,
+    pub x1: f64,
+    pub x2: f64,
+    pub x: f64,
+    pub error: f64
+}
+
+/*
+ *  slope is |g'(x)| at the start point, iteration converges locally only when |g'| < 1 near the root
+ *  iterations is the Plain trace, accelerated is the Aitken / Steffensen trace
+ */
+#[derive(Serialize)] // Serialize the struct
+pub struct FixedPointMethodResult {
+    pub(crate) mode: FixedPointMode,
+    pub slope: f64,
+    pub warning: Option<String>,
+    pub iterations: Vec<FixedPointResult>,
+    pub accelerated: Vec<AcceleratedResult>,
+    pub outcome: Outcome,
+    pub stopped_by: Option<StopCriterion>
+}
+
+#[derive(Serialize)] // Serialize the struct
+pub struct TaylorResult {
+    pub iteration: u64,
+    pub x: f64,
+    pub sum: f64,
+    pub error: f64
+}
+
+// order is k of the term f^(k)(a) (x - a)^k / k!, error is against the true value
+#[derive(Serialize)] // Serialize the struct
+pub struct TaylorTerm {
+    pub order: usize,
+    pub term: f64,
+    pub sum: f64,
+    pub true_error: f64,
+    pub relative_error: f64
+}
+
+/*
+ *  coefficients are ascending in (x - center): f(x) ~ sum c_k (x - center)^k, c_k = f^(k)(center) / k!
+ *  derivatives are [f, f', ..., f^(order + 1)] as computed symbolically
+ *  remainder_bound is max |f^(n+1)| sampled between center and eval_x * |eval_x - center|^(n+1) / (n+1)!
+ */
+#[derive(Serialize)] // Serialize the struct
+pub struct TaylorSeriesResult {
+    pub(crate) center: f64,
+    pub(crate) eval_x: f64,
+    pub(crate) derivatives: Vec<String>,
+    pub coefficients: Vec<f64>,
+    pub terms: Vec<TaylorTerm>,
+    pub true_value: f64,
+    pub remainder_bound: Option<f64>
+}
+
+// slope is f'(x) used for the step from x of previous iteration
 #[derive(Serialize)] // Serialize the struct
-pub(crate) struct FixedPointResult {
-    pub(crate) iteration: u64,
-    pub(crate) x: f64,
-    pub(crate) error: f64
+pub struct NewtonResult {
+    pub iteration: u64,
+    pub x: f64,
+    pub slope: f64,
+    pub error: f64
 }
 
+/*
+ *  Supplied         : equation_diff is given
+ *  Automatic        : dual numbers over equation_base (autodiff.rs)
+ *  FiniteDifference : central difference with step halving, when some function has no dual rule
+ *  Symbolic         : derivative expressions from symbolic.rs (higher order methods)
+ */
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub enum DerivativeSource {
+    Supplied,
+    Automatic,
+    FiniteDifference,
+    Symbolic
+}
+
+// Same fields as IterativeResult + where f'(x) comes from
+#[derive(Serialize)] // Serialize the struct
+pub struct NewtonRaphsonResult {
+    pub derivative_source: DerivativeSource,
+    pub iterations: Vec<NewtonResult>,
+    pub outcome: Outcome,
+    pub stopped_by: Option<StopCriterion>
+}
+
+// convergence_order is estimated from the last three steps, None before that
+#[derive(Serialize)] // Serialize the struct
+pub struct HouseholderResult {
+    pub iteration: u64,
+    pub x: f64,
+    pub fx: f64,
+    pub error: f64,
+    pub convergence_order: Option<f64>
+}
+
+// order is d of Householder method (1 Newton, 2 Halley, ...)
+#[derive(Serialize)] // Serialize the struct
+pub struct HouseholderMethodResult {
+    pub(crate) order: usize,
+    pub derivative_source: DerivativeSource,
+    pub iterations: Vec<HouseholderResult>,
+    pub outcome: Outcome,
+    pub stopped_by: Option<StopCriterion>
+}
+
+#[derive(Serialize)] // Serialize the struct
+pub struct SecantResult {
+    pub iteration: u64,
+    pub x0: f64,
+    pub x1: f64,
+    pub error: f64
+}
+
+// Same as SecantResult with the third point, x2 is the newest
+#[derive(Serialize)] // Serialize the struct
+pub struct InverseQuadraticResult {
+    pub iteration: u64,
+    pub x0: f64,
+    pub x1: f64,
+    pub x2: f64,
+    pub error: f64
+}
+
+// x0 .. x2 are real parts, x0_im .. x2_im are 0 until the parabola has no real root
+#[derive(Serialize)] // Serialize the struct
+pub struct MullerResult {
+    pub iteration: u64,
+    pub x0: f64,
+    pub x1: f64,
+    pub x2: f64,
+    pub(crate) x0_im: f64,
+    pub(crate) x1_im: f64,
+    pub x2_im: f64,
+    pub error: f64
+}
+
+/*
+ *  Broyden updates after each step dx, df = F(x_new) - F(x_old)
+ *      Good : B = B + (df - B dx) dx^T / (dx^T dx), then solve B dx = -F
+ *      Bad  : H = H + (dx - H df) df^T / (df^T df), then dx = -H F (H approximates J^-1)
+ *  B_0 is a finite difference Jacobian at the initial guess (H_0 its inverse), the only full Jacobian
+ */
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub enum BroydenUpdate {
+    Good,
+    Bad
+}
+
+// jacobian is the approximation after the update: B (Good) or H = B^-1 (Bad)
 #[derive(Serialize)] // Serialize the struct
-pub(crate) struct TaylorResult {
-    pub(crate) iteration: u64,
-    pub(crate) x: f64,
-    pub(crate) sum: f64,
-    pub(crate) error: f64
+pub struct BroydenResult {
+    pub iteration: u64,
+    pub x: Vec<f64>,
+    pub residual: f64,
+    pub jacobian: Matrix,
+    pub error: f64
+}
+
+/*
+ *  SignChange : f(x) changes sign between two samples, refined by Brent
+ *  Exact      : f(x) is exactly 0 at a sample
+ *  Touching   : |f(x)| has a local minimum without sign change (even multiplicity root),
+ *               refined by golden section search on |f(x)|
+ *  xl, xr are the samples around the root
+ */
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub enum RootKind {
+    SignChange,
+    Exact,
+    Touching
 }
 
 #[derive(Serialize)] // Serialize the struct
-pub(crate) struct NewtonResult {
-    pub(crate) iteration: u64,
-    pub(crate) x: f64,
-    pub(crate) error: f64
+pub struct ScannedRoot {
+    pub x: f64,
+    pub fx: f64,
+    pub xl: f64,
+    pub xr: f64,
+    pub kind: RootKind,
+    pub iterations: usize,
+    pub outcome: Outcome
 }
 
+// warning is set when f(a), f(b) have no sign change (bisection / false position on [a, b] would fail)
 #[derive(Serialize)] // Serialize the struct
-pub(crate) struct SecantResult {
-    pub(crate) iteration: u64,
-    pub(crate) x0: f64,
-    pub(crate) x1: f64,
-    pub(crate) error: f64
+pub struct RootScanResult {
+    pub roots: Vec<ScannedRoot>,
+    pub(crate) samples: usize,
+    pub warning: Option<String>
 }
 
+// f^(d) gets long quickly in symbolic form
+const MAX_HOUSEHOLDER_ORDER: usize = 8;
+
+// f^(order + 1) is needed for the remainder, limited by symbolic.rs
+const MAX_TAYLOR_ORDER: usize = 19;
+
+// Points sampled for max |f^(n+1)| of the Lagrange remainder
+const REMAINDER_SAMPLES: usize = 200;
+
+const MAX_SCAN_SAMPLES: usize = 100_000;
+
+// |f(x)| at touching minimum relative to largest sampled |f(x)| to be counted as root
+const TOUCH_TOL: f64 = 1e-10;
+
 
 
 // wasm conversion JsValue
 
 #[wasm_bindgen]
-pub fn bisection(equation: &str, xl: f64, xr: f64) -> JsValue {
-    match bisection_core(equation, xl, xr) {
+pub fn bisection(equation: &str, xl: f64, xr: f64, criteria: JsValue) -> JsValue {
+    let criteria: StoppingCriteria = match StoppingCriteria::from_js(criteria) {
+        Ok(c)  => c,
+        Err(e) => return e.into(),
+    };
+
+    match bisection_core(equation, xl, xr, &criteria) {
+        Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
+        Err(e) => e.into(),
+    }
+}
+
+#[wasm_bindgen]
+pub fn false_position(equation: &str, xl: f64, xr: f64, variant_type: u32, criteria: JsValue) -> JsValue {
+    let criteria: StoppingCriteria = match StoppingCriteria::from_js(criteria) {
+        Ok(c)  => c,
+        Err(e) => return e.into(),
+    };
+
+    let variant: FalsePositionVariant = match variant_type {
+        1 => FalsePositionVariant::RegulaFalsi,
+        2 => FalsePositionVariant::Illinois,
+        3 => FalsePositionVariant::Pegasus,
+        4 => FalsePositionVariant::AndersonBjorck,
+        _ => return CalError::invalid_argument("variant_type", "must be 1 (regula falsi), 2 (Illinois), 3 (Pegasus) or 4 (Anderson-Bjorck)").into(),
+    };
+
+    match false_position_core(equation, xl, xr, variant, &criteria) {
+        Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
+        Err(e) => e.into(),
+    }
+}
+
+#[wasm_bindgen]
+pub fn brent(equation: &str, xl: f64, xr: f64, criteria: JsValue) -> JsValue {
+    let criteria: StoppingCriteria = match StoppingCriteria::from_js(criteria) {
+        Ok(c)  => c,
+        Err(e) => return e.into(),
+    };
+
+    match brent_core(equation, xl, xr, &criteria) {
+        Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
+        Err(e) => e.into(),
+    }
+}
+
+#[wasm_bindgen]
+pub fn ridders(equation: &str, xl: f64, xr: f64, criteria: JsValue) -> JsValue {
+    let criteria: StoppingCriteria = match StoppingCriteria::from_js(criteria) {
+        Ok(c)  => c,
+        Err(e) => return e.into(),
+    };
+
+    match ridders_core(equation, xl, xr, &criteria) {
+        Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
+        Err(e) => e.into(),
+    }
+}
+
+// System version of secant, update_type 1 (good) or 2 (bad)
+#[wasm_bindgen]
+pub fn broyden(equations: Vec<String>, variables: Vec<String>, initial: Vec<f64>, update_type: u32, criteria: JsValue) -> JsValue {
+    let criteria: StoppingCriteria = match StoppingCriteria::from_js(criteria) {
+        Ok(c)  => c,
+        Err(e) => return e.into(),
+    };
+
+    let update: BroydenUpdate = match update_type {
+        1 => BroydenUpdate::Good,
+        2 => BroydenUpdate::Bad,
+        _ => return CalError::invalid_argument("update_type", "must be 1 (good) or 2 (bad)").into(),
+    };
+
+    match broyden_core(&equations, &variables, &initial, update, &criteria) {
         Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
-        Err(e) => JsValue::from_str(&e),
+        Err(e) => e.into(),
     }
 }
 
+// intervals is the number of sub-intervals sampled over [a, b]
 #[wasm_bindgen]
-pub fn false_position(equation: &str, xl: f64, xr: f64) -> JsValue {
-    match false_position_core(equation, xl, xr) {
+pub fn scan_roots(equation: &str, a: f64, b: f64, intervals: usize, criteria: JsValue) -> JsValue {
+    let criteria: StoppingCriteria = match StoppingCriteria::from_js(criteria) {
+        Ok(c)  => c,
+        Err(e) => return e.into(),
+    };
+
+    match scan_roots_core(equation, a, b, intervals, &criteria) {
         Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
-        Err(e) => JsValue::from_str(&e),
+        Err(e) => e.into(),
     }
 }
 
 #[wasm_bindgen]
-pub fn fixed_point(equation: &str, x: f64) -> JsValue {
-    match fixed_point_core(equation, x) {
+pub fn fixed_point(equation: &str, x: f64, mode_type: u32, criteria: JsValue) -> JsValue {
+    let criteria: StoppingCriteria = match StoppingCriteria::from_js(criteria) {
+        Ok(c)  => c,
+        Err(e) => return e.into(),
+    };
+
+    let mode: FixedPointMode = match mode_type {
+        1 => FixedPointMode::Plain,
+        2 => FixedPointMode::Aitken,
+        3 => FixedPointMode::Steffensen,
+        _ => return CalError::invalid_argument("mode_type", "must be 1 (plain), 2 (Aitken) or 3 (Steffensen)").into(),
+    };
+
+    match fixed_point_core(equation, x, mode, &criteria) {
         Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
-        Err(e) => JsValue::from_str(&e),
+        Err(e) => e.into(),
     }
 }
 
@@ -90,52 +446,125 @@ pub fn fixed_point(equation: &str, x: f64) -> JsValue {
 pub fn taylor(equations: Vec<String>, xl: f64, xr: f64) -> JsValue {
     match taylor_core(equations, xl, xr) {
         Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
-        Err(e) => JsValue::from_str(&e),
+        Err(e) => e.into(),
     }
 }
 
+// Derivatives are computed from equation, no need to send them
 #[wasm_bindgen]
-pub fn newton_raphson(equation_base: &str, equation_diff: &str, x: f64) -> JsValue {
-    match newton_raphson_core(equation_base, equation_diff, x) {
+pub fn taylor_series(equation: &str, center: f64, order: usize, eval_x: f64) -> JsValue {
+    match taylor_series_core(equation, center, order, eval_x) {
         Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
-        Err(e) => JsValue::from_str(&e),
+        Err(e) => e.into(),
     }
 }
 
+// equation_diff is optional (undefined | null | ""), then f'(x) is computed automatically
 #[wasm_bindgen]
-pub fn secant(equation: &str, x0: f64, x1: f64) -> JsValue {
-    match secant_core(equation, x0, x1) {
+pub fn newton_raphson(equation_base: &str, equation_diff: Option<String>, x: f64, criteria: JsValue) -> JsValue {
+    let criteria: StoppingCriteria = match StoppingCriteria::from_js(criteria) {
+        Ok(c)  => c,
+        Err(e) => return e.into(),
+    };
+
+    match newton_raphson_core(equation_base, equation_diff.as_deref(), x, &criteria) {
         Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
-        Err(e) => JsValue::from_str(&e)
+        Err(e) => e.into(),
     }
 }
 
+// Missing derivatives (None or empty) are computed symbolically
+#[wasm_bindgen]
+pub fn halley(equation_base: &str, equation_diff: Option<String>, equation_diff2: Option<String>, x: f64, criteria: JsValue) -> JsValue {
+    let criteria: StoppingCriteria = match StoppingCriteria::from_js(criteria) {
+        Ok(c)  => c,
+        Err(e) => return e.into(),
+    };
+
+    match halley_core(equation_base, equation_diff.as_deref(), equation_diff2.as_deref(), x, &criteria) {
+        Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
+        Err(e) => e.into(),
+    }
+}
 
-// Add implement method
+// derivatives are [f', f'', ..., f^(order)], empty for computing symbolically
+#[wasm_bindgen]
+pub fn householder(equation: &str, derivatives: Vec<String>, order: usize, x: f64, criteria: JsValue) -> JsValue {
+    let criteria: StoppingCriteria = match StoppingCriteria::from_js(criteria) {
+        Ok(c)  => c,
+        Err(e) => return e.into(),
+    };
+
+    match householder_core(equation, &derivatives, order, x, &criteria) {
+        Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
+        Err(e) => e.into(),
+    }
+}
+
+#[wasm_bindgen]
+pub fn secant(equation: &str, x0: f64, x1: f64, criteria: JsValue) -> JsValue {
+    let criteria: StoppingCriteria = match StoppingCriteria::from_js(criteria) {
+        Ok(c)  => c,
+        Err(e) => return e.into(),
+    };
+
+    match secant_core(equation, x0, x1, &criteria) {
+        Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
+        Err(e) => e.into(),
+    }
+}
+
+#[wasm_bindgen]
+pub fn inverse_quadratic(equation: &str, x0: f64, x1: f64, x2: f64, criteria: JsValue) -> JsValue {
+    let criteria: StoppingCriteria = match StoppingCriteria::from_js(criteria) {
+        Ok(c)  => c,
+        Err(e) => return e.into(),
+    };
+
+    match inverse_quadratic_core(equation, x0, x1, x2, &criteria) {
+        Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
+        Err(e) => e.into(),
+    }
+}
 
-pub(crate) fn bisection_core(equation: &str, mut xl: f64, mut xr: f64) -> Result<Vec<BisectionResult>, String> {
-    let expr: Expr = match equation.parse() {
-        Ok(e)  => e,
-        Err(_) => return Err("Invalid function".to_string()),
+#[wasm_bindgen]
+pub fn muller(equation: &str, x0: f64, x1: f64, x2: f64, criteria: JsValue) -> JsValue {
+    let criteria: StoppingCriteria = match StoppingCriteria::from_js(criteria) {
+        Ok(c)  => c,
+        Err(e) => return e.into(),
     };
 
+    match muller_core(equation, x0, x1, x2, &criteria) {
+        Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
+        Err(e) => e.into(),
+    }
+}
+
+
+// Add implement method
+
+pub fn bisection_core(equation: &str, mut xl: f64, mut xr: f64, criteria: &StoppingCriteria) -> Result<IterativeResult<BisectionResult>, CalError> {
+    let expr: Expr = parse_expr(equation)?;
+
     if xl >= xr {
-        return Err(format!("Invalid interval: xl: {} must be less than xr: {}", xl, xr));
+        return Err(CalError::InvalidInterval { xl, xr });
     }
     
 
-    let mut result  : Vec<BisectionResult>  = Vec::new();
-    let mut xm      : f64                   = bisection_calc(xl, xr);
-    let mut err     : f64                   = 100.0;
+    criteria.validate()?;
+
+    let mut result  : IterativeResult<BisectionResult> = IterativeResult::new();
+    let mut xm      : f64                              = bisection_calc(xl, xr);
+    let mut err     : f64                              = 100.0;
     let mut xm_prev : f64;
 
-    for iter in 0..100 {
+    for iter in 0..criteria.max_iter {
 
         // let fx_left : f64  = function_calc(expr, xl); <-- unused function
-        let fx_right: f64  = utils::evaluate_expr(&expr, xr); 
-        let fx_mid  : f64  = utils::evaluate_expr(&expr, xm);
+        let fx_right: f64  = utils::evaluate_expr(&expr, xr)?; 
+        let fx_mid  : f64  = utils::evaluate_expr(&expr, xm)?;
 
-        result.push(BisectionResult {
+        result.iterations.push(BisectionResult {
             iteration: iter + 1,
             xl,
             xr,
@@ -154,7 +583,14 @@ pub(crate) fn bisection_core(equation: &str, mut xl: f64, mut xr: f64) -> Result
         xm          = bisection_calc(xl, xr);
         err         = utils::error_calc(xm, xm_prev);
         
-        if err < 1e-6 {
+        if let Some(criterion) = criteria.check(xm, xm_prev, fx_mid) {
+            result.converge(criterion);
+            break;
+        }
+
+        // NaN f(x) or interval can't be halved anymore
+        if let Some(outcome) = Outcome::inspect(xm, xm_prev, fx_mid) {
+            result.fail(outcome);
             break;
         }
     }
@@ -162,50 +598,90 @@ pub(crate) fn bisection_core(equation: &str, mut xl: f64, mut xr: f64) -> Result
     Ok(result)
 }
 
-pub(crate) fn false_position_core(equation: &str, mut xl: f64, mut xr: f64) -> Result<Vec<FalsePositionResult>, String> { 
-    let expr: Expr = match equation.parse() {
-        Ok(e)  => e,
-        Err(_) => return Err("Invalid function".to_string()),
-    };
+pub fn false_position_core(equation: &str, mut xl: f64, mut xr: f64, variant: FalsePositionVariant, criteria: &StoppingCriteria) -> Result<IterativeResult<FalsePositionResult>, CalError> { 
+    let expr: Expr = parse_expr(equation)?;
 
     if xl >= xr {
-        return Err(format!("Invalid interval: xl: {} must be less than xr: {}", xl, xr));
+        return Err(CalError::InvalidInterval { xl, xr });
     }
 
-    let mut result  : Vec<FalsePositionResult>  = Vec::new();
-    let mut err     : f64                       = 100.0;
-    let mut xm_prev : f64                       = 0.0;
-    let mut xm      : f64;
+    criteria.validate()?;
 
-    for iter in 0..100 {
+    let mut result   : IterativeResult<FalsePositionResult> = IterativeResult::new();
+    let mut err      : f64                                  = 100.0;
+    let mut xm_prev  : f64                                  = 0.0;
+    let mut xm       : f64;
 
-        let fx_left : f64  = utils::evaluate_expr(&expr, xl);
-        let fx_right: f64  = utils::evaluate_expr(&expr, xr); 
+    // kept between iterations since the variants scale them
+    let mut fx_left  : f64              = utils::evaluate_expr(&expr, xl)?;
+    let mut fx_right : f64              = utils::evaluate_expr(&expr, xr)?;
+    let mut replaced : Option<Endpoint> = None;
+
+    for iter in 0..criteria.max_iter {
+
+        // flat chord can't cross the axis
+        if fx_right == fx_left {
+            result.fail(Outcome::Stagnated);
+            break;
+        }
 
         xm = false_position_calc(xl, xr, fx_left, fx_right);
-        let fx_mid  : f64  = utils::evaluate_expr(&expr, xm);
+        let fx_mid  : f64  = utils::evaluate_expr(&expr, xm)?;
 
         if iter > 0 {
             err = utils::error_calc(xm, xm_prev);
         }
 
-        result.push(FalsePositionResult {
+        let (bracket_l, bracket_r) = (xl, xr);
+        let (side, f_old): (Endpoint, f64) = if fx_mid * fx_right < 0.0 {
+            let f_old: f64 = fx_left;
+            xl      = xm;
+            fx_left = fx_mid;
+            (Endpoint::Left, f_old)
+        }
+        else {
+            let f_old: f64 = fx_right;
+            xr       = xm;
+            fx_right = fx_mid;
+            (Endpoint::Right, f_old)
+        };
+
+        // same side replaced twice, the opposite endpoint is stuck
+        let factor: Option<f64> = if replaced == Some(side) {
+            false_position_factor(variant, f_old, fx_mid)
+        } else {
+            None
+        };
+        let scaled: Option<Endpoint> = factor.map(|m| match side {
+            Endpoint::Left  => { fx_right *= m; Endpoint::Right },
+            Endpoint::Right => { fx_left  *= m; Endpoint::Left },
+        });
+        replaced = Some(side);
+
+        result.iterations.push(FalsePositionResult {
             iteration: iter + 1,
-            xl,
-            xr,
+            xl: bracket_l,
+            xr: bracket_r,
             xm,
-            error: err
+            error: err,
+            scaled,
+            factor
         });
-
-        if fx_mid * fx_right < 0.0 {
-            xl = xm;
-        }
-        else {
-            xr = xm;
-        }
         
+        // first point has no previous step to compare
+        let (criterion, outcome) = if iter > 0 {
+            (criteria.check(xm, xm_prev, fx_mid), Outcome::inspect(xm, xm_prev, fx_mid))
+        } else {
+            (criteria.residual_met(fx_mid), Outcome::inspect(xm, f64::NAN, fx_mid))
+        };
+
         xm_prev = xm;
-        if err < 1e-6 {
+        if let Some(criterion) = criterion {
+            result.converge(criterion);
+            break;
+        }
+        if let Some(outcome) = outcome {
+            result.fail(outcome);
             break;
         }
     }
@@ -213,134 +689,880 @@ pub(crate) fn false_position_core(equation: &str, mut xl: f64, mut xr: f64) -> R
     Ok(result)
 }
 
-pub(crate) fn fixed_point_core(equation: &str, mut x: f64) -> Result<Vec<FixedPointResult>, String> {
-    let expr: Expr = match equation.parse() {
-        Ok(e)  => e,
-        Err(_) => return Err("Invalid function".to_string()),
-    };
-
-    let mut result  : Vec<FixedPointResult>  = Vec::new();
-    let mut x_prev  : f64                    = 0.0;
-    let mut err     : f64                    = 100.0;
+/*
+ *  Brent (zbrent): inverse quadratic interpolation / secant when it stays inside the bracket
+ *  and shrinks fast enough, otherwise bisection, so it is never slower than bisection by much
+ *  b is the best estimate, a the previous b, c the opposite end of the bracket
+ */
+pub fn brent_core(equation: &str, xl: f64, xr: f64, criteria: &StoppingCriteria) -> Result<IterativeResult<BrentResult>, CalError> {
+    let expr: Expr = parse_expr(equation)?;
 
-    for iter in 0..100 {
-        x = utils::evaluate_expr(&expr, x);
+    if xl >= xr {
+        return Err(CalError::InvalidInterval { xl, xr });
+    }
 
-        result.push(FixedPointResult {
-            iteration: iter+1,
-            x,
-            error: err
-        });
-        
-        err    = utils::error_calc(x, x_prev);
-        x_prev = x;
+    criteria.validate()?;
 
-        if err < 1e-6 {
-            break;
-        }
-    }
-    
-    Ok(result)
+    brent_calc(&|x: f64| utils::evaluate_expr(&expr, x), xl, xr, criteria)
 }
 
-pub(crate) fn taylor_core(equations: Vec<String>, xl: f64, xr: f64) -> Result<Vec<TaylorResult>, String> {
-    let len          : usize              = equations.len();
-
-    let mut result   : Vec<TaylorResult>  = Vec::new();
-    let mut sum      : f64                = 0.0;
-    let mut sum_prev : f64                = 0.0;
+/*
+ *  Ridders: with midpoint xm, fit f(x) * e^(kx) to a line through xl, xm, xr
+ *      x = xm + (xm - xl) * sign(f(xl) - f(xr)) * f(xm) / sqrt(f(xm)^2 - f(xl) f(xr))
+ *  New x always stays in the bracket, the bracket is re-chosen from {xl, xm, x, xr}
+ */
+pub fn ridders_core(equation: &str, mut xl: f64, mut xr: f64, criteria: &StoppingCriteria) -> Result<IterativeResult<RiddersResult>, CalError> {
+    let expr: Expr = parse_expr(equation)?;
 
-    for iter in 0..len {
-        let expr: Expr = match equations[iter].parse() {
-            Ok(e)  => e,
-            Err(_) => return Err("Invalid function".to_string()),
-        };
+    if xl >= xr {
+        return Err(CalError::InvalidInterval { xl, xr });
+    }
 
-        let fac     : usize     = utils::factorial(iter);
-        let  x      : f64       = utils::evaluate_expr(&expr, xl);
-        let pow     : f64       = if iter != 0 { (xr - xl).powf(iter as f64) } else { 1.0 };
+    criteria.validate()?;
 
-        let term    : f64       =  x * pow / fac as f64;    
-        sum    += term;
-        
-        let err     : f64       = utils::error_calc(sum, sum_prev);
+    let mut result : IterativeResult<RiddersResult> = IterativeResult::new();
+    let mut fx_left  : f64 = utils::evaluate_expr(&expr, xl)?;
+    let mut fx_right : f64 = utils::evaluate_expr(&expr, xr)?;
 
-        sum_prev =  sum;
-        
-        result.push(TaylorResult {
-            iteration: iter as u64 +1,
-            x: term,
-            sum,
-            error: err
-        });
+    if fx_left * fx_right > 0.0 {
+        return Err(CalError::NoSignChange { xl, xr });
     }
 
-    Ok(result)
-}
+    let mut x_prev : f64 = f64::NAN;
+    let mut err    : f64 = 100.0;
 
-pub(crate) fn newton_raphson_core(equation_base: &str, equation_diff: &str, mut x: f64) -> Result<Vec<NewtonResult>, String> {
-    let expr_base: Expr = match equation_base.parse() {
-        Ok(e)  => e,
-        Err(_) => return Err("Invalid function".to_string()),
-        };
-    let expr_diff: Expr = match equation_diff.parse() {
-        Ok(e)  => e,
-        Err(_) => return Err("Invalid function".to_string()),
-        };
+    for iter in 0..criteria.max_iter {
+        let xm     : f64 = bisection_calc(xl, xr);
+        let fx_mid : f64 = utils::evaluate_expr(&expr, xm)?;
+        let root   : f64 = (fx_mid * fx_mid - fx_left * fx_right).sqrt();
 
-    let mut result  : Vec<NewtonResult>  = Vec::new();
+        // f(xl) = f(xm) = 0 or f(xr) = 0, nothing to correct
+        if root == 0.0 {
+            result.fail(Outcome::Stagnated);
+            break;
+        }
 
-    for iter in 0..100 {
-        let x_prev  : f64 = x;
-        let fx_base : f64 = utils::evaluate_expr(&expr_base, x);
-        let fx_diff : f64 = utils::evaluate_expr(&expr_diff, x);
+        let x  : f64 = ridders_calc(xl, xm, fx_left, fx_right, fx_mid, root);
+        let fx : f64 = utils::evaluate_expr(&expr, x)?;
 
-        x = newton_calc(x, fx_base, fx_diff);
-        let err     : f64 = utils::error_calc(x, x_prev);
+        if iter > 0 {
+            err = utils::error_calc(x, x_prev);
+        }
 
-        result.push(NewtonResult {
-            iteration: iter+1,
+        result.iterations.push(RiddersResult {
+            iteration: iter + 1,
+            xl,
+            xr,
+            xm,
             x,
             error: err
         });
 
-        if err < 1e-6 {
+        // first estimate has no previous step to compare, exact root stops regardless of tolerance
+        let (criterion, outcome) = if fx == 0.0 {
+            (Some(StopCriterion::Residual), None)
+        }
+        else if iter > 0 {
+            (criteria.check(x, x_prev, fx), Outcome::inspect(x, x_prev, fx))
+        }
+        else {
+            (criteria.residual_met(fx), Outcome::inspect(x, f64::NAN, fx))
+        };
+
+        x_prev = x;
+        if let Some(criterion) = criterion {
+            result.converge(criterion);
+            break;
+        }
+        if let Some(outcome) = outcome {
+            result.fail(outcome);
             break;
         }
+
+        // smallest bracket with sign change
+        if fx_mid * fx < 0.0 {
+            xl       = xm.min(x);
+            xr       = xm.max(x);
+            fx_left  = if xm < x { fx_mid } else { fx };
+            fx_right = if xm < x { fx } else { fx_mid };
+        }
+        else if fx_left * fx < 0.0 {
+            xr       = x;
+            fx_right = fx;
+        }
+        else {
+            xl      = x;
+            fx_left = fx;
+        }
     }
-    
+
     Ok(result)
 }
 
-pub(crate) fn secant_core(equation: &str, mut x0: f64, mut x1: f64) -> Result<Vec<SecantResult>, String> {
-    let expr: Expr = match equation.parse() {
-        Ok(e)  => e,
-        Err(_) => return Err("Invalid function".to_string()),
-    };
+/*
+ *  Sample f(x) at intervals + 1 evenly spaced points over [a, b], then
+ *    - sample with f(x) = 0 is a root as is
+ *    - sign change between neighbours is refined by brent_core
+ *    - local minimum of |f(x)| with the same sign on both sides is refined by golden section,
+ *      kept when the minimum is below TOUCH_TOL (relative to sampled values)
+ *  Undefined samples (ln of negative, poles) split the scan, sign change across a pole is dropped
+ *  Roots closer than the sample spacing can be missed, increase intervals for that
+ */
+pub fn scan_roots_core(equation: &str, a: f64, b: f64, intervals: usize, criteria: &StoppingCriteria) -> Result<RootScanResult, CalError> {
+    let expr: Expr = parse_expr(equation)?;
+
+    if a >= b {
+        return Err(CalError::InvalidInterval { xl: a, xr: b });
+    }
+    if !(2..=MAX_SCAN_SAMPLES).contains(&intervals) {
+        return Err(CalError::invalid_argument("intervals", &format!("must be between 2 and {}", MAX_SCAN_SAMPLES)));
+    }
 
-    let mut result  : Vec<SecantResult>  = Vec::new();
+    criteria.validate()?;
 
-    for iter in 0..100 {
-        let fx_x0: f64 = utils::evaluate_expr(&expr, x0);
-        let fx_x1: f64 = utils::evaluate_expr(&expr, x1);
+    // unknown names are rejected here, undefined points become NaN while sampling
+    let compiled = utils::compile_expr(expr, &["x"])?;
+    let f        = |values: &[f64]| compiled(values).unwrap_or(f64::NAN);
 
-        let x2   : f64 = secant_calc(x0, x1, fx_x0, fx_x1);
-        let err  : f64 = utils::error_calc(x2, x1);
+    // same rule as evaluate_expr for the refinement
+    let refine_f = |x: f64| -> Result<f64, CalError> {
+        let fx: f64 = compiled(&[x])?;
+        if !fx.is_finite() {
+            return Err(CalError::NonFiniteValue { x, value: fx });
+        }
+        Ok(fx)
+    };
 
-        x0 = x1;
-        x1 = x2;
+    let h      : f64              = (b - a) / intervals as f64;
+    let xs     : Vec<f64>         = (0..=intervals).map(|i| if i == intervals { b } else { a + h * i as f64 }).collect();
+    let fxs    : Vec<Option<f64>> = xs.iter().map(|&x| Some(f(&[x])).filter(|fx| fx.is_finite())).collect();
+    let scale  : f64              = fxs.iter().flatten().fold(1.0_f64, |acc, fx| acc.max(fx.abs()));
 
-        result.push(SecantResult {
-            iteration: iter+1,
-            x0,
-            x1,
-            error: err
-        });
+    let mut roots: Vec<ScannedRoot> = Vec::new();
 
-        if err < 1e-6 {
-            break;
+    for i in 0..=intervals {
+        let fx: f64 = match fxs[i] {
+            Some(fx) => fx,
+            None     => continue,
+        };
+
+        if fx == 0.0 {
+            roots.push(ScannedRoot { x: xs[i], fx, xl: xs[i], xr: xs[i], kind: RootKind::Exact, iterations: 0, outcome: Outcome::Converged });
+            continue;
         }
-    }
+
+        // sign change to the right neighbour
+        if let Some(Some(fx_right)) = fxs.get(i + 1) {
+            if fx * fx_right < 0.0 {
+                let refined = match brent_calc(&refine_f, xs[i], xs[i + 1], criteria) {
+                    Ok(refined) => refined,
+                    // undefined inside the bracket, reported without ending the scan
+                    Err(_) => {
+                        roots.push(ScannedRoot {
+                            x: (xs[i] + xs[i + 1]) / 2.0,
+                            fx: f64::NAN,
+                            xl: xs[i],
+                            xr: xs[i + 1],
+                            kind: RootKind::SignChange,
+                            iterations: 0,
+                            outcome: Outcome::NonFinite
+                        });
+                        continue;
+                    },
+                };
+                if let Some(last) = refined.iterations.last() {
+                    let fx_root: f64 = f(&[last.x]);
+
+                    // pole: |f| grows toward the "root" instead of vanishing
+                    if fx_root.abs() <= fx.abs().max(fx_right.abs()) {
+                        roots.push(ScannedRoot {
+                            x: last.x,
+                            fx: fx_root,
+                            xl: xs[i],
+                            xr: xs[i + 1],
+                            kind: RootKind::SignChange,
+                            iterations: refined.iterations.len(),
+                            outcome: refined.outcome
+                        });
+                    }
+                }
+                continue;
+            }
+        }
+
+        // touching minimum of |f| between both neighbours with the same sign
+        if i == 0 || i == intervals {
+            continue;
+        }
+        if let (Some(fx_left), Some(fx_right)) = (fxs[i - 1], fxs[i + 1]) {
+            let same_sign : bool = fx_left * fx > 0.0 && fx * fx_right > 0.0;
+            let minimum   : bool = fx.abs() <= fx_left.abs() && fx.abs() < fx_right.abs();
+
+            if same_sign && minimum {
+                let (x, fx_min, iterations) = golden_section_calc(&f, xs[i - 1], xs[i + 1], criteria.max_iter);
+
+                if fx_min.abs() <= TOUCH_TOL * scale {
+                    roots.push(ScannedRoot {
+                        x,
+                        fx: fx_min,
+                        xl: xs[i - 1],
+                        xr: xs[i + 1],
+                        kind: RootKind::Touching,
+                        iterations,
+                        outcome: Outcome::Converged
+                    });
+                }
+            }
+        }
+    }
+
+    let warning: Option<String> = match (fxs[0], fxs[intervals]) {
+        (Some(fa), Some(fb)) if fa * fb > 0.0 => Some(format!("f(x) has the same sign at a: {} and b: {}, a bracketing method can't be started on [a, b]", a, b)),
+        (None, _) | (_, None) => Some("f(x) is undefined at a or b, a bracketing method can't be started on [a, b]".to_string()),
+        _ => None,
+    };
+
+    Ok(RootScanResult {
+        roots,
+        samples: intervals + 1,
+        warning
+    })
+}
+
+// equation is g(x) of x = g(x)
+pub fn fixed_point_core(equation: &str, x: f64, mode: FixedPointMode, criteria: &StoppingCriteria) -> Result<FixedPointMethodResult, CalError> {
+    let expr: Expr = parse_expr(equation)?;
+
+    criteria.validate()?;
+
+    let slope   : f64            = slope_at(&expr, x)?.abs();
+    let warning : Option<String> = if slope >= 1.0 {
+        Some(format!("|g'(x)| = {} at x = {} is not less than 1, iteration may diverge", slope, x))
+    }
+    else {
+        None
+    };
+
+    let (iterations, accelerated, outcome, stopped_by) = match mode {
+        FixedPointMode::Plain => {
+            let result: IterativeResult<FixedPointResult> = fixed_point_plain(&expr, x, criteria)?;
+            (result.iterations, Vec::new(), result.outcome, result.stopped_by)
+        },
+        _ => {
+            let result: IterativeResult<AcceleratedResult> = fixed_point_accelerated(&expr, x, mode, criteria)?;
+            (Vec::new(), result.iterations, result.outcome, result.stopped_by)
+        },
+    };
+
+    Ok(FixedPointMethodResult {
+        mode,
+        slope,
+        warning,
+        iterations,
+        accelerated,
+        outcome,
+        stopped_by
+    })
+}
+
+fn fixed_point_plain(expr: &Expr, mut x: f64, criteria: &StoppingCriteria) -> Result<IterativeResult<FixedPointResult>, CalError> {
+    let mut result : IterativeResult<FixedPointResult> = IterativeResult::new();
+    let mut x_prev : f64                               = 0.0;
+    let mut err    : f64                               = 100.0;
+
+    for iter in 0..criteria.max_iter {
+        x = utils::evaluate_expr(expr, x)?;
+
+        result.iterations.push(FixedPointResult {
+            iteration: iter+1,
+            x,
+            error: err
+        });
+        
+        err    = utils::error_calc(x, x_prev);
+
+        // residual of x = g(x) is the step itself
+        let criterion = criteria.check(x, x_prev, x - x_prev);
+        let outcome   = Outcome::inspect(x, x_prev, x - x_prev);
+        x_prev = x;
+
+        if let Some(criterion) = criterion {
+            result.converge(criterion);
+            break;
+        }
+        if let Some(outcome) = outcome {
+            result.fail(outcome);
+            break;
+        }
+    }
+    
+    Ok(result)
+}
+
+fn fixed_point_accelerated(expr: &Expr, x: f64, mode: FixedPointMode, criteria: &StoppingCriteria) -> Result<IterativeResult<AcceleratedResult>, CalError> {
+    let mut result : IterativeResult<AcceleratedResult> = IterativeResult::new();
+    let mut x0     : f64                                = x;
+    let mut x1     : f64                                = utils::evaluate_expr(expr, x0)?;
+    let mut x_prev : f64                                = f64::NAN;
+
+    for iter in 0..criteria.max_iter {
+        let x2 : f64 = utils::evaluate_expr(expr, x1)?;
+
+        // x0, x1, x2 on a line (or already fixed), nothing to extrapolate
+        let x: f64 = match aitken_calc(x0, x1, x2) {
+            Some(x) => x,
+            None if x2 == x1 => {
+                result.converge(StopCriterion::Residual);
+                break;
+            },
+            None => {
+                result.fail(Outcome::Stagnated);
+                break;
+            },
+        };
+
+        result.iterations.push(AcceleratedResult {
+            iteration: iter + 1,
+            x0,
+            x1,
+            x2,
+            x,
+            error: if iter > 0 { utils::error_calc(x, x_prev) } else { 100.0 }
+        });
+
+        // raw step x2 - x1 is the residual as in Plain
+        let (criterion, outcome) = if iter > 0 {
+            (criteria.check(x, x_prev, x2 - x1), Outcome::inspect(x, x_prev, x2 - x1))
+        }
+        else {
+            (criteria.residual_met(x2 - x1), Outcome::inspect(x, f64::NAN, x2 - x1))
+        };
+
+        x_prev = x;
+        if let Some(criterion) = criterion {
+            result.converge(criterion);
+            break;
+        }
+        if let Some(outcome) = outcome {
+            result.fail(outcome);
+            break;
+        }
+
+        match mode {
+            FixedPointMode::Steffensen => {
+                x0 = x;
+                x1 = utils::evaluate_expr(expr, x0)?;
+            },
+            _ => {
+                x0 = x1;
+                x1 = x2;
+            },
+        }
+    }
+
+    Ok(result)
+}
+
+pub fn taylor_core(equations: Vec<String>, xl: f64, xr: f64) -> Result<Vec<TaylorResult>, CalError> {
+    let len          : usize              = equations.len();
+
+    let mut result   : Vec<TaylorResult>  = Vec::new();
+    let mut sum      : f64                = 0.0;
+    let mut sum_prev : f64                = 0.0;
+
+    for iter in 0..len {
+        let expr: Expr = parse_expr(&equations[iter])?;
+
+        let fac     : usize     = utils::factorial(iter);
+        let  x      : f64       = utils::evaluate_expr(&expr, xl)?;
+        let pow     : f64       = if iter != 0 { (xr - xl).powf(iter as f64) } else { 1.0 };
+
+        let term    : f64       =  x * pow / fac as f64;    
+        sum    += term;
+        
+        let err     : f64       = utils::error_calc(sum, sum_prev);
+
+        sum_prev =  sum;
+        
+        result.push(TaylorResult {
+            iteration: iter as u64 +1,
+            x: term,
+            sum,
+            error: err
+        });
+    }
+
+    Ok(result)
+}
+
+pub fn taylor_series_core(equation: &str, center: f64, order: usize, eval_x: f64) -> Result<TaylorSeriesResult, CalError> {
+    if order > MAX_TAYLOR_ORDER {
+        return Err(CalError::invalid_argument("order", &format!("must be at most {}", MAX_TAYLOR_ORDER)));
+    }
+
+    let derivatives : Vec<String> = symbolic_derivatives_core(equation, "x", order + 1)?;
+    let exprs       : Vec<Expr>   = derivatives.iter().map(|e| parse_expr(e)).collect::<Result<_, _>>()?;
+
+    let true_value  : f64 = utils::evaluate_expr(&exprs[0], eval_x)?;
+    let h           : f64 = eval_x - center;
+
+    let mut coefficients : Vec<f64>        = Vec::new();
+    let mut terms        : Vec<TaylorTerm> = Vec::new();
+    let mut sum          : f64             = 0.0;
+    let mut factorial    : f64             = 1.0;
+
+    for (k, expr) in exprs[..=order].iter().enumerate() {
+        if k > 0 {
+            factorial *= k as f64;
+        }
+
+        let coefficient : f64 = utils::evaluate_expr(expr, center)? / factorial;
+        let term        : f64 = coefficient * h.powi(k as i32);
+        sum += term;
+
+        coefficients.push(coefficient);
+        terms.push(TaylorTerm {
+            order: k,
+            term,
+            sum,
+            true_error: true_value - sum,
+            relative_error: utils::error_calc(true_value, sum)
+        });
+    }
+
+    // sampled, so it is an estimate of the bound (exact at the endpoints only)
+    let next      : &Expr = &exprs[order + 1];
+    let max_deriv : f64   = (0..=REMAINDER_SAMPLES)
+        .map(|i| center + h * i as f64 / REMAINDER_SAMPLES as f64)
+        .filter_map(|xi| utils::evaluate_expr(next, xi).ok())
+        .fold(f64::NAN, |acc, v| acc.max(v.abs()));
+    let remainder_bound: Option<f64> = if max_deriv.is_nan() {
+        None
+    }
+    else {
+        Some(max_deriv * h.abs().powi(order as i32 + 1) / (factorial * (order + 1) as f64))
+    };
+
+    Ok(TaylorSeriesResult {
+        center,
+        eval_x,
+        derivatives,
+        coefficients,
+        terms,
+        true_value,
+        remainder_bound
+    })
+}
+
+pub fn newton_raphson_core(equation_base: &str, equation_diff: Option<&str>, mut x: f64, criteria: &StoppingCriteria) -> Result<NewtonRaphsonResult, CalError> {
+    let expr_base: Expr = parse_expr(equation_base)?;
+    let expr_diff: Option<Expr> = match equation_diff {
+        Some(equation) if !equation.trim().is_empty() => Some(parse_expr(equation)?),
+        _ => None,
+    };
+
+    criteria.validate()?;
+
+    let source: DerivativeSource = match expr_diff {
+        Some(_)                                    => DerivativeSource::Supplied,
+        None if autodiff::is_supported(&expr_base) => DerivativeSource::Automatic,
+        None                                       => DerivativeSource::FiniteDifference,
+    };
+
+    let mut result : IterativeResult<NewtonResult> = IterativeResult::new();
+
+    for iter in 0..criteria.max_iter {
+        let x_prev : f64 = x;
+
+        let (fx_base, fx_diff) = match (&expr_diff, source) {
+            (Some(expr_diff), _) => (utils::evaluate_expr(&expr_base, x)?, utils::evaluate_expr(expr_diff, x)?),
+            (None, DerivativeSource::Automatic) => {
+                let dual = autodiff::evaluate_dual(&expr_base, x)?;
+                (dual.value, dual.slope)
+            },
+            (None, _) => (utils::evaluate_expr(&expr_base, x)?, central_slope(&expr_base, x)?),
+        };
+
+        // tangent line is parallel to x-axis (exact 0, any scale of f(x) is kept)
+        if fx_diff == 0.0 {
+            result.fail(Outcome::DerivativeZero);
+            break;
+        }
+        if !fx_diff.is_finite() {
+            result.fail(Outcome::NonFinite);
+            break;
+        }
+
+        x = newton_calc(x, fx_base, fx_diff);
+        let err     : f64 = utils::error_calc(x, x_prev);
+
+        result.iterations.push(NewtonResult {
+            iteration: iter+1,
+            x,
+            slope: fx_diff,
+            error: err
+        });
+
+        if let Some(criterion) = criteria.check(x, x_prev, fx_base) {
+            result.converge(criterion);
+            break;
+        }
+        if let Some(outcome) = Outcome::inspect(x, x_prev, fx_base) {
+            result.fail(outcome);
+            break;
+        }
+    }
+    
+    Ok(NewtonRaphsonResult {
+        derivative_source: source,
+        iterations: result.iterations,
+        outcome: result.outcome,
+        stopped_by: result.stopped_by
+    })
+}
+
+pub fn halley_core(equation_base: &str, equation_diff: Option<&str>, equation_diff2: Option<&str>, x: f64, criteria: &StoppingCriteria) -> Result<HouseholderMethodResult, CalError> {
+    let given = |equation: Option<&str>| equation.filter(|e| !e.trim().is_empty()).map(|e| e.to_string());
+
+    let derivatives: Vec<String> = match (given(equation_diff), given(equation_diff2)) {
+        (Some(diff), Some(diff2)) => vec![diff, diff2],
+        (None, None)              => Vec::new(),
+        _ => return Err(CalError::invalid_argument("equation_diff2", "f'(x) and f''(x) must be given together or both left empty")),
+    };
+
+    householder_core(equation_base, &derivatives, 2, x, criteria)
+}
+
+/*
+ *  Householder method of order d, convergence of order d + 1 near a simple root
+ *      x_(n+1) = x_n + d * (1/f)^(d-1)(x_n) / (1/f)^(d)(x_n)
+ *  d = 1 is Newton-Raphson, d = 2 is Halley
+ *  Derivatives of 1/f are built from f, f', ..., f^(d) (householder_calc)
+ */
+pub fn householder_core(equation: &str, derivatives: &[String], order: usize, mut x: f64, criteria: &StoppingCriteria) -> Result<HouseholderMethodResult, CalError> {
+    if order == 0 || order > MAX_HOUSEHOLDER_ORDER {
+        return Err(CalError::invalid_argument("order", &format!("must be between 1 and {}", MAX_HOUSEHOLDER_ORDER)));
+    }
+    if !derivatives.is_empty() && derivatives.len() != order {
+        return Err(CalError::DimensionMismatch { expected: order, got: derivatives.len() });
+    }
+
+    // [f, f', ..., f^(d)]
+    let (source, equations): (DerivativeSource, Vec<String>) = if derivatives.is_empty() {
+        (DerivativeSource::Symbolic, symbolic_derivatives_core(equation, "x", order)?)
+    }
+    else {
+        let mut equations: Vec<String> = vec![equation.to_string()];
+        equations.extend(derivatives.iter().cloned());
+        (DerivativeSource::Supplied, equations)
+    };
+
+    let exprs: Vec<Expr> = equations.iter().map(|e| parse_expr(e)).collect::<Result<_, _>>()?;
+
+    criteria.validate()?;
+
+    let mut result : IterativeResult<HouseholderResult> = IterativeResult::new();
+    let mut steps  : Vec<f64>                           = Vec::new();
+
+    for iter in 0..criteria.max_iter {
+        let x_prev : f64      = x;
+        let values : Vec<f64> = exprs.iter().map(|expr| utils::evaluate_expr(expr, x)).collect::<Result<_, _>>()?;
+
+        // exactly on the root, 1/f is undefined
+        if values[0] == 0.0 {
+            result.converge(StopCriterion::Residual);
+            break;
+        }
+
+        x = match householder_calc(x, &values) {
+            Some(x) => x,
+            None    => {
+                result.fail(Outcome::DerivativeZero);
+                break;
+            },
+        };
+        steps.push((x - x_prev).abs());
+
+        result.iterations.push(HouseholderResult {
+            iteration: iter+1,
+            x,
+            fx: values[0],
+            error: utils::error_calc(x, x_prev),
+            convergence_order: estimate_order(&steps)
+        });
+
+        if let Some(criterion) = criteria.check(x, x_prev, values[0]) {
+            result.converge(criterion);
+            break;
+        }
+        if let Some(outcome) = Outcome::inspect(x, x_prev, values[0]) {
+            result.fail(outcome);
+            break;
+        }
+    }
+
+    Ok(HouseholderMethodResult {
+        order,
+        derivative_source: source,
+        iterations: result.iterations,
+        outcome: result.outcome,
+        stopped_by: result.stopped_by
+    })
+}
+
+pub fn secant_core(equation: &str, mut x0: f64, mut x1: f64, criteria: &StoppingCriteria) -> Result<IterativeResult<SecantResult>, CalError> {
+    let expr: Expr = parse_expr(equation)?;
+
+    criteria.validate()?;
+
+    let mut result : IterativeResult<SecantResult> = IterativeResult::new();
+
+    for iter in 0..criteria.max_iter {
+        let fx_x0: f64 = utils::evaluate_expr(&expr, x0)?;
+        let fx_x1: f64 = utils::evaluate_expr(&expr, x1)?;
+
+        // secant line is parallel to x-axis
+        if fx_x1 == fx_x0 {
+            result.fail(Outcome::Stagnated);
+            break;
+        }
+
+        let x2   : f64 = secant_calc(x0, x1, fx_x0, fx_x1);
+        let err  : f64 = utils::error_calc(x2, x1);
+
+        x0 = x1;
+        x1 = x2;
+
+        result.iterations.push(SecantResult {
+            iteration: iter+1,
+            x0,
+            x1,
+            error: err
+        });
+
+        if let Some(criterion) = criteria.check(x1, x0, fx_x1) {
+            result.converge(criterion);
+            break;
+        }
+        if let Some(outcome) = Outcome::inspect(x1, x0, fx_x1) {
+            result.fail(outcome);
+            break;
+        }
+    }
+
+    Ok(result)
+}
+
+/*
+ *  Inverse quadratic interpolation
+ *      x = Lagrange polynomial through (f0, x0), (f1, x1), (f2, x2) evaluated at f = 0
+ *  Same step as Brent without the bisection safeguard, equal f values stop with Stagnated
+ */
+pub fn inverse_quadratic_core(equation: &str, x0: f64, x1: f64, x2: f64, criteria: &StoppingCriteria) -> Result<IterativeResult<InverseQuadraticResult>, CalError> {
+    let expr: Expr = parse_expr(equation)?;
+
+    if x0 == x1 || x1 == x2 || x0 == x2 {
+        return Err(CalError::invalid_argument("x0, x1, x2", "must be distinct"));
+    }
+
+    criteria.validate()?;
+
+    let mut result : IterativeResult<InverseQuadraticResult> = IterativeResult::new();
+    let mut x      : [f64; 3]                                = [x0, x1, x2];
+    let mut fx     : [f64; 3]                                = [
+        utils::evaluate_expr(&expr, x0)?,
+        utils::evaluate_expr(&expr, x1)?,
+        utils::evaluate_expr(&expr, x2)?
+    ];
+
+    for iter in 0..criteria.max_iter {
+        // interpolating x(f) needs distinct f
+        if fx[0] == fx[1] || fx[1] == fx[2] || fx[0] == fx[2] {
+            result.fail(Outcome::Stagnated);
+            break;
+        }
+
+        let x_new  : f64 = inverse_quadratic_calc(&x, &fx);
+        let fx_new : f64 = utils::evaluate_expr(&expr, x_new)?;
+        let err    : f64 = utils::error_calc(x_new, x[2]);
+
+        x  = [x[1], x[2], x_new];
+        fx = [fx[1], fx[2], fx_new];
+
+        result.iterations.push(InverseQuadraticResult {
+            iteration: iter + 1,
+            x0: x[0],
+            x1: x[1],
+            x2: x[2],
+            error: err
+        });
+
+        if let Some(criterion) = criteria.check(x[2], x[1], fx[2]) {
+            result.converge(criterion);
+            break;
+        }
+        if let Some(outcome) = Outcome::inspect(x[2], x[1], fx[2]) {
+            result.fail(outcome);
+            break;
+        }
+    }
+
+    Ok(result)
+}
+
+/*
+ *  Muller's method (Chapra 7.4)
+ *      parabola a (x - x2)^2 + b (x - x2) + c through the three points
+ *      x3 = x2 - 2c / (b ± sqrt(b^2 - 4ac)), sign taken for the larger denominator
+ *  Evaluated over Complex, so real starting points can walk into a complex root
+ *  Tolerances are checked on |x3 - x2| and |f(x3)|
+ */
+pub fn muller_core(equation: &str, x0: f64, x1: f64, x2: f64, criteria: &StoppingCriteria) -> Result<IterativeResult<MullerResult>, CalError> {
+    let expr: Expr = parse_expr(equation)?;
+
+    if x0 == x1 || x1 == x2 || x0 == x2 {
+        return Err(CalError::invalid_argument("x0, x1, x2", "must be distinct"));
+    }
+
+    criteria.validate()?;
+
+    let mut result : IterativeResult<MullerResult> = IterativeResult::new();
+    let mut x      : [Complex; 3]                  = [Complex::real(x0), Complex::real(x1), Complex::real(x2)];
+    let mut fx     : [Complex; 3]                  = [
+        complex::evaluate_complex(&expr, x[0])?,
+        complex::evaluate_complex(&expr, x[1])?,
+        complex::evaluate_complex(&expr, x[2])?
+    ];
+
+    for iter in 0..criteria.max_iter {
+        let dx: Complex = match muller_calc(&x, &fx) {
+            Some(dx) => dx,
+            None     => {
+                result.fail(Outcome::Stagnated);
+                break;
+            },
+        };
+        let x_new: Complex = x[2] + dx;
+
+        if let Some(outcome) = Outcome::inspect_vec(&[x_new.re, x_new.im]) {
+            result.fail(outcome);
+            break;
+        }
+
+        let fx_new : Complex = complex::evaluate_complex(&expr, x_new)?;
+        let err    : f64     = nonlinear::relative_error(&[x_new.re, x_new.im], &[x[2].re, x[2].im]);
+
+        x  = [x[1], x[2], x_new];
+        fx = [fx[1], fx[2], fx_new];
+
+        result.iterations.push(MullerResult {
+            iteration: iter + 1,
+            x0: x[0].re,
+            x1: x[1].re,
+            x2: x[2].re,
+            x0_im: x[0].im,
+            x1_im: x[1].im,
+            x2_im: x[2].im,
+            error: err
+        });
+
+        if let Some(criterion) = criteria.check_vec(&[x[2].re, x[2].im], &[x[1].re, x[1].im], fx[2].abs()) {
+            result.converge(criterion);
+            break;
+        }
+        if x[2] == x[1] {
+            result.fail(Outcome::Stagnated);
+            break;
+        }
+    }
+
+    Ok(result)
+}
+
+pub fn broyden_core(equations: &[String], variables: &[String], initial: &[f64], update: BroydenUpdate, criteria: &StoppingCriteria) -> Result<IterativeResult<BroydenResult>, CalError> {
+    let system = System::new(equations, variables, initial)?;
+
+    criteria.validate()?;
+
+    let mut result : IterativeResult<BroydenResult> = IterativeResult::new();
+    let mut x      : Vec<f64>                       = initial.to_vec();
+    let mut fx     : Vec<f64>                       = system.evaluate(&x)?;
+
+    if fx.iter().any(|v| !v.is_finite()) {
+        return Err(CalError::invalid_argument("initial", "F(x) is not finite at the initial guess"));
+    }
+
+    let mut matrix: Matrix = Matrix::from_rows(&system.finite_jacobian(&x)?)?;
+    if matrix.row_iter().flatten().any(|v| !v.is_finite()) {
+        return Err(CalError::invalid_argument("initial", "Jacobian is not finite at the initial guess"));
+    }
+    // J^-1 from one factorization, solved against every column of I
+    if update == BroydenUpdate::Bad {
+        let inverse = LuFactorization::factor(&matrix).and_then(|lu| lu.solve_matrix(&Matrix::identity(x.len())));
+        matrix = match inverse {
+            Ok((_, inverse)) if inverse.row_iter().flatten().all(|v| v.is_finite()) => inverse,
+            _ => {
+                result.fail(Outcome::DerivativeZero);
+                return Ok(result);
+            },
+        };
+    }
+
+    for iter in 0..criteria.max_iter {
+        let neg_fx : Vec<f64> = fx.iter().map(|v| -v).collect();
+        let dx     : Vec<f64> = match update {
+            BroydenUpdate::Good => match LuFactorization::factor(&matrix).and_then(|lu| lu.solve(&neg_fx)) {
+                Ok((_, dx)) if dx.iter().all(|v| v.is_finite()) => dx,
+                _ => {
+                    result.fail(Outcome::DerivativeZero);
+                    break;
+                },
+            },
+            BroydenUpdate::Bad  => matrix.mul_vec(&neg_fx)?,
+        };
+
+        let x_prev : Vec<f64> = x.clone();
+        for (xi, dxi) in x.iter_mut().zip(&dx) {
+            *xi += dxi;
+        }
+
+        if let Some(outcome) = Outcome::inspect_vec(&x) {
+            result.fail(outcome);
+            break;
+        }
+
+        let fx_new   : Vec<f64> = system.evaluate(&x)?;
+        let df       : Vec<f64> = fx_new.iter().zip(&fx).map(|(a, b)| a - b).collect();
+        let residual : f64      = nonlinear::norm(&fx_new);
+        fx = fx_new;
+
+        // no change in F, the next update would divide by 0
+        let updated: bool = match update {
+            BroydenUpdate::Good => broyden_good_calc(&mut matrix, &dx, &df)?,
+            BroydenUpdate::Bad  => broyden_bad_calc(&mut matrix, &dx, &df)?,
+        };
+
+        result.iterations.push(BroydenResult {
+            iteration: iter + 1,
+            x: x.clone(),
+            residual,
+            jacobian: matrix.clone(),
+            error: nonlinear::relative_error(&x, &x_prev)
+        });
+
+        if let Some(criterion) = criteria.check_vec(&x, &x_prev, residual) {
+            result.converge(criterion);
+            break;
+        }
+        if !residual.is_finite() {
+            result.fail(Outcome::NonFinite);
+            break;
+        }
+        if !updated {
+            result.fail(Outcome::Stagnated);
+            break;
+        }
+    }
 
     Ok(result)
 }
@@ -353,14 +1575,341 @@ fn bisection_calc(xl: f64, xr: f64) -> f64 {
     ( xl + xr ) / 2.0
 }
 
+fn ridders_calc(xl: f64, xm: f64, fx_left: f64, fx_right: f64, fx_mid: f64, root: f64) -> f64 {
+    let sign: f64 = if fx_left >= fx_right { 1.0 } else { -1.0 };
+    xm + (xm - xl) * sign * fx_mid / root
+}
+
 fn false_position_calc(xl: f64, xr: f64, fx_left: f64, fx_right: f64) -> f64 {
     ((xl * fx_right) - (xr * fx_left)) / (fx_right - fx_left)
 }
 
+// m for the kept endpoint, None for plain regula falsi
+fn false_position_factor(variant: FalsePositionVariant, f_old: f64, f_new: f64) -> Option<f64> {
+    match variant {
+        FalsePositionVariant::RegulaFalsi    => None,
+        FalsePositionVariant::Illinois       => Some(0.5),
+        FalsePositionVariant::Pegasus        => Some(f_old / (f_old + f_new)),
+        FalsePositionVariant::AndersonBjorck => {
+            let m: f64 = 1.0 - f_new / f_old;
+            Some(if m > 0.0 { m } else { 0.5 })
+        },
+    }
+}
+
+// Brent iterations on any f, evaluation errors are returned as is
+fn brent_calc(f: &impl Fn(f64) -> Result<f64, CalError>, xl: f64, xr: f64, criteria: &StoppingCriteria) -> Result<IterativeResult<BrentResult>, CalError> {
+    let mut result : IterativeResult<BrentResult> = IterativeResult::new();
+
+    let (mut a, mut b, mut c) = (xl, xr, xr);
+    let mut fa : f64 = f(a)?;
+    let mut fb : f64 = f(b)?;
+    let mut fc : f64 = fb;
+
+    if fa * fb > 0.0 {
+        return Err(CalError::NoSignChange { xl, xr });
+    }
+
+    // endpoint is already a root, nothing to bracket
+    if fa == 0.0 || fb == 0.0 {
+        result.iterations.push(BrentResult {
+            iteration: 1,
+            xl,
+            xr,
+            x: if fa == 0.0 { a } else { b },
+            step: BrentStep::Bisection,
+            error: 0.0
+        });
+        result.converge(StopCriterion::Residual);
+        return Ok(result);
+    }
+
+    let mut d  : f64 = b - a;
+    let mut e  : f64 = d;
+
+    for iter in 0..criteria.max_iter {
+        // keep root between b and c
+        if (fb > 0.0 && fc > 0.0) || (fb < 0.0 && fc < 0.0) {
+            c  = a;
+            fc = fa;
+            d  = b - a;
+            e  = d;
+        }
+        // b must be the best estimate
+        if fc.abs() < fb.abs() {
+            a  = b;
+            b  = c;
+            c  = a;
+            fa = fb;
+            fb = fc;
+            fc = fa;
+        }
+
+        let tol  : f64 = 2.0 * f64::EPSILON * b.abs() + 0.5 * criteria.abs_tol;
+        let half : f64 = 0.5 * (c - b);
+
+        if fb == 0.0 {
+            result.converge(StopCriterion::Residual);
+            break;
+        }
+        // bracket can't be shrunk anymore
+        if half.abs() <= tol {
+            result.converge(StopCriterion::AbsoluteTolerance);
+            break;
+        }
+
+        let mut step : BrentStep = BrentStep::Bisection;
+
+        if e.abs() >= tol && fa.abs() > fb.abs() {
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                step = BrentStep::Secant;
+                (2.0 * half * s, 1.0 - s)
+            }
+            else {
+                step = BrentStep::InverseQuadratic;
+                let q = fa / fc;
+                let r = fb / fc;
+                (
+                    s * (2.0 * half * q * (q - r) - (b - a) * (r - 1.0)),
+                    (q - 1.0) * (r - 1.0) * (s - 1.0)
+                )
+            };
+
+            if p > 0.0 {
+                q = -q;
+            }
+            p = p.abs();
+
+            // interpolation must fall inside the bracket and shrink faster than bisection
+            if 2.0 * p < (3.0 * half * q - (tol * q).abs()).min((e * q).abs()) {
+                e = d;
+                d = p / q;
+            }
+            else {
+                step = BrentStep::Bisection;
+                d    = half;
+                e    = d;
+            }
+        }
+        else {
+            d = half;
+            e = d;
+        }
+
+        let (bracket_l, bracket_r) = (b.min(c), b.max(c));
+        let b_prev : f64 = b;
+
+        a  = b;
+        fa = fb;
+        b += if d.abs() > tol { d } else { tol.copysign(half) };
+        fb = f(b)?;
+
+        result.iterations.push(BrentResult {
+            iteration: iter + 1,
+            xl: bracket_l,
+            xr: bracket_r,
+            x: b,
+            step,
+            error: utils::error_calc(b, b_prev)
+        });
+
+        if let Some(criterion) = criteria.check(b, b_prev, fb) {
+            result.converge(criterion);
+            break;
+        }
+        if let Some(outcome) = Outcome::inspect(b, b_prev, fb) {
+            result.fail(outcome);
+            break;
+        }
+    }
+
+    Ok(result)
+}
+
+// (x, f(x), iterations) minimizing |f(x)| in [lo, hi]
+fn golden_section_calc(f: &impl Fn(&[f64]) -> f64, mut lo: f64, mut hi: f64, max_iter: u64) -> (f64, f64, usize) {
+    let ratio          : f64   = (5f64.sqrt() - 1.0) / 2.0;
+    let mut x1         : f64   = hi - ratio * (hi - lo);
+    let mut x2         : f64   = lo + ratio * (hi - lo);
+    let mut f1         : f64   = f(&[x1]).abs();
+    let mut f2         : f64   = f(&[x2]).abs();
+    let mut iterations : usize = 0;
+
+    while (iterations as u64) < max_iter && hi - lo > f64::EPSILON * (lo.abs() + hi.abs()) {
+        // NaN is never smaller, moves away from undefined part
+        if f1 < f2 || f2.is_nan() {
+            hi = x2;
+            x2 = x1;
+            f2 = f1;
+            x1 = hi - ratio * (hi - lo);
+            f1 = f(&[x1]).abs();
+        }
+        else {
+            lo = x1;
+            x1 = x2;
+            f1 = f2;
+            x2 = lo + ratio * (hi - lo);
+            f2 = f(&[x2]).abs();
+        }
+        iterations += 1;
+    }
+
+    let x: f64 = (lo + hi) / 2.0;
+    (x, f(&[x]), iterations)
+}
+
 fn newton_calc(x: f64, fx_base: f64, fx_diff: f64) -> f64 {
     x - ( fx_base / fx_diff )
 }
 
+/*
+ *  g = 1/f, from (f g)^(n) = 0 (Leibniz rule)
+ *      g^(n) = -g * sum_(k = 1..n) C(n, k) f^(k) g^(n-k)
+ *  values are [f, f', ..., f^(d)], None when g^(d) is 0
+ */
+fn householder_calc(x: f64, values: &[f64]) -> Option<f64> {
+    let order  : usize    = values.len() - 1;
+    let mut g  : Vec<f64> = vec![1.0 / values[0]];
+
+    for n in 1..=order {
+        let mut binomial : f64 = 1.0;
+        let mut sum      : f64 = 0.0;
+        for k in 1..=n {
+            binomial *= (n - k + 1) as f64 / k as f64;
+            sum += binomial * values[k] * g[n - k];
+        }
+        g.push(-g[0] * sum);
+    }
+
+    if g[order] == 0.0 || !g[order].is_finite() {
+        return None;
+    }
+
+    Some(x + order as f64 * g[order - 1] / g[order])
+}
+
 fn secant_calc(x0: f64, x1: f64, fx_x0: f64, fx_x1: f64) -> f64 {
     x1 - ( fx_x1 * ( (x1 - x0) / (fx_x1 - fx_x0) ) )
-}
\ No newline at end of file
+}
+
+// Lagrange form of x(f) at f = 0, f values must be distinct
+fn inverse_quadratic_calc(x: &[f64; 3], fx: &[f64; 3]) -> f64 {
+    x[0] * fx[1] * fx[2] / ((fx[0] - fx[1]) * (fx[0] - fx[2]))
+        + x[1] * fx[0] * fx[2] / ((fx[1] - fx[0]) * (fx[1] - fx[2]))
+        + x[2] * fx[0] * fx[1] / ((fx[2] - fx[0]) * (fx[2] - fx[1]))
+}
+
+// step from x2, None when the parabola has no usable root (zero denominator)
+fn muller_calc(x: &[Complex; 3], fx: &[Complex; 3]) -> Option<Complex> {
+    let h0 : Complex = x[1] - x[0];
+    let h1 : Complex = x[2] - x[1];
+    let d0 : Complex = (fx[1] - fx[0]) / h0;
+    let d1 : Complex = (fx[2] - fx[1]) / h1;
+
+    let a   : Complex = (d1 - d0) / (h1 + h0);
+    let b   : Complex = a * h1 + d1;
+    let c   : Complex = fx[2];
+    let rad : Complex = (b * b - Complex::real(4.0) * a * c).sqrt();
+
+    let den: Complex = if (b + rad).abs() >= (b - rad).abs() { b + rad } else { b - rad };
+    if den.abs() == 0.0 {
+        return None;
+    }
+
+    Some(-(Complex::real(2.0) * c) / den)
+}
+
+// B = B + (df - B dx) dx^T / (dx^T dx), false when dx = 0
+fn broyden_good_calc(b: &mut Matrix, dx: &[f64], df: &[f64]) -> Result<bool, CalError> {
+    let denom: f64 = dx.iter().map(|v| v * v).sum();
+    if denom == 0.0 {
+        return Ok(false);
+    }
+
+    let b_dx: Vec<f64> = b.mul_vec(dx)?;
+    for i in 0..b.rows() {
+        let scale: f64 = (df[i] - b_dx[i]) / denom;
+        for (value, dxj) in b.row_mut(i).iter_mut().zip(dx) {
+            *value += scale * dxj;
+        }
+    }
+
+    Ok(true)
+}
+
+// H = H + (dx - H df) df^T / (df^T df), false when df = 0
+fn broyden_bad_calc(h: &mut Matrix, dx: &[f64], df: &[f64]) -> Result<bool, CalError> {
+    let denom: f64 = df.iter().map(|v| v * v).sum();
+    if denom == 0.0 {
+        return Ok(false);
+    }
+
+    let h_df: Vec<f64> = h.mul_vec(df)?;
+    for i in 0..h.rows() {
+        let scale: f64 = (dx[i] - h_df[i]) / denom;
+        for (value, dfj) in h.row_mut(i).iter_mut().zip(df) {
+            *value += scale * dfj;
+        }
+    }
+
+    Ok(true)
+}
+
+// x0 - (x1 - x0)^2 / (x2 - 2 x1 + x0), None when the denominator is 0
+fn aitken_calc(x0: f64, x1: f64, x2: f64) -> Option<f64> {
+    let denom: f64 = x2 - 2.0 * x1 + x0;
+    if denom == 0.0 {
+        return None;
+    }
+
+    Some(x0 - (x1 - x0) * (x1 - x0) / denom)
+}
+
+// f'(x) by dual numbers, central difference when some function has no dual rule
+fn slope_at(expr: &Expr, x: f64) -> Result<f64, CalError> {
+    if autodiff::is_supported(expr) {
+        return Ok(autodiff::evaluate_dual(expr, x)?.slope);
+    }
+
+    central_slope(expr, x)
+}
+
+/*
+ *  Central difference (4th order) with step halving from h = 1e-2 * max(|x|, 1)
+ *      f'(x) = (-f(x + 2h) + 8 f(x + h) - 8 f(x - h) + f(x - 2h)) / 12h
+ *  Stopped when two estimates agree, or when the change grows again (round-off dominates)
+ */
+fn central_slope(expr: &Expr, x: f64) -> Result<f64, CalError> {
+    let central = |h: f64| -> Result<f64, CalError> {
+        let fx_p_h2 : f64 = utils::evaluate_expr(expr, x + h * 2.)?;
+        let fx_p_h  : f64 = utils::evaluate_expr(expr, x + h)?;
+        let fx_n_h2 : f64 = utils::evaluate_expr(expr, x - h * 2.)?;
+        let fx_n_h  : f64 = utils::evaluate_expr(expr, x - h)?;
+
+        Ok((-fx_p_h2 + 8. * fx_p_h - 8. * fx_n_h + fx_n_h2) / (12. * h))
+    };
+
+    let mut h         : f64 = 1e-2 * x.abs().max(1.0);
+    let mut slope     : f64 = central(h)?;
+    let mut last_diff : f64 = f64::INFINITY;
+
+    for _ in 0..20 {
+        h /= 2.0;
+        let next : f64 = central(h)?;
+        let diff : f64 = (next - slope).abs();
+
+        if diff > last_diff {
+            break;
+        }
+
+        slope     = next;
+        last_diff = diff;
+
+        if diff <= 1e-12 * slope.abs().max(1.0) {
+            break;
+        }
+    }
+
+    Ok(slope)
+}
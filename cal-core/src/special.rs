@@ -0,0 +1,318 @@
+// special.rs
+use std::f64::consts;
+
+
+
+/*
+ * ---- Special functions registered into utils context ----
+ *
+ *  gamma, lgamma : Lanczos approximation (g = 7, n = 9), reflection for x < 0.5
+ *  erf, erfc     : Maclaurin series for |x| < 2, continued fraction of erfc otherwise
+ *  j0, j1, y0, y1: Bessel functions, power series for |x| < BESSEL_SERIES_LIMIT
+ *                  and Hankel asymptotic expansion otherwise
+ *
+ *  Poles / outside domain give NaN or inf, which is reported by utils::evaluate_expr
+ */
+const EULER_GAMMA: f64 = 0.577_215_664_901_532_9;
+
+const LANCZOS_G    : f64       = 7.0;
+const LANCZOS_COEF : [f64; 9]  = [
+    0.999_999_999_999_809_9,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_1,
+    -176.615_029_162_140_6,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_572e-6,
+    1.505_632_735_149_311_6e-7
+];
+
+// Crossing point where series cancellation and asymptotic truncation are both around 1e-11
+const BESSEL_SERIES_LIMIT: f64 = 13.0;
+
+
+
+// Gamma & Beta
+
+pub fn gamma(x: f64) -> f64 {
+    if x.is_nan() {
+        return f64::NAN;
+    }
+    // exact value for integer, factorial is overflowed after 171
+    if x == x.floor() {
+        if x <= 0.0 {
+            return f64::NAN;
+        }
+        if x <= 171.0 {
+            return (1..x as u64).fold(1.0, |acc, i| acc * i as f64);
+        }
+    }
+    if x < 0.5 {
+        return consts::PI / ((consts::PI * x).sin() * gamma(1.0 - x));
+    }
+
+    let (t, sum) = lanczos_sum(x - 1.0);
+
+    // split power to avoid overflow before multiplying e^-t
+    let half_pow: f64 = t.powf((x - 0.5) / 2.0);
+    (2.0 * consts::PI).sqrt() * half_pow * (half_pow * (-t).exp()) * sum
+}
+
+// ln|gamma(x)|
+pub fn lgamma(x: f64) -> f64 {
+    if x.is_nan() {
+        return f64::NAN;
+    }
+    if x == x.floor() && x <= 0.0 {
+        return f64::INFINITY;
+    }
+    if x < 0.5 {
+        return (consts::PI / (consts::PI * x).sin().abs()).ln() - lgamma(1.0 - x);
+    }
+
+    let (t, sum) = lanczos_sum(x - 1.0);
+
+    0.5 * (2.0 * consts::PI).ln() + (x - 0.5) * t.ln() - t + sum.ln()
+}
+
+pub fn beta(a: f64, b: f64) -> f64 {
+    if a > 0.0 && b > 0.0 {
+        return (lgamma(a) + lgamma(b) - lgamma(a + b)).exp();
+    }
+
+    gamma(a) * gamma(b) / gamma(a + b)
+}
+
+
+
+// Error function
+
+pub fn erf(x: f64) -> f64 {
+    if x.abs() < 2.0 {
+        return erf_series(x);
+    }
+
+    x.signum() * (1.0 - erfc_fraction(x.abs()))
+}
+
+pub fn erfc(x: f64) -> f64 {
+    if x.abs() < 2.0 {
+        return 1.0 - erf_series(x);
+    }
+    if x > 0.0 {
+        return erfc_fraction(x);
+    }
+
+    2.0 - erfc_fraction(-x)
+}
+
+
+
+// Bessel functions (first and second kind, order 0 and 1)
+
+pub fn bessel_j0(x: f64) -> f64 {
+    let x: f64 = x.abs();
+    if x < BESSEL_SERIES_LIMIT {
+        return bessel_j_series(0, x);
+    }
+
+    let (p, q) = hankel_pq(0, x);
+    let chi: f64 = x - consts::FRAC_PI_4;
+    (2.0 / (consts::PI * x)).sqrt() * (p * chi.cos() - q * chi.sin())
+}
+
+pub fn bessel_j1(x: f64) -> f64 {
+    // odd function
+    if x < 0.0 {
+        return -bessel_j1(-x);
+    }
+    if x < BESSEL_SERIES_LIMIT {
+        return bessel_j_series(1, x);
+    }
+
+    let (p, q) = hankel_pq(1, x);
+    let chi: f64 = x - 3.0 * consts::FRAC_PI_4;
+    (2.0 / (consts::PI * x)).sqrt() * (p * chi.cos() - q * chi.sin())
+}
+
+pub fn bessel_y0(x: f64) -> f64 {
+    if x <= 0.0 {
+        return if x == 0.0 { f64::NEG_INFINITY } else { f64::NAN };
+    }
+    if x < BESSEL_SERIES_LIMIT {
+        return 2.0 / consts::PI * (x / 2.0).ln() * bessel_j_series(0, x) - bessel_y_sum(0, x) / consts::PI;
+    }
+
+    let (p, q) = hankel_pq(0, x);
+    let chi: f64 = x - consts::FRAC_PI_4;
+    (2.0 / (consts::PI * x)).sqrt() * (p * chi.sin() + q * chi.cos())
+}
+
+pub fn bessel_y1(x: f64) -> f64 {
+    if x <= 0.0 {
+        return if x == 0.0 { f64::NEG_INFINITY } else { f64::NAN };
+    }
+    if x < BESSEL_SERIES_LIMIT {
+        return -2.0 / (consts::PI * x) + 2.0 / consts::PI * (x / 2.0).ln() * bessel_j_series(1, x) - bessel_y_sum(1, x) / consts::PI;
+    }
+
+    let (p, q) = hankel_pq(1, x);
+    let chi: f64 = x - 3.0 * consts::FRAC_PI_4;
+    (2.0 / (consts::PI * x)).sqrt() * (p * chi.sin() + q * chi.cos())
+}
+
+
+
+// Elementary helpers
+
+// sin(x) / x (unnormalized), 1 at x = 0
+pub fn sinc(x: f64) -> f64 {
+    if x == 0.0 {
+        return 1.0;
+    }
+
+    x.sin() / x
+}
+
+pub fn log(base: f64, x: f64) -> f64 {
+    x.ln() / base.ln()
+}
+
+// Result has the sign of divisor (floored division), unlike `%` operator
+pub fn modulo(a: f64, b: f64) -> f64 {
+    a - b * (a / b).floor()
+}
+
+// H(0) = 0.5
+pub fn heaviside(x: f64) -> f64 {
+    if x > 0.0 {
+        1.0
+    }
+    else if x < 0.0 {
+        0.0
+    }
+    else {
+        0.5
+    }
+}
+
+// step(0) = 1
+pub fn step(x: f64) -> f64 {
+    if x >= 0.0 { 1.0 } else { 0.0 }
+}
+
+
+
+// Calculations
+
+// (t, A(x)) of Lanczos approximation: gamma(x + 1) = sqrt(2pi) * t^(x + 0.5) * e^-t * A(x)
+fn lanczos_sum(x: f64) -> (f64, f64) {
+    let mut sum: f64 = LANCZOS_COEF[0];
+    for (i, coef) in LANCZOS_COEF.iter().enumerate().skip(1) {
+        sum += coef / (x + i as f64);
+    }
+
+    (x + LANCZOS_G + 0.5, sum)
+}
+
+// 2 / sqrt(pi) * sum (-1)^n x^(2n + 1) / (n! (2n + 1))
+fn erf_series(x: f64) -> f64 {
+    let x2       : f64 = x * x;
+    let mut term : f64 = x;
+    let mut sum  : f64 = x;
+
+    for n in 1..100 {
+        term *= -x2 / n as f64;
+        let add: f64 = term / (2 * n + 1) as f64;
+        sum += add;
+
+        if add.abs() < 1e-17 * sum.abs() {
+            break;
+        }
+    }
+
+    2.0 / consts::PI.sqrt() * sum
+}
+
+// erfc(x) = e^(-x^2) / sqrt(pi) * 1 / (x + (1/2) / (x + 1 / (x + (3/2) / (x + ...)))), x >= 2
+fn erfc_fraction(x: f64) -> f64 {
+    let mut fraction: f64 = x;
+    for k in (1..=60).rev() {
+        fraction = x + (k as f64 / 2.0) / fraction;
+    }
+
+    (-x * x).exp() / (consts::PI.sqrt() * fraction)
+}
+
+// J_n(x) = (x/2)^n sum (-x^2/4)^k / (k! (n + k)!)
+fn bessel_j_series(n: u32, x: f64) -> f64 {
+    let q        : f64 = -x * x / 4.0;
+    let mut term : f64 = (x / 2.0).powi(n as i32) / (1..=n).fold(1.0, |acc, i| acc * i as f64);
+    let mut sum  : f64 = term;
+
+    for k in 1..200 {
+        term *= q / (k as f64 * (n + k) as f64);
+        sum += term;
+
+        if term.abs() < 1e-17 * sum.abs() {
+            break;
+        }
+    }
+
+    sum
+}
+
+// (x/2)^n sum (psi(k + 1) + psi(n + k + 1)) (-x^2/4)^k / (k! (n + k)!), psi(m + 1) = H_m - gamma
+fn bessel_y_sum(n: u32, x: f64) -> f64 {
+    let q            : f64 = -x * x / 4.0;
+    let mut term     : f64 = (x / 2.0).powi(n as i32) / (1..=n).fold(1.0, |acc, i| acc * i as f64);
+    let mut harmonic : f64 = 0.0;                                          // H_k
+    let mut shifted  : f64 = (1..=n).fold(0.0, |acc, i| acc + 1.0 / i as f64); // H_(n + k)
+    let mut sum      : f64 = term * (harmonic + shifted - 2.0 * EULER_GAMMA);
+
+    for k in 1..200 {
+        term     *= q / (k as f64 * (n + k) as f64);
+        harmonic += 1.0 / k as f64;
+        shifted  += 1.0 / (n + k) as f64;
+
+        let add: f64 = term * (harmonic + shifted - 2.0 * EULER_GAMMA);
+        sum += add;
+
+        if add.abs() < 1e-17 * sum.abs() {
+            break;
+        }
+    }
+
+    sum
+}
+
+/*
+ *  Hankel asymptotic P, Q with mu = 4n^2
+ *      t_k = prod_(j = 1..k) (mu - (2j - 1)^2) / (k! (8x)^k)
+ *      P = t_0 - t_2 + t_4 - ...,  Q = t_1 - t_3 + t_5 - ...
+ *  Stopped at the smallest term since the series is divergent
+ */
+fn hankel_pq(n: u32, x: f64) -> (f64, f64) {
+    let mu       : f64 = 4.0 * (n * n) as f64;
+    let mut term : f64 = 1.0;
+    let mut p    : f64 = 1.0;
+    let mut q    : f64 = 0.0;
+
+    for k in 1..100 {
+        let next: f64 = term * (mu - ((2 * k - 1) * (2 * k - 1)) as f64) / (k as f64 * 8.0 * x);
+        if next.abs() >= term.abs() || next == 0.0 {
+            break;
+        }
+        term = next;
+
+        match k % 4 {
+            0 => p += term,
+            1 => q += term,
+            2 => p -= term,
+            _ => q -= term,
+        }
+    }
+
+    (p, q)
+}
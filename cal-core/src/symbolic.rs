@@ -0,0 +1,512 @@
+// symbolic.rs
+use crate::error::CalError;
+use crate::expression::parse_expr;
+
+use std::fmt;
+
+use meval::Expr;
+use meval::tokenizer::{Operation, Token};
+use serde_wasm_bindgen::to_value;
+use wasm_bindgen::prelude::*;
+
+
+
+// Duplication struct
+
+/*
+ * ---- Expression tree built from meval RPN ----
+ *
+ *  Every node is simplified when it is built (see add / sub / mul ... below),
+ *  so the printed derivative is already folded: d/dx (x^3) => 3 * x^2
+ *  Variables other than the differentiating one are counted as constants
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Node {
+    Num(f64),
+    Var(String),
+    Neg(Box<Node>),
+    Add(Box<Node>, Box<Node>),
+    Sub(Box<Node>, Box<Node>),
+    Mul(Box<Node>, Box<Node>),
+    Div(Box<Node>, Box<Node>),
+    Rem(Box<Node>, Box<Node>),
+    Pow(Box<Node>, Box<Node>),
+    Func(String, Vec<Node>)
+}
+
+// Expression size is grown quickly with the order (product rule)
+const MAX_ORDER: usize = 20;
+
+
+
+// wasm conversion JsValue
+
+// n-th derivative by x, e.g. ("x^3 + sin(x)", 2) => "6 * x - sin(x)"
+#[wasm_bindgen]
+pub fn symbolic_derivative(equation: &str, order: usize) -> JsValue {
+    match symbolic_derivative_core(equation, "x", order) {
+        Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
+        Err(e) => e.into(),
+    }
+}
+
+// [f, f', ..., f^(order)] by x, same shape as `taylor` equations input
+#[wasm_bindgen]
+pub fn symbolic_derivatives(equation: &str, order: usize) -> JsValue {
+    match symbolic_derivatives_core(equation, "x", order) {
+        Ok(result) => to_value(&result).unwrap_or_else(|e| JsValue::from_str(&format!("Serialization error: {}", e))),
+        Err(e) => e.into(),
+    }
+}
+
+
+
+// Add implement method
+
+pub fn symbolic_derivative_core(equation: &str, var: &str, order: usize) -> Result<String, CalError> {
+    let derivatives: Vec<String> = symbolic_derivatives_core(equation, var, order)?;
+
+    Ok(derivatives[order].clone())
+}
+
+pub fn symbolic_derivatives_core(equation: &str, var: &str, order: usize) -> Result<Vec<String>, CalError> {
+    if order > MAX_ORDER {
+        return Err(CalError::invalid_argument("order", &format!("must be at most {}", MAX_ORDER)));
+    }
+
+    let expr     : Expr        = parse_expr(equation)?;
+    let mut node : Node        = Node::from_expr(&expr)?;
+    let mut result : Vec<String> = vec![node.to_string()];
+
+    for _ in 0..order {
+        node = node.differentiate(var)?;
+        result.push(node.to_string());
+    }
+
+    Ok(result)
+}
+
+impl Node {
+    pub(crate) fn from_expr(expr: &Expr) -> Result<Node, CalError> {
+        let mut stack: Vec<Node> = Vec::new();
+
+        for token in expr.iter() {
+            let node: Node = match token {
+                Token::Number(value)        => Node::Num(*value),
+                Token::Var(name)            => Node::Var(name.clone()),
+                Token::Unary(Operation::Minus) => neg(pop(&mut stack)?),
+                Token::Unary(_)             => continue,
+                Token::Binary(op)           => {
+                    let right : Node = pop(&mut stack)?;
+                    let left  : Node = pop(&mut stack)?;
+
+                    match op {
+                        Operation::Plus  => add(left, right),
+                        Operation::Minus => sub(left, right),
+                        Operation::Times => mul(left, right),
+                        Operation::Div   => div(left, right),
+                        Operation::Rem   => Node::Rem(Box::new(left), Box::new(right)),
+                        Operation::Pow   => pow(left, right),
+                    }
+                },
+                Token::Func(name, Some(count)) => {
+                    if stack.len() < *count {
+                        return Err(malformed());
+                    }
+                    let args: Vec<Node> = stack.split_off(stack.len() - count);
+                    Node::Func(name.clone(), args)
+                },
+                _ => return Err(malformed()),
+            };
+
+            stack.push(node);
+        }
+
+        match (stack.pop(), stack.is_empty()) {
+            (Some(node), true) => Ok(node),
+            _                  => Err(malformed()),
+        }
+    }
+
+    pub(crate) fn depends_on(&self, var: &str) -> bool {
+        match self {
+            Node::Num(_)                                   => false,
+            Node::Var(name)                                => name == var,
+            Node::Neg(a)                                   => a.depends_on(var),
+            Node::Add(a, b) | Node::Sub(a, b) | Node::Mul(a, b)
+            | Node::Div(a, b) | Node::Rem(a, b) | Node::Pow(a, b) => a.depends_on(var) || b.depends_on(var),
+            Node::Func(_, args)                            => args.iter().any(|arg| arg.depends_on(var)),
+        }
+    }
+
+    pub(crate) fn differentiate(&self, var: &str) -> Result<Node, CalError> {
+        if !self.depends_on(var) {
+            return Ok(Node::Num(0.0));
+        }
+
+        let node: Node = match self {
+            Node::Num(_)    => Node::Num(0.0),
+            Node::Var(_)    => Node::Num(1.0),
+            Node::Neg(a)    => neg(a.differentiate(var)?),
+            Node::Add(a, b) => add(a.differentiate(var)?, b.differentiate(var)?),
+            Node::Sub(a, b) => sub(a.differentiate(var)?, b.differentiate(var)?),
+            // (uv)' = u'v + uv'
+            Node::Mul(a, b) => add(
+                mul(a.differentiate(var)?, (**b).clone()),
+                mul((**a).clone(), b.differentiate(var)?)
+            ),
+            // (u/v)' = (u'v - uv') / v^2
+            Node::Div(a, b) => {
+                if !b.depends_on(var) {
+                    div(a.differentiate(var)?, (**b).clone())
+                }
+                else {
+                    div(
+                        sub(mul(a.differentiate(var)?, (**b).clone()), mul((**a).clone(), b.differentiate(var)?)),
+                        pow((**b).clone(), Node::Num(2.0))
+                    )
+                }
+            },
+            // u % c = u - c * trunc(u / c), piecewise derivative is u'
+            Node::Rem(a, b) => {
+                if b.depends_on(var) {
+                    return Err(unsupported("%"));
+                }
+                a.differentiate(var)?
+            },
+            Node::Pow(a, b) => pow_derivative(a, b, var)?,
+            Node::Func(name, args) => func_derivative(name, args, var)?,
+        };
+
+        Ok(node)
+    }
+
+    // 1 = +/-, 2 = * / %, 3 = unary minus, 4 = ^, 5 = atom (same as meval)
+    fn precedence(&self) -> u8 {
+        match self {
+            Node::Num(value) if *value < 0.0       => 3,
+            Node::Num(_) | Node::Var(_) | Node::Func(..) => 5,
+            Node::Neg(_)                           => 3,
+            Node::Add(..) | Node::Sub(..)          => 1,
+            Node::Mul(..) | Node::Div(..) | Node::Rem(..) => 2,
+            Node::Pow(..)                          => 4,
+        }
+    }
+
+    fn is_negative(&self) -> bool {
+        match self {
+            Node::Num(value) => *value < 0.0,
+            Node::Neg(_)     => true,
+            _                => false,
+        }
+    }
+}
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Node::Num(value)    => write!(f, "{}", value),
+            Node::Var(name)     => write!(f, "{}", name),
+            Node::Neg(a)        => write!(f, "-{}", wrap(a, 4, true)),
+            Node::Add(a, b)     => write!(f, "{} + {}", wrap(a, 1, true), wrap(b, 1, false)),
+            Node::Sub(a, b)     => write!(f, "{} - {}", wrap(a, 1, true), wrap(b, 2, false)),
+            Node::Mul(a, b)     => write!(f, "{} * {}", wrap(a, 2, true), wrap(b, 2, false)),
+            Node::Div(a, b)     => write!(f, "{} / {}", wrap(a, 2, true), wrap(b, 3, false)),
+            Node::Rem(a, b)     => write!(f, "{} % {}", wrap(a, 2, true), wrap(b, 3, false)),
+            Node::Pow(a, b)     => write!(f, "{}^{}", wrap(a, 5, false), wrap(b, 5, false)),
+            Node::Func(name, args) => {
+                let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+                write!(f, "{}({})", name, args.join(", "))
+            },
+        }
+    }
+}
+
+
+
+// Calculations
+
+fn malformed() -> CalError {
+    CalError::ParseError {
+        position: None,
+        token: None,
+        reason: "Malformed expression".to_string()
+    }
+}
+
+fn unsupported(name: &str) -> CalError {
+    CalError::invalid_argument(name, "symbolic derivative is not supported")
+}
+
+fn pop(stack: &mut Vec<Node>) -> Result<Node, CalError> {
+    stack.pop().ok_or_else(malformed)
+}
+
+// Parenthesized when the child binds looser than needed, or is negative behind an operator
+fn wrap(node: &Node, min_precedence: u8, leading: bool) -> String {
+    if node.precedence() < min_precedence || (!leading && node.is_negative()) {
+        format!("({})", node)
+    }
+    else {
+        node.to_string()
+    }
+}
+
+fn num(value: f64) -> Node {
+    Node::Num(value)
+}
+
+fn func(name: &str, arg: Node) -> Node {
+    Node::Func(name.to_string(), vec![arg])
+}
+
+fn is_num(node: &Node, value: f64) -> bool {
+    matches!(node, Node::Num(v) if *v == value)
+}
+
+fn is_integer(value: f64) -> bool {
+    value.is_finite() && value == value.trunc()
+}
+
+// u^c => (u, c), otherwise (node, 1)
+fn base_exponent(node: &Node) -> (&Node, f64) {
+    match node {
+        Node::Pow(base, e) => match **e {
+            Node::Num(e) => (base, e),
+            _            => (node, 1.0),
+        },
+        _ => (node, 1.0),
+    }
+}
+
+// c * u => (c, u), otherwise (1, node)
+fn coefficient(node: &Node) -> (f64, &Node) {
+    match node {
+        Node::Mul(c, rest) => match **c {
+            Node::Num(c) => (c, rest),
+            _            => (1.0, node),
+        },
+        _ => (1.0, node),
+    }
+}
+
+// u^a / u^b
+fn power_ratio(base: &Node, exponent: f64) -> Node {
+    if exponent < 0.0 {
+        div(num(1.0), pow(base.clone(), num(-exponent)))
+    }
+    else {
+        pow(base.clone(), num(exponent))
+    }
+}
+
+fn neg(a: Node) -> Node {
+    match a {
+        Node::Num(value) => num(-value),
+        Node::Neg(inner) => *inner,
+        Node::Sub(x, y)  => Node::Sub(y, x),
+        a if coefficient(&a).0 != 1.0 => {
+            let (c, rest) = coefficient(&a);
+            mul(num(-c), rest.clone())
+        },
+        a => Node::Neg(Box::new(a)),
+    }
+}
+
+fn add(a: Node, b: Node) -> Node {
+    match (a, b) {
+        (Node::Num(x), Node::Num(y))      => num(x + y),
+        (Node::Num(0.0), b)               => b,
+        (a, Node::Num(0.0))               => a,
+        (a, Node::Num(y)) if y < 0.0      => Node::Sub(Box::new(a), Box::new(num(-y))),
+        (a, Node::Neg(b))                 => sub(a, *b),
+        (Node::Neg(a), b)                 => sub(b, *a),
+        (a, b) if coefficient(&b).0 < 0.0 => sub(a, neg(b)),
+        // like terms: c1 * u + c2 * u = (c1 + c2) * u
+        (a, b) if coefficient(&a).1 == coefficient(&b).1 => mul(num(coefficient(&a).0 + coefficient(&b).0), coefficient(&a).1.clone()),
+        (a, b) => Node::Add(Box::new(a), Box::new(b)),
+    }
+}
+
+fn sub(a: Node, b: Node) -> Node {
+    match (a, b) {
+        (Node::Num(x), Node::Num(y)) => num(x - y),
+        (a, Node::Num(0.0))          => a,
+        (Node::Num(0.0), b)          => neg(b),
+        (a, Node::Neg(b))            => add(a, *b),
+        (a, b) if coefficient(&a).1 == coefficient(&b).1 => mul(num(coefficient(&a).0 - coefficient(&b).0), coefficient(&a).1.clone()),
+        (a, b) => Node::Sub(Box::new(a), Box::new(b)),
+    }
+}
+
+fn mul(a: Node, b: Node) -> Node {
+    match (a, b) {
+        (Node::Num(x), Node::Num(y))              => num(x * y),
+        (Node::Num(0.0), _) | (_, Node::Num(0.0)) => num(0.0),
+        (Node::Num(1.0), b)                       => b,
+        (a, Node::Num(1.0))                       => a,
+        (Node::Num(-1.0), b)                      => neg(b),
+        (a, Node::Num(-1.0))                      => neg(a),
+        // coefficient is moved to the front and merged
+        (a, Node::Num(y))                         => mul(num(y), a),
+        (Node::Num(x), b) if coefficient(&b).0 != 1.0 => {
+            let (c, rest) = coefficient(&b);
+            mul(num(x * c), rest.clone())
+        },
+        (a, b) if !matches!(a, Node::Num(_)) && coefficient(&a).0 != 1.0 => {
+            let (c, rest) = coefficient(&a);
+            mul(num(c), mul(rest.clone(), b))
+        },
+        (Node::Neg(a), b)                         => neg(mul(*a, b)),
+        (a, Node::Neg(b))                         => neg(mul(a, *b)),
+        (Node::Num(x), Node::Div(n, d)) if matches!(*n, Node::Num(_)) => div(mul(num(x), *n), *d),
+        (a, Node::Div(n, d)) if is_num(&n, 1.0)   => div(a, *d),
+        // u^a * u^b = u^(a + b)
+        (a, b) if base_exponent(&a).0 == base_exponent(&b).0 => pow(base_exponent(&a).0.clone(), num(base_exponent(&a).1 + base_exponent(&b).1)),
+        (a, Node::Mul(b, c)) if base_exponent(&a).0 == base_exponent(&b).0 => mul(mul(a, *b), *c),
+        (a, b) => Node::Mul(Box::new(a), Box::new(b)),
+    }
+}
+
+fn div(a: Node, b: Node) -> Node {
+    match (a, b) {
+        // folded only when the quotient is exact, 1/3 is kept as fraction
+        (Node::Num(x), Node::Num(y)) if y != 0.0 && is_integer(x / y) => num(x / y),
+        (Node::Num(0.0), _)  => num(0.0),
+        (a, Node::Num(1.0))  => a,
+        (a, Node::Num(-1.0)) => neg(a),
+        (Node::Neg(a), b)    => neg(div(*a, b)),
+        (a, Node::Neg(b))    => neg(div(a, *b)),
+        // u^a / u^b and c * u^a / u^b
+        (a, b) if base_exponent(&a).0 == base_exponent(&b).0 => power_ratio(base_exponent(&a).0, base_exponent(&a).1 - base_exponent(&b).1),
+        (a, b) if coefficient(&a).0 != 1.0 && base_exponent(coefficient(&a).1).0 == base_exponent(&b).0 => {
+            let (c, rest) = coefficient(&a);
+            mul(num(c), power_ratio(base_exponent(rest).0, base_exponent(rest).1 - base_exponent(&b).1))
+        },
+        (a, b) => Node::Div(Box::new(a), Box::new(b)),
+    }
+}
+
+fn pow(a: Node, b: Node) -> Node {
+    match (a, b) {
+        (Node::Num(x), Node::Num(y)) if is_integer(y) && x.powf(y).is_finite() => num(x.powf(y)),
+        (_, Node::Num(0.0)) => num(1.0),
+        (a, Node::Num(1.0)) => a,
+        (Node::Num(1.0), _) => num(1.0),
+        // (u^a)^b = u^(ab) only for integer b, (x^2)^0.5 is |x| not x
+        (a, Node::Num(y)) if is_integer(y) && base_exponent(&a).1 != 1.0 => pow(base_exponent(&a).0.clone(), num(base_exponent(&a).1 * y)),
+        (a, b) => Node::Pow(Box::new(a), Box::new(b)),
+    }
+}
+
+fn pow_derivative(a: &Node, b: &Node, var: &str) -> Result<Node, CalError> {
+    let da: Node = a.differentiate(var)?;
+
+    // (u^c)' = c * u^(c - 1) * u'
+    if !b.depends_on(var) {
+        return Ok(mul(mul(b.clone(), pow(a.clone(), sub(b.clone(), num(1.0)))), da));
+    }
+
+    let db: Node = b.differentiate(var)?;
+
+    // (c^v)' = c^v * ln(c) * v'
+    if !a.depends_on(var) {
+        let ln_a: Node = match a {
+            Node::Var(name) if name == "e" => num(1.0),
+            _                              => func("ln", a.clone()),
+        };
+        return Ok(mul(mul(pow(a.clone(), b.clone()), ln_a), db));
+    }
+
+    // (u^v)' = u^v * (v' * ln(u) + v * u' / u)
+    Ok(mul(
+        pow(a.clone(), b.clone()),
+        add(mul(db, func("ln", a.clone())), div(mul(b.clone(), da), a.clone()))
+    ))
+}
+
+fn func_derivative(name: &str, args: &[Node], var: &str) -> Result<Node, CalError> {
+    // two arguments functions
+    if args.len() == 2 {
+        let (u, v) = (args[0].clone(), args[1].clone());
+
+        let node: Node = match name {
+            // log(b, x) = ln(x) / ln(b)
+            "log"   => return div(func("ln", v), func("ln", u)).differentiate(var),
+            // (u u' + v v') / hypot(u, v)
+            "hypot" => div(
+                add(mul(u.clone(), u.differentiate(var)?), mul(v.clone(), v.differentiate(var)?)),
+                Node::Func(name.to_string(), args.to_vec())
+            ),
+            // atan2(y, x)' = (x y' - y x') / (x^2 + y^2)
+            "atan2" => div(
+                sub(mul(v.clone(), u.differentiate(var)?), mul(u.clone(), v.differentiate(var)?)),
+                add(pow(v, num(2.0)), pow(u, num(2.0)))
+            ),
+            "mod" if !v.depends_on(var) => u.differentiate(var)?,
+            _ => return Err(unsupported(name)),
+        };
+
+        return Ok(node);
+    }
+
+    if args.len() != 1 {
+        return Err(unsupported(name));
+    }
+
+    let u  : Node = args[0].clone();
+    let du : Node = u.differentiate(var)?;
+
+    let outer: Node = match name {
+        "sin"   => func("cos", u),
+        "cos"   => neg(func("sin", u)),
+        "tan"   => pow(func("sec", u), num(2.0)),
+        "sec"   => mul(func("sec", u.clone()), func("tan", u)),
+        "csc"   => neg(mul(func("csc", u.clone()), func("cot", u))),
+        "cot"   => neg(pow(func("csc", u), num(2.0))),
+
+        "asin"  => div(num(1.0), func("sqrt", sub(num(1.0), pow(u, num(2.0))))),
+        "acos"  => neg(div(num(1.0), func("sqrt", sub(num(1.0), pow(u, num(2.0)))))),
+        "atan"  => div(num(1.0), add(num(1.0), pow(u, num(2.0)))),
+        "asec"  => div(num(1.0), mul(func("abs", u.clone()), func("sqrt", sub(pow(u, num(2.0)), num(1.0))))),
+        "acsc"  => neg(div(num(1.0), mul(func("abs", u.clone()), func("sqrt", sub(pow(u, num(2.0)), num(1.0)))))),
+        "acot"  => neg(div(num(1.0), add(num(1.0), pow(u, num(2.0))))),
+
+        "sinh"  => func("cosh", u),
+        "cosh"  => func("sinh", u),
+        "tanh"  => pow(func("sech", u), num(2.0)),
+        "sech"  => neg(mul(func("sech", u.clone()), func("tanh", u))),
+        "csch"  => neg(mul(func("csch", u.clone()), func("coth", u))),
+        "coth"  => neg(pow(func("csch", u), num(2.0))),
+
+        "asinh" => div(num(1.0), func("sqrt", add(pow(u, num(2.0)), num(1.0)))),
+        "acosh" => div(num(1.0), func("sqrt", sub(pow(u, num(2.0)), num(1.0)))),
+        "atanh" => div(num(1.0), sub(num(1.0), pow(u, num(2.0)))),
+
+        "exp"   => func("exp", u),
+        "ln"    => div(num(1.0), u),
+        "log10" => div(num(1.0), mul(u, func("ln", num(10.0)))),
+        "log2"  => div(num(1.0), mul(u, func("ln", num(2.0)))),
+        "sqrt"  => div(num(1.0), mul(num(2.0), func("sqrt", u))),
+        "cbrt"  => div(num(1.0), mul(num(3.0), pow(func("cbrt", u), num(2.0)))),
+        "abs"   => func("signum", u),
+
+        "erf"   => mul(div(num(2.0), func("sqrt", Node::Var("pi".to_string()))), func("exp", neg(pow(u, num(2.0))))),
+        "erfc"  => neg(mul(div(num(2.0), func("sqrt", Node::Var("pi".to_string()))), func("exp", neg(pow(u, num(2.0)))))),
+
+        "j0"    => neg(func("j1", u)),
+        "j1"    => sub(func("j0", u.clone()), div(func("j1", u.clone()), u)),
+        "y0"    => neg(func("y1", u)),
+        "y1"    => sub(func("y0", u.clone()), div(func("y1", u.clone()), u)),
+
+        // undefined at u = 0 (limit is 0)
+        "sinc"  => sub(div(func("cos", u.clone()), u.clone()), div(func("sin", u.clone()), pow(u, num(2.0)))),
+
+        // piecewise constant, derivative is 0 almost everywhere
+        "floor" | "ceil" | "round" | "signum" | "heaviside" | "step" => num(0.0),
+
+        _ => return Err(unsupported(name)),
+    };
+
+    Ok(mul(outer, du))
+}
@@ -1,5 +1,10 @@
 // utils.rs
-use meval::{Context, Expr};
+use crate::error::CalError;
+use crate::matrix::Matrix;
+use crate::special;
+
+use meval::{Context, ContextProvider, Expr, FuncEvalError};
+use meval::tokenizer::Token;
 use std::cell::RefCell;
 use std::f64::consts;
  
@@ -25,41 +30,225 @@ use std::f64::consts;
   *       asec, acsc, acot,
   *       sech, csch, coth,
   *       ln2, ln10, log2e, log10e
+  * 
+  * special (special.rs):
+  *       gamma(x), lgamma, beta(a, b),
+  *       erf, erfc,
+  *       j0, j1, y0, y1,
+  *       sinc, log(base, x), log10, log2,
+  *       hypot(x, y), mod(a, b),
+  *       heaviside, step
+  * 
+  *  gamma without "(" is still Euler-Mascheroni constant (vars and funcs are separated)
   */
+fn default_context() -> Context<'static> {
+    let mut ctx = Context::new();
+    
+    ctx.var("tau", consts::TAU);
+    ctx.var("phi", (1. + 5.0_f64.sqrt()) / 2.);
+    ctx.var("gamma", 0.577_215_664_901_532_9); // Euler-Mascheroni
+
+    ctx.func("cbrt", f64::cbrt);
+
+    ctx.func("sec", |x: f64| 1.0 / x.cos());
+    ctx.func("csc", |x: f64| 1.0 / x.sin());
+    ctx.func("cot", |x: f64| 1.0 / x.tan());
+
+    ctx.func("asec", |x: f64| (1.0 / x).acos());
+    ctx.func("acsc", |x: f64| (1.0 / x).asin());
+    ctx.func("acot", |x: f64| (1.0 / x).atan());
+
+    ctx.func("sech", |x: f64| 1.0 / x.cosh());
+    ctx.func("csch", |x: f64| 1.0 / x.sinh());
+    ctx.func("coth", |x: f64| 1.0 / x.tanh());
+
+    ctx.func("gamma", special::gamma);
+    ctx.func("lgamma", special::lgamma);
+    ctx.func2("beta", special::beta);
+
+    ctx.func("erf", special::erf);
+    ctx.func("erfc", special::erfc);
+
+    ctx.func("j0", special::bessel_j0);
+    ctx.func("j1", special::bessel_j1);
+    ctx.func("y0", special::bessel_y0);
+    ctx.func("y1", special::bessel_y1);
+
+    ctx.func("sinc", special::sinc);
+    ctx.func2("log", special::log);
+    ctx.func("log10", f64::log10);
+    ctx.func("log2", f64::log2);
+    ctx.func2("hypot", f64::hypot);
+    ctx.func2("mod", special::modulo);
+
+    ctx.func("heaviside", special::heaviside);
+    ctx.func("step", special::step);
+    
+    ctx
+}
+
+// Session context: default_context + constants / functions registered by user
+// Builtin: default_context only, built once for is_builtin lookups
 thread_local! {
-    static CTX: RefCell<Context<'static>> = RefCell::new({
-        let mut ctx = Context::new();
-        
-        ctx.var("tau", consts::TAU);
-        ctx.var("phi", (1. + 5.0_f64.sqrt()) / 2.);
-        ctx.var("gamma", 0.57721566490153286060651209008240243104215933593992); // Euler-Mascheroni 
-
-        ctx.func("cbrt", f64::cbrt);
-
-        ctx.func("sec", |x: f64| 1.0 / x.cos());
-        ctx.func("csc", |x: f64| 1.0 / x.sin());
-        ctx.func("cot", |x: f64| 1.0 / x.tan());
-
-        ctx.func("asec", |x: f64| (1.0 / x).acos());
-        ctx.func("acsc", |x: f64| (1.0 / x).asin());
-        ctx.func("acot", |x: f64| (1.0 / x).atan());
-
-        ctx.func("sech", |x: f64| 1.0 / x.cosh());
-        ctx.func("csch", |x: f64| 1.0 / x.sinh());
-        ctx.func("coth", |x: f64| 1.0 / x.tanh());
-        
-        ctx
+    static CTX     : RefCell<Context<'static>> = RefCell::new(default_context());
+    static BUILTIN : Context<'static>          = default_context();
+}
+
+pub fn with_context<R>(f: impl FnOnce(&Context<'static>) -> R) -> R {
+    CTX.with(|ctx_cell: &RefCell<Context<'static>>| f(&ctx_cell.borrow()))
+}
+
+/*
+ * ---- User definitions ----
+ *
+ *  Registered into CTX until reset_context(), so every method sees them
+ *  Function body is compiled with snapshot of CTX at definition time,
+ *  it can call earlier definitions but never itself (no recursion)
+ *  Names from default_context can't be redefined
+ */
+pub fn is_builtin(name: &str) -> bool {
+    BUILTIN.with(|ctx: &Context<'static>| {
+        ctx.get_var(name).is_some() || !matches!(ctx.eval_func(name, &[0.0]), Err(FuncEvalError::UnknownFunction))
+    })
+}
+
+pub fn register_constant(name: &str, value: f64) -> Result<(), CalError> {
+    if is_builtin(name) {
+        return Err(CalError::invalid_argument(name, "built-in name can't be redefined"));
+    }
+    if !value.is_finite() {
+        return Err(CalError::invalid_argument(name, "constant must be finite"));
+    }
+
+    CTX.with(|ctx_cell: &RefCell<Context<'static>>| {
+        ctx_cell.borrow_mut().var(name, value);
+    });
+
+    Ok(())
+}
+
+pub fn register_function(name: &str, params: &[&str], body: Expr) -> Result<(), CalError> {
+    if is_builtin(name) {
+        return Err(CalError::invalid_argument(name, "built-in name can't be redefined"));
+    }
+
+    let f     = compile_expr(body, params)?;
+    let count = params.len();
+
+    // meval checks the arguments count, NaN is reported as NonFiniteValue by the caller
+    CTX.with(|ctx_cell: &RefCell<Context<'static>>| {
+        ctx_cell.borrow_mut().funcn(name, move |args: &[f64]| f(args).unwrap_or(f64::NAN), count);
+    });
+
+    Ok(())
+}
+
+pub fn reset_context() {
+    CTX.with(|ctx_cell: &RefCell<Context<'static>>| {
+        *ctx_cell.borrow_mut() = default_context();
     });
 }
 
-pub fn evaluate_expr(expr: &Expr, value: f64) -> f64 {
+// x is bound per call, CTX itself is never modified
+pub fn evaluate_expr(expr: &Expr, value: f64) -> Result<f64, CalError> {
+    let result: f64 = match evaluate_expr_vars(expr, &[("x", value)]) {
+        Ok(result) => result,
+        Err(e)     => return Err(CalError::EvaluationFailed { x: value, reason: e.to_string() }),
+    };
+
+    // ln(-1), 1/0, ... are not counted as value
+    if !result.is_finite() {
+        return Err(CalError::NonFiniteValue { x: value, value: result });
+    }
+
+    Ok(result)
+}
+
+/*
+ * ---- Multi-variable evaluation ----
+ *
+ *  bindings are looked up before CTX, so a binding can shadow a constant (e.g. "gamma")
+ *  evaluate_expr_vars(&expr, &[("t", 0.5), ("y", 1.0)])
+ *  evaluate_expr_with(&expr, &map)   <- HashMap<String, f64>, BTreeMap, Vec<(S, f64)>
+ *
+ *  NaN / inf is returned as is, the caller decides how to treat it
+ */
+struct Bindings<'a>(&'a [(&'a str, f64)]);
+
+// names and values are matched by index (compiled expression)
+struct Positional<'a> {
+    names: &'a [String],
+    values: &'a [f64]
+}
+
+impl<'a> ContextProvider for Bindings<'a> {
+    fn get_var(&self, name: &str) -> Option<f64> {
+        self.0.iter().find(|(var, _)| *var == name).map(|(_, value)| *value)
+    }
+}
+
+impl<'a> ContextProvider for Positional<'a> {
+    fn get_var(&self, name: &str) -> Option<f64> {
+        let index = self.names.iter().position(|var| var == name)?;
+        self.values.get(index).copied()
+    }
+}
+
+pub fn evaluate_expr_with<C: ContextProvider>(expr: &Expr, bindings: C) -> Result<f64, CalError> {
     CTX.with(|ctx_cell: &RefCell<Context<'static>>| {
-        let mut ctx = ctx_cell.borrow_mut();
-        ctx.var("x", value);
-        expr.eval_with_context(&(*ctx)).unwrap_or(0.)
+        let ctx = ctx_cell.borrow();
+        expr.eval_with_context((bindings, &(*ctx))).map_err(eval_error)
     })
 }
 
+pub fn evaluate_expr_vars(expr: &Expr, vars: &[(&str, f64)]) -> Result<f64, CalError> {
+    evaluate_expr_with(expr, Bindings(vars))
+}
+
+/*
+ *  Compile once, call many times with values in the same order as vars
+ *      let f = compile_expr(expr, &["t", "y"])?;
+ *      f(&[0.5, 1.0])?;
+ *
+ *  Snapshot of CTX is taken here, unknown variables / functions are rejected up front
+ *  NaN / inf is returned as is like evaluate_expr_vars
+ */
+pub fn compile_expr(expr: Expr, vars: &[&str]) -> Result<impl Fn(&[f64]) -> Result<f64, CalError>, CalError> {
+    let ctx   : Context<'static> = CTX.with(|ctx_cell: &RefCell<Context<'static>>| ctx_cell.borrow().clone());
+    let names : Vec<String>      = vars.iter().map(|var| var.to_string()).collect();
+
+    for token in expr.iter() {
+        match token {
+            Token::Var(name) if !names.contains(name) && ctx.get_var(name).is_none() => {
+                return Err(CalError::UnknownVariable { name: name.clone() });
+            },
+            Token::Func(name, Some(args)) => match ctx.eval_func(name, &vec![0.0; *args]) {
+                Err(FuncEvalError::UnknownFunction) => return Err(CalError::UnknownFunction { name: name.clone() }),
+                Err(e)                              => return Err(CalError::invalid_argument(name, &e.to_string())),
+                Ok(_)                               => {},
+            },
+            _ => {},
+        }
+    }
+
+    Ok(move |values: &[f64]| {
+        if values.len() != names.len() {
+            return Err(CalError::DimensionMismatch { expected: names.len(), got: values.len() });
+        }
+
+        expr.eval_with_context((Positional { names: &names, values }, &ctx)).map_err(eval_error)
+    })
+}
+
+fn eval_error(e: meval::Error) -> CalError {
+    match e {
+        meval::Error::UnknownVariable(name)                           => CalError::UnknownVariable { name },
+        meval::Error::Function(name, FuncEvalError::UnknownFunction) => CalError::UnknownFunction { name },
+        meval::Error::Function(name, e)                               => CalError::invalid_argument(&name, &e.to_string()),
+        e                                                             => CalError::from(e),
+    }
+}
+
 pub fn error_calc(x_new: f64, x_old: f64) -> f64 {
     if x_new.abs() <= 1e-24 && x_old.abs() <= 1e-24 { return 0.; }
     (x_new - x_old).abs() / x_new * 100.0
@@ -123,235 +312,7 @@ pub fn vec_add(mat1: &Vec<f64>, mat2: &Vec<f64>) -> Vec<f64> {
         .collect()
 }
 
-pub fn mat_conv2d(mat: &Vec<f64>, size: usize) -> Vec<Vec<f64>> {
-    let mut result: Vec<Vec<f64>> = vec![vec![0.0; size]; size];
-    let mut index : usize         = 0;
-
-    for i in 0..size {
-        for j in 0..size {
-            result[i][j] = mat[index];
-            index += 1;
-        }
-    }
-
-    result
-}
-
-pub fn mat_transpose(mat: &Vec<Vec<f64>>) -> Vec<Vec<f64>> {
-    // Empty Matrix
-    if mat.is_empty() || mat[0].is_empty() {
-        return vec![];
-    }
-
-    let cols: usize = mat[0].len();
-
-    // Matrix has not transpose property
-    if !mat.iter().all(|row| row.len() == cols) {
-        panic!("Matrix is can't transpose");
-    }
-
-    let rows: usize = mat.len();
-
-    let mut transposed: Vec<Vec<f64>> = vec![vec![0.0; rows]; cols];
-
-    for i in 0..rows {
-        for j in 0..cols {
-            transposed[j][i] = mat[i][j];
-        }
-    }
-
-    transposed
-}
-
-pub fn mat_imul_vec(mat: &Vec<Vec<f64>>, vec: &Vec<f64>) -> Vec<f64> {
-    let rows: usize = mat.len();
-    let cols: usize = mat[0].len();
-
-    if cols != vec.len() {
-        panic!("Matrix is can't Mult");
-    }
-
-    let mut result: Vec<f64> = vec![0.0; rows];
-
-    for i in 0..rows {
-        for j in 0..cols {
-            result[i] += mat[i][j] * vec[j];
-        }
-    }
-
-    result
-}
-
-// Using RREF
-pub fn det(mat: &Vec<Vec<f64>>) -> f64 {
-    let rows: usize = mat.len();
-    let cols: usize = mat[0].len();
-    if rows != cols {
-        panic!("Matrix is not rectangular");
-    }
-
-    let mut m   : Vec<Vec<f64>> = mat.clone();
-    let mut det : f64           = 1.0;
-
-    let size : usize = m.len(); // rows named as size
-
-    for i in 0..size {
-        let mut max_row: usize = i;
-        for j in i+1 .. size {
-            if m[j][i].abs() > m[max_row][i].abs() {
-                max_row = j;
-            }
-        }
-
-        // minimum value that counted as 0.0
-        if m[max_row][i].abs() < 1e-12 {
-            return 0.0;
-        }
-
-        // Swap flag **MUST
-        if max_row != i {
-            m.swap(i, max_row);
-            det = -det;
-        }
-
-        for j in i + 1..size {
-            let ratio: f64 = m[j][i] / m[i][i];
-            for k in i..size {
-                m[j][k] -= ratio * m[i][k];
-            }
-        }
-    }
-
-    // Det calc
-    for i in 0..size {
-        det *= m[i][i];
-    }
-    
-    det
-}
-
-// Duplicated method for using in other topics
-pub fn guass(mat: &Vec<Vec<f64>>, ans: &Vec<f64>) -> Result<Vec<f64>, String> {
-
-    const EPSILON: f64 = 1e-32;
-
-    let mut aug_matrix  : Vec<Vec<f64>> = mat.clone();
-    let size            : usize         = mat.len();
-
-    if mat.len() != mat[0].len() || ans.len() != size {
-        return Err("Matrix size is not match".to_string());
-    }
-
-    for i in 0..size {
-        aug_matrix[i].push(ans[i]);
-    }
-
-    for i in 0..size {
-        let mut max_row: usize = i;
-        for j in i+1 ..size {
-            if aug_matrix[j][i].abs() >aug_matrix[max_row][i].abs() {
-                max_row = j;
-            }
-        }
-
-        if max_row != i {
-            aug_matrix.swap(i, max_row);
-        }
-
-        if aug_matrix[i][i].abs() < EPSILON {
-            continue;
-        }
-
-        let pivot: f64 = aug_matrix[i][i];
-        for j in 0..=size {
-            aug_matrix[i][j] /= pivot;
-        }
-
-        for j in 0..size {
-            if j != i {
-                let ratio: f64 = aug_matrix[j][i];
-                for k in 0..=size {
-                    aug_matrix[j][k] -= ratio * aug_matrix[i][k];
-                }
-            }
-        }
-    }
-
-    for row in &aug_matrix {
-        let lhs_zero = row[..size].iter().all(|&v| v.abs() < EPSILON);
-        let rhs_nonzero = row[size].abs() >= EPSILON;
-        if lhs_zero && rhs_nonzero {
-            return Err("Inconsistent system (no solution)".to_string());
-        }
-    }
-
-    let result = aug_matrix.iter().map(|row| row[size]).collect();
-
-    Ok(result)
-} 
-
-pub fn is_symmetric(mat: &Vec<Vec<f64>>) -> bool {
-    let size: usize = mat.len();
-
-    for i in 0..size {
-        for j in 0..size {
-            if mat[i][j] != mat[j][i] {
-                return false;
-            }
-        }
-    }
-
-    true
-}
-
-// Called when square matrix ONLY
-pub fn is_positive_definite(mat: &Vec<Vec<f64>>) -> bool {
-    let size = mat.len();
-
-    // testing symmetric
-    if !is_symmetric(mat) {
-        return false;
-    }
-
-    // make sub-matrix
-    for iter in 0..size {
-        let sub_matrix: Vec<Vec<f64>> = mat.iter()
-            .take(iter + 1)
-            .map(|rows| rows.iter().take(iter + 1).copied().collect())
-            .collect();
-
-        if det(&sub_matrix) < 0.0 {
-            return false;
-        }
-    }
-
-    true
-}
-
-// ----------------- unused method ----------------- 
-/*
- * pub fn mat_imul_mat(mat1: &Vec<Vec<f64>>, mat2: &Vec<Vec<f64>>) -> Vec<Vec<f64>> {
-   
-    let row_mat1: usize = mat1.len();
-    let col_mat1: usize = mat1[0].len();
-    let row_mat2: usize = mat2.len();
-    let col_mat2: usize = mat2[0].len();
-   
-    // Not mult property matrix
-    if col_mat1 != row_mat2 {
-        panic!("Matrix is can't Mult");
-    }
-
-    let mut result: Vec<Vec<f64>> = vec![vec![0.0; col_mat2]; row_mat1];
-
-    for i in 0..row_mat1 {
-        for j in 0..col_mat2 {
-            for k in 0..col_mat1 {
-                result[i][j] += mat1[i][k] * mat2[k][j];
-            }
-        }
-    }
-
-    result
+// Duplicated method for using in other topics, rows are copied into Matrix once
+pub fn guass(mat: &[Vec<f64>], ans: &[f64]) -> Result<Vec<f64>, CalError> {
+    Matrix::from_rows(mat)?.solve(ans)
 }
-*/
\ No newline at end of file
@@ -0,0 +1,125 @@
+#[cfg(test)]
+mod expression {
+    use cal_core::{bisection_core, clear_definitions, define_core, evaluate_expression_core, validate_expression_core, StoppingCriteria};
+
+    fn print_validation(equation: &str, variables: &[String]) {
+        let result = validate_expression_core(equation, variables);
+
+        println!("equation: {}\nvalid: {}", equation, result.valid);
+        if let Some(reason) = &result.reason {
+            println!("reason: {} token: {:?} position: {:?}", reason, result.token, result.position);
+        }
+        for v in &result.unknown_variables {
+            println!("unknown variable: {} at {}", v.name, v.position);
+        }
+        for f in &result.unknown_functions {
+            println!("unknown function: {} at {}", f.name, f.position);
+        }
+        println!();
+    }
+
+    #[test]
+    fn test_validate_expression() {
+        let variables = vec!["x".to_string()];
+
+        print_validation("x^2 - 7 * sin(x) + tau", &variables);
+        print_validation("43 * x - ", &variables);
+        print_validation("(x + 2", &variables);
+        print_validation("2 * x ) + 1", &variables);
+        print_validation("y * foo(x) + 1e-3 * bar", &variables);
+    }
+
+    #[test]
+    fn test_evaluate_expression() {
+        let variables = vec!["t".to_string(), "y".to_string()];
+
+        let cases = [
+            ("t^2 + y", vec![2.0, 1.0]),
+            ("cbrt(y) * tau + coth(t)", vec![1.0, 8.0]),
+            ("sin(t) + gamma", vec![0.0, 0.0]),
+        ];
+
+        for (equation, values) in cases.iter() {
+            match evaluate_expression_core(equation, &variables, values) {
+                Ok(result) => println!("{} at {:?} = {}", equation, values, result),
+                Err(e)     => println!("{} : {}", equation, e),
+            }
+        }
+
+        let unknown = evaluate_expression_core("t + z", &variables, &[1.0, 2.0]);
+        println!("t + z : {:?}", unknown);
+        assert!(unknown.is_err());
+
+        let result = evaluate_expression_core("t * y - 1", &variables, &[3.0, 4.0]).unwrap();
+        assert!((result - 11.0).abs() < 1e-12);
+
+        // same rule as every method: NaN / inf is an error, not a value
+        for equation in ["ln(-t)", "y / (t - 1)"].iter() {
+            let non_finite = evaluate_expression_core(equation, &variables, &[1.0, 2.0]);
+            println!("{} : {:?}", equation, non_finite);
+            assert!(non_finite.is_err());
+        }
+    }
+
+    #[test]
+    fn test_define() {
+        for definition in ["g = 9.81", "f(t) = t^2 + 1", "h(a, b) = f(a) * g - b"].iter() {
+            match define_core(definition) {
+                Ok(result) => println!("{} => {}({:?}) = {} {:?}", definition, result.name, result.params, result.body, result.value),
+                Err(e)     => panic!("{} : {}", definition, e),
+            }
+        }
+
+        let result = evaluate_expression_core("h(2, g)", &[], &[]).unwrap();
+        assert!((result - 4.0 * 9.81).abs() < 1e-12);
+
+        // definitions are shared with every method
+        let root = bisection_core("f(x) - 5", 0.0, 3.0, &StoppingCriteria::default()).unwrap();
+        println!("f(x) - 5 = 0 at x = {}", root.iterations.last().unwrap().xm);
+
+        for definition in ["sin(x) = x", "k(t) = t + q", "k(t, t) = t", "k(t = t", "k(t) = t +", "pi2"].iter() {
+            match define_core(definition) {
+                Ok(_)  => panic!("{} must be rejected", definition),
+                Err(e) => println!("{} : {}", definition, e),
+            }
+        }
+
+        clear_definitions();
+        assert!(evaluate_expression_core("g", &[], &[]).is_err());
+    }
+
+    #[test]
+    fn test_special_functions() {
+        // reference values from high precision series / libm
+        let cases: [(&str, f64); 22] = [
+            ("gamma(5)", 24.0),
+            ("gamma(0.5)", 1.7724538509055159),
+            ("gamma(-0.5)", -3.544907701811032),
+            ("gamma(170.5) / 1e305", 5.56209241456),
+            ("lgamma(100)", 359.1342053695754),
+            ("lgamma(-2.5)", -0.05624371649767457),
+            ("beta(2, 3)", 1.0 / 12.0),
+            ("erf(0.5)", 0.5204998778130465),
+            ("erf(3)", 0.9999779095030014),
+            ("erfc(3)", 2.2090496998585438e-05),
+            ("erfc(-2.5)", 1.999593047982555),
+            ("j0(1) + y1(1)", 0.7651976865579666 - 0.7812128213002887),
+            ("j1(5) + y0(5)", -0.32757913759146523 - 0.30851762524903376),
+            ("j0(12.9) + y0(13.1)", 0.19884243713633099 - 0.056925256781293836),
+            ("j1(20) + y1(50)", 0.06683312417585005 - 0.05679566856201477),
+            ("sinc(0) + sinc(pi)", 1.0),
+            ("log(2, 8) + log10(1000) + log2(0.25)", 4.0),
+            ("hypot(3, 4)", 5.0),
+            ("mod(-7, 3) + mod(7, -3)", 0.0),
+            ("heaviside(0) + heaviside(-1) + step(0)", 1.5),
+            ("gamma * gamma(1)", 0.5772156649015329),
+            ("x * erf(x)", 0.0),
+        ];
+
+        for (equation, expected) in cases.iter() {
+            let result = evaluate_expression_core(equation, &["x".to_string()], &[0.0]).unwrap();
+            println!("{} = {} (expected {})", equation, result, expected);
+            assert!((result - expected).abs() < 1e-10 * expected.abs().max(1.0));
+        }
+    }
+}
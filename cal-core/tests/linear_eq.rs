@@ -8,6 +8,13 @@ mod linear_eq {
         guass_jordan_core,
         inverse_matrix_core,
         lu_decomposition_core,
+        lu_factorize_core,
+        lu_solve_core,
+        qr_decomposition_core,
+        least_squares_core,
+        QrMethod,
+        Matrix,
+        RowOperation,
         cholesky_core,
         jacobi_core,
         guass_seidel_core,
@@ -74,62 +81,95 @@ mod linear_eq {
     #[test]
     fn test_cramer() {
         let (mat, rows, ans) = sample_i();
-        let result = cramer_core(mat, rows, ans).unwrap();
+        let result = cramer_core(mat, rows, ans, 1).unwrap();
         println!("det_true: {}", result.det_true);
 
         println!("det_iter:");
         for (i, d) in result.det_iter.iter().enumerate() {
-            println!("  det_iter[{}] = {}", i, d);
+            println!("  det_iter[{}] = {:?}", i, d);
         }
 
         println!("value:");
-        for (i, v) in result.value.iter().enumerate() {
-            println!("  value[{}] = {}", i, v);
+        for (i, v) in result.value.row_iter().enumerate() {
+            println!("  value[{}] = {:?}", i, v);
         }
     }
 
     #[test]
     fn test_guass_naive() {
         let (mat, rows, ans) = sample_i();
-        let result = guass_naive_core(mat, rows, ans).unwrap();
-        for (i, v) in (result.value).iter().enumerate() {
-            println!("[result{}]: {}", i, v);
+        let result = guass_naive_core(mat, rows, ans, 1).unwrap();
+        for (i, v) in result.value.row_iter().enumerate() {
+            println!("[result{}]: {:?}", i, v);
         }
     }
 
     #[test]
     fn test_guass_jordan() {
         let (mat, rows, ans) = sample_i();
-        let result = guass_jordan_core(mat, rows, ans).unwrap();
-        for (i, v) in (result.value).iter().enumerate() {
-            println!("[result{}]: {}", i, v);
+        let result = guass_jordan_core(mat, rows, ans, 1).unwrap();
+        for (i, v) in result.value.row_iter().enumerate() {
+            println!("[result{}]: {:?}", i, v);
         }
     }
 
+    #[test]
+    fn test_elimination_steps() {
+        let mat = vec![
+            0., 2., 1.,
+            1., 1., 1.,
+            2., 1., 0.
+        ];
+        let ans = vec![3., 3., 3.];
+
+        let result = guass_jordan_core(mat.clone(), 3, ans.clone(), 1).unwrap();
+        let steps  = result.steps.unwrap();
+        for s in &steps {
+            println!("step: {}, operation: {:?}", s.step, s.operation);
+            for row in s.matrix.row_iter() {
+                println!("  {:?}", row);
+            }
+        }
+
+        // zero leading entry is swapped first, last snapshot is [I | x]
+        assert_eq!(steps[0].operation, RowOperation::Swap { row: 0, other: 2 });
+        let last = &steps.last().unwrap().matrix;
+        for i in 0..3 {
+            assert!((last[(i, i)] - 1.).abs() < 1e-12);
+            assert!((last[(i, 3)] - 1.).abs() < 1e-12);
+        }
+
+        let naive = guass_naive_core(mat.clone(), 3, ans, 1).unwrap().steps.unwrap();
+        assert!(naive.iter().all(|s| !matches!(s.operation, RowOperation::Scale { .. })));
+
+        let inverse = inverse_matrix_core(mat, 3, vec![1., 0., 0.], 1).unwrap().steps.unwrap();
+        assert_eq!(inverse.last().unwrap().matrix.row(0).len(), 6);
+    }
+
     #[test]
     fn test_inverse_matrix() {
         let (mat, rows, ans) = sample_i();
-        let result = inverse_matrix_core(mat, rows, ans).unwrap();
+        let result = inverse_matrix_core(mat, rows, ans, 1).unwrap();
 
-        for i in result.inverse_mat {
+        for i in result.inverse_mat.row_iter() {
             for (_, val) in i.iter().enumerate() {
                 print!("{} ", val);
             }
             println!();
         }
 
-        for (i, v) in (result.value).iter().enumerate() {
-            println!("[result{}]: {}", i, v);
+        for (i, v) in result.value.row_iter().enumerate() {
+            println!("[result{}]: {:?}", i, v);
         }
     }
 
     #[test]
     fn test_lu_decomposition() {
         let (mat, rows, ans) = sample_i();
-        let result = lu_decomposition_core(mat, rows, ans).unwrap();
+        let result = lu_decomposition_core(mat, rows, ans, 1).unwrap();
 
         println!("LOWER MAT");
-        for i in result.lower_mat {
+        for i in result.lower_mat.row_iter() {
             for (_, val) in i.iter().enumerate() {
                 print!("{} ", val);
             }
@@ -138,7 +178,7 @@ mod linear_eq {
         println!();
 
         println!("UPPER MAT");
-        for i in result.upper_mat {
+        for i in result.upper_mat.row_iter() {
             for (_, val) in i.iter().enumerate() {
                 print!("{} ", val);
             }
@@ -147,25 +187,200 @@ mod linear_eq {
         println!();
 
         println!("FORWARD");
-        for val in result.forward_value.iter() {
-            println!("{} ", val);
+        for val in result.forward_value.row_iter() {
+            println!("{:?} ", val);
         }
         println!();
 
         println!("BACKWARD");
-        for val in result.backward_value.iter() {
-            println!("{} ", val);
+        for val in result.backward_value.row_iter() {
+            println!("{:?} ", val);
         }
         println!();
     }
 
+    #[test]
+    fn test_lu_pivoting() {
+        // zero leading entry, fails without row swap
+        let mat = vec![
+            0., 2., 1.,
+            1., 1., 1.,
+            2., 1., 0.
+        ];
+
+        let lu = lu_factorize_core(mat.clone(), 3).unwrap();
+        println!("permutation: {:?}", lu.permutation);
+        for (p, (l, u)) in lu.permutation_mat.row_iter().zip(lu.lower_mat.row_iter().zip(lu.upper_mat.row_iter())) {
+            println!("{:?} {:?} {:?}", p, l, u);
+        }
+
+        // P A = L U
+        let pa = lu.permutation_mat.mul(&Matrix::new(3, 3, mat.clone()).unwrap()).unwrap();
+        let lu_product = lu.lower_mat.mul(&lu.upper_mat).unwrap();
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!((pa[(i, j)] - lu_product[(i, j)]).abs() < 1e-12);
+            }
+        }
+
+        // same factorization for every right-hand side
+        for ans in [vec![3., 3., 3.], vec![1., 0., -1.]].iter() {
+            let (_, x) = lu.solve(ans).unwrap();
+            println!("x: {:?}", x);
+            let check = lu_decomposition_core(mat.clone(), 3, ans.clone(), 1).unwrap().backward_value.column(0);
+            for (a, b) in x.iter().zip(&check) {
+                assert!((a - b).abs() < 1e-12);
+            }
+        }
+        let (_, x) = lu.solve(&[3., 3., 3.]).unwrap();
+        for (a, b) in x.iter().zip(&[1., 1., 1.]) {
+            assert!((a - b).abs() < 1e-12);
+        }
+
+        assert!(lu_factorize_core(vec![1., 2., 2., 4.], 2).is_err());
+
+        // P, L, U sent back for new right-hand sides without refactoring
+        let flat  = |m: &Matrix| m.row_iter().flat_map(|row| row.to_vec()).collect::<Vec<f64>>();
+        let solve = lu_solve_core(lu.permutation.clone(), flat(&lu.lower_mat), flat(&lu.upper_mat), vec![3., 1., 3., 0., 3., -1.], 2).unwrap();
+        for (c, ans) in [vec![3., 3., 3.], vec![1., 0., -1.]].iter().enumerate() {
+            let (_, x) = lu.solve(ans).unwrap();
+            for (a, b) in solve.backward_value.column(c).iter().zip(&x) {
+                assert!((a - b).abs() < 1e-12);
+            }
+        }
+
+        assert!(lu_solve_core(vec![0, 0, 1], flat(&lu.lower_mat), flat(&lu.upper_mat), vec![3., 3., 3.], 1).is_err());
+        assert!(lu_solve_core(lu.permutation.clone(), flat(&lu.lower_mat), vec![1., 0., 0., 1.], vec![3., 3., 3.], 1).is_err());
+    }
+
+    #[test]
+    fn test_multiple_answers() {
+        // B = [b | e1], column 1 of X is column 0 of A^-1
+        let (mat, rows, ans) = sample_ii();
+        let mut answers: Vec<f64> = Vec::new();
+        for (i, b) in ans.iter().enumerate() {
+            answers.push(*b);
+            answers.push(if i == 0 { 1. } else { 0. });
+        }
+
+        let values = vec![
+            cramer_core(mat.clone(), rows, answers.clone(), 2).unwrap().value,
+            guass_naive_core(mat.clone(), rows, answers.clone(), 2).unwrap().value,
+            guass_jordan_core(mat.clone(), rows, answers.clone(), 2).unwrap().value,
+            inverse_matrix_core(mat.clone(), rows, answers.clone(), 2).unwrap().value,
+            lu_decomposition_core(mat.clone(), rows, answers.clone(), 2).unwrap().backward_value,
+            cholesky_core(mat.clone(), rows, answers.clone(), 2).unwrap().backward_value
+        ];
+
+        let a = Matrix::new(rows, rows, mat.clone()).unwrap();
+        let b = Matrix::new(rows, 2, answers.clone()).unwrap();
+        let inverse = inverse_matrix_core(mat.clone(), rows, ans, 1).unwrap().inverse_mat;
+        for value in &values {
+            println!("{:?}", value.row_iter().collect::<Vec<_>>());
+
+            // A X = B
+            let product = a.mul(value).unwrap();
+            for i in 0..rows {
+                for c in 0..2 {
+                    assert!((product[(i, c)] - b[(i, c)]).abs() < 1e-9);
+                }
+                assert!((value[(i, 1)] - inverse[(i, 0)]).abs() < 1e-9);
+            }
+        }
+
+        assert!(guass_naive_core(mat.clone(), rows, answers.clone(), 3).is_err());
+        assert!(guass_naive_core(mat, rows, answers, 0).is_err());
+    }
+
+    #[test]
+    fn test_qr_householder_mgs() {
+        // 4 x 3, over-determined
+        let mat = vec![
+            1., -1., 4.,
+            1., 4., -2.,
+            1., 4., 2.,
+            1., -1., 0.
+        ];
+        let a = Matrix::new(4, 3, mat.clone()).unwrap();
+
+        let householder = qr_decomposition_core(mat.clone(), 4, QrMethod::Householder).unwrap();
+        let gram_schmidt = qr_decomposition_core(mat.clone(), 4, QrMethod::ModifiedGramSchmidt).unwrap();
+        for qr in [&householder, &gram_schmidt].iter() {
+            println!("{:?}", qr.method);
+            for (q, r) in qr.q_mat.row_iter().zip(qr.r_mat.row_iter()) {
+                println!("{:?} {:?}", q, r);
+            }
+
+            // Q^T Q = I, Q R = A
+            let qt_q = qr.q_mat.transpose().mul(&qr.q_mat).unwrap();
+            let q_r = qr.q_mat.mul(&qr.r_mat).unwrap();
+            for i in 0..3 {
+                for j in 0..3 {
+                    let identity = if i == j { 1. } else { 0. };
+                    assert!((qt_q[(i, j)] - identity).abs() < 1e-12);
+                }
+            }
+            for i in 0..4 {
+                for j in 0..3 {
+                    assert!((q_r[(i, j)] - a[(i, j)]).abs() < 1e-12);
+                }
+            }
+        }
+
+        // r_ii > 0, both methods give the same factors
+        for i in 0..3 {
+            assert!(householder.r_mat[(i, i)] > 0.);
+            for j in 0..3 {
+                assert!((householder.r_mat[(i, j)] - gram_schmidt.r_mat[(i, j)]).abs() < 1e-12);
+            }
+        }
+
+        // wide and rank deficient
+        assert!(qr_decomposition_core(vec![1., 2., 3., 4., 5., 6.], 2, QrMethod::Householder).is_err());
+        assert!(qr_decomposition_core(vec![1., 2., 2., 4., 3., 6.], 3, QrMethod::ModifiedGramSchmidt).is_err());
+    }
+
+    #[test]
+    fn test_least_squares() {
+        // y = a + b x through (0, 1), (1, 2), (2, 2), (3, 4)  -->  a = b = 0.9
+        let mat = vec![
+            1., 0.,
+            1., 1.,
+            1., 2.,
+            1., 3.
+        ];
+        let ans = vec![1., 2., 2., 4.];
+
+        for method in [QrMethod::Householder, QrMethod::ModifiedGramSchmidt].iter() {
+            let result = least_squares_core(mat.clone(), 4, ans.clone(), *method).unwrap();
+            println!("{:?} x: {:?} residual: {}", result.method, result.value, result.residual_norm);
+
+            for (a, b) in result.value.iter().zip(&[0.9, 0.9]) {
+                assert!((a - b).abs() < 1e-12);
+            }
+            assert!((result.residual_norm - 0.7f64.sqrt()).abs() < 1e-12);
+        }
+
+        // square system is solved exactly
+        let (mat, rows, ans) = sample_ii();
+        let result = least_squares_core(mat.clone(), rows, ans.clone(), QrMethod::Householder).unwrap();
+        let check = guass_naive_core(mat, rows, ans, 1).unwrap().value.column(0);
+        for (a, b) in result.value.iter().zip(&check) {
+            assert!((a - b).abs() < 1e-9);
+        }
+        assert!(result.residual_norm < 1e-9);
+
+        assert!(least_squares_core(vec![1., 0., 1., 1., 1., 2.], 3, vec![1., 2.], QrMethod::Householder).is_err());
+    }
+
     #[test]
     fn test_cholesky() {
         let init: Vec<f64> = vec![0., 0., 0., 0.];
         let (mat, rows, ans) = sample_ii();
         let result = jacobi_core(mat, rows, ans, init).unwrap();
 
-        for r in &result {
+        println!("outcome: {:?}", result.outcome);
+        for r in &result.iterations {
             println!(
                 "iteraions: {} err: {}",
                 r.iteration, r.error
@@ -184,7 +399,8 @@ mod linear_eq {
         let (mat, rows, ans) = sample_ii();
         let result = guass_seidel_core(mat, rows, ans, init).unwrap();
 
-        for r in &result {
+        println!("outcome: {:?}", result.outcome);
+        for r in &result.iterations {
             println!(
                 "iteraions: {} err: {}",
                 r.iteration, r.error
@@ -202,7 +418,8 @@ mod linear_eq {
         let (mat, rows, ans, init, omega) = sample_iii();
         let result = over_relaxation_core(mat, rows, ans, init, omega).unwrap();
 
-        for r in &result {
+        println!("outcome: {:?}", result.outcome);
+        for r in &result.iterations {
             println!(
                 "iteraions: {} err: {}",
                 r.iteration, r.error
@@ -220,7 +437,8 @@ mod linear_eq {
         let init = vec![0., 0., 0., 0.];
         let (mat, rows, ans) = sample_ii();
         let result = cg_core(mat, rows, ans, init).unwrap();
-        for r in &result {
+        println!("outcome: {:?}", result.outcome);
+        for r in &result.iterations {
             println!(
                 "iteraions: {} err: {} lambda: {} alpha: {}",
                 r.iteration, r.error, r.lambda, r.alpha
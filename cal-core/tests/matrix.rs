@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod matrix {
+    use cal_core::{
+        matrix_det_core,
+        matrix_multiply_core,
+        matrix_transpose_core,
+        matrix_norm_core,
+        Matrix,
+        MatrixNorm
+    };
+
+    #[test]
+    fn test_det() {
+        let mat = vec![
+            6., -2., 2., 4.,
+            12., -8., 6., 10.,
+            3., -13., 9., 3.,
+            -6., 4., 1., -18.
+        ];
+
+        let det = matrix_det_core(mat, 4).unwrap();
+        println!("det: {}", det);
+        assert!((det - 144.).abs() < 1e-9);
+
+        // 2 * 3 is not square
+        assert!(matrix_det_core(vec![1., 2., 3., 4., 5., 6.], 2).is_err());
+        assert!(matrix_det_core(vec![1., 2., 3.], 0).is_err());
+    }
+
+    #[test]
+    fn test_multiply_transpose() {
+        let a = vec![
+            1., 2., 3.,
+            4., 5., 6.
+        ];
+        let b = vec![
+            7., 8.,
+            9., 10.,
+            11., 12.
+        ];
+
+        let product = matrix_multiply_core(a.clone(), 2, b, 3).unwrap();
+        for row in product.row_iter() {
+            println!("{:?}", row);
+        }
+        assert_eq!(product.row(0), &[58., 64.]);
+        assert_eq!(product.row(1), &[139., 154.]);
+
+        let transposed = matrix_transpose_core(a.clone(), 2).unwrap();
+        assert_eq!(transposed.row(2), &[3., 6.]);
+
+        // (2 * 3) * (2 * 3)
+        assert!(matrix_multiply_core(a.clone(), 2, a, 2).is_err());
+    }
+
+    #[test]
+    fn test_norm() {
+        let mat = vec![
+            1., -2.,
+            -3., 4.
+        ];
+
+        let one       = matrix_norm_core(mat.clone(), 2, MatrixNorm::One).unwrap();
+        let infinity  = matrix_norm_core(mat.clone(), 2, MatrixNorm::Infinity).unwrap();
+        let frobenius = matrix_norm_core(mat, 2, MatrixNorm::Frobenius).unwrap();
+        println!("one: {}, infinity: {}, frobenius: {}", one, infinity, frobenius);
+
+        assert_eq!(one, 6.);
+        assert_eq!(infinity, 7.);
+        assert!((frobenius - 30_f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_positive_definite() {
+        let spd = Matrix::new(3, 3, vec![4., 12., -16., 12., 37., -43., -16., -43., 98.]).unwrap();
+        let lower = spd.cholesky_lower().unwrap();
+        println!("{:?}", lower.row_iter().collect::<Vec<_>>());
+        assert!(spd.is_positive_definite());
+
+        // L L^T = A
+        let product = lower.mul(&lower.transpose()).unwrap();
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!((product[(i, j)] - spd[(i, j)]).abs() < 1e-12);
+            }
+        }
+
+        // semidefinite (zero, singular) and not symmetric
+        assert!(!Matrix::zeros(2, 2).is_positive_definite());
+        assert!(!Matrix::new(2, 2, vec![1., 1., 1., 1.]).unwrap().is_positive_definite());
+        assert!(!Matrix::new(2, 2, vec![2., 1., 0., 2.]).unwrap().is_positive_definite());
+    }
+}
@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod nonlinear {
+    use cal_core::{
+        newton_system_core,
+        JacobianSource,
+        Outcome,
+        StoppingCriteria
+    };
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_newton_system() {
+        // u = x^2 + xy - 10, v = y + 3xy^2 - 57, root at (2, 3)
+        let equations = strings(&["x^2 + x * y - 10", "y + 3 * x * y^2 - 57"]);
+        let variables = strings(&["x", "y"]);
+        let jacobian  = strings(&["2 * x + y", "x", "3 * y^2", "1 + 6 * x * y"]);
+        const INITIAL: [f64; 2] = [1.5, 3.5];
+
+        let supplied = newton_system_core(&equations, &variables, &INITIAL, &jacobian, &StoppingCriteria::default()).unwrap();
+        for r in &supplied.iterations {
+            println!("iter: {}, x: {:?}, residual: {}, error: {}, jacobian: {:?}", 
+                r.iteration, r.x, r.residual, r.error, r.jacobian);
+        }
+        println!("outcome: {:?}, stopped by: {:?}", supplied.outcome, supplied.stopped_by);
+
+        let finite = newton_system_core(&equations, &variables, &INITIAL, &[], &StoppingCriteria::default()).unwrap();
+        assert_eq!(finite.jacobian_source, JacobianSource::FiniteDifference);
+
+        for result in [supplied, finite].iter() {
+            let x = &result.iterations.last().unwrap().x;
+            assert!((x[0] - 2.0).abs() < 1e-9 && (x[1] - 3.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_newton_system_singular() {
+        // J is singular at the origin
+        let equations = strings(&["x^2 + y^2 - 1", "x^2 - y^2"]);
+        let variables = strings(&["x", "y"]);
+
+        let results = newton_system_core(&equations, &variables, &[0., 0.], &[], &StoppingCriteria::default()).unwrap();
+        println!("outcome: {:?}, stopped by: {:?}", results.outcome, results.stopped_by);
+        assert_ne!(results.outcome, Outcome::Converged);
+    }
+
+    #[test]
+    fn test_newton_system_invalid() {
+        let criteria = StoppingCriteria::default();
+
+        if let Err(e) = newton_system_core(&strings(&["x + y"]), &strings(&["x", "y"]), &[1.], &[], &criteria) {
+            println!("{} => {:?}", e, e);
+        }
+        if let Err(e) = newton_system_core(&strings(&["x + z", "x - y"]), &strings(&["x", "y"]), &[1., 2.], &[], &criteria) {
+            println!("{} => {:?}", e, e);
+        }
+        if let Err(e) = newton_system_core(&strings(&["x + y", "x - y"]), &strings(&["x", "y"]), &[1., 2.], &strings(&["1"]), &criteria) {
+            println!("{} => {:?}", e, e);
+        }
+    }
+}
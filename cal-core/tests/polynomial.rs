@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod polynomial {
+    use cal_core::{
+        polynomial_coefficients_core,
+        polynomial_roots_core,
+        bairstow_core,
+        Outcome,
+        StoppingCriteria
+    };
+
+    #[test]
+    fn test_polynomial_coefficients() {
+        let coefficients = polynomial_coefficients_core("(x - 1)^2 * (x + 2)").unwrap();
+        println!("{:?}", coefficients);
+        assert_eq!(coefficients, vec![2.0, -3.0, 0.0, 1.0]);
+
+        let coefficients = polynomial_coefficients_core("pi * x^2 / 2 - x").unwrap();
+        println!("{:?}", coefficients);
+
+        if let Err(e) = polynomial_coefficients_core("sin(x) + x") {
+            println!("{} => {:?}", e, e);
+        }
+        if let Err(e) = polynomial_coefficients_core("x^0.5") {
+            println!("{} => {:?}", e, e);
+        }
+    }
+
+    #[test]
+    fn test_polynomial_roots() {
+        // (x - 1)(x + 2)(x^2 + 1) x
+        const COEFFICIENTS: [f64; 6] = [0., -2., 1., -1., 1., 1.];
+
+        let results = polynomial_roots_core(&COEFFICIENTS, &StoppingCriteria::default()).unwrap();
+        for r in &results.iterations {
+            println!("iter: {}, error: {}", r.iteration, r.error);
+        }
+        for z in &results.roots {
+            println!("{} + {}i", z.re, z.im);
+        }
+        println!("outcome: {:?}, stopped by: {:?}", results.outcome, results.stopped_by);
+
+        let expected = [(-2., 0.), (0., -1.), (0., 0.), (0., 1.), (1., 0.)];
+        assert_eq!(results.roots.len(), expected.len());
+        for (z, (re, im)) in results.roots.iter().zip(expected.iter()) {
+            assert!((z.re - re).abs() < 1e-9 && (z.im - im).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_bairstow() {
+        // x^5 - 3.5x^4 + 2.75x^3 + 2.125x^2 - 3.875x + 1.25, roots -1, 0.5, 2, 1 +- 0.5i
+        const COEFFICIENTS: [f64; 6] = [1.25, -3.875, 2.125, 2.75, -3.5, 1.];
+
+        let results = bairstow_core(&COEFFICIENTS, -1., -1., &StoppingCriteria::default()).unwrap();
+        for r in &results.iterations {
+            println!("factor: {}, iter: {}, r: {}, s: {}, error_r: {}, error_s: {}", 
+                r.factor, r.iteration, r.r, r.s, r.error_r, r.error_s);
+        }
+        for f in &results.factors {
+            println!("x^2 - {} x - {}", f.r, f.s);
+        }
+        for z in &results.roots {
+            println!("{} + {}i", z.re, z.im);
+        }
+        println!("outcome: {:?}, stopped by: {:?}", results.outcome, results.stopped_by);
+
+        let expected = [(-1., 0.), (0.5, 0.), (1., -0.5), (1., 0.5), (2., 0.)];
+        assert_eq!(results.roots.len(), expected.len());
+        // last root is left after deflations, error is accumulated
+        for (z, (re, im)) in results.roots.iter().zip(expected.iter()) {
+            assert!((z.re - re).abs() < 1e-6 && (z.im - im).abs() < 1e-6);
+        }
+        assert_eq!(results.missing_roots, 0);
+
+        // first factor can't converge in one step, nothing is solved
+        let criteria = StoppingCriteria { max_iter: 1, ..StoppingCriteria::default() };
+        let results  = bairstow_core(&COEFFICIENTS, -1., -1., &criteria).unwrap();
+        println!("outcome: {:?}, roots: {}, missing: {}", results.outcome, results.roots.len(), results.missing_roots);
+        assert_eq!(results.outcome, Outcome::MaxIterations);
+        assert!(results.stopped_by.is_none());
+        assert_eq!(results.missing_roots, 5);
+    }
+
+    #[test]
+    fn test_polynomial_invalid() {
+        if let Err(e) = polynomial_roots_core(&[3., 0.], &StoppingCriteria::default()) {
+            println!("{} => {:?}", e, e);
+        }
+        if let Err(e) = bairstow_core(&[1., f64::NAN, 1.], -1., -1., &StoppingCriteria::default()) {
+            println!("{} => {:?}", e, e);
+        }
+    }
+}
@@ -223,6 +223,45 @@ mod regression {
     }
     
 
+    #[test]
+    fn test_regression_qr_reference() {
+        let (x, y) = lsq_sample_i();
+        let n = x.len() as f64;
+
+        // straight line from the closed form
+        let (sx, sy) = (x.iter().sum::<f64>(), y.iter().sum::<f64>());
+        let sxx: f64 = x.iter().map(|a| a * a).sum();
+        let sxy: f64 = x.iter().zip(&y).map(|(a, b)| a * b).sum();
+        let slope     = (n * sxy - sx * sy) / (n * sxx - sx * sx);
+        let intercept = (sy - slope * sx) / n;
+
+        let result = lsq_regression_core(x.clone(), y.clone(), 1).unwrap();
+        println!("a0: {} ~ {}, a1: {} ~ {}", result.answer[0], intercept, result.answer[1], slope);
+        assert!((result.answer[0] - intercept).abs() < 1e-9);
+        assert!((result.answer[1] - slope).abs() < 1e-12);
+
+        // QR answer satisfies the displayed normal equations
+        let result = lsq_regression_core(x.clone(), y.clone(), 2).unwrap();
+        for (row, rhs) in result.matrix.iter().zip(&result.solution) {
+            let lhs: f64 = row.iter().zip(&result.answer).map(|(a, b)| a * b).sum();
+            assert!((lhs - rhs).abs() < 1e-9 * rhs.abs());
+        }
+
+        // more terms than points: the polynomial passes through every point
+        let result = lsq_regression_core(x.clone(), y.clone(), 10).unwrap();
+        for (xi, yi) in x.iter().zip(&y) {
+            let fit: f64 = result.answer.iter().enumerate().map(|(i, a)| a * xi.powi(i as i32)).sum();
+            assert!((fit - yi).abs() < 1e-6);
+        }
+
+        // y is exactly 4 + 2 x1 - 3 x2 - 2 x3
+        let (x, y) = mult_sample_i();
+        let result = mult_lsq_regression_core(x, y, vec![1, 1, 1]).unwrap();
+        for (a, b) in result.answer.iter().zip(&[4., 2., -3., -2.]) {
+            assert!((a - b).abs() < 1e-9);
+        }
+    }
+
     // IMPORTANT: Testing the possibilities how many x are includes
     #[test]
     fn test_combinations() {
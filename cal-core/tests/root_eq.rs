@@ -2,11 +2,28 @@
 mod root_eq {
     use cal_core::{ 
         bisection_core, 
+        brent_core, 
+        ridders_core, 
         false_position_core, 
+        FalsePositionVariant,
         fixed_point_core, 
+        FixedPointMode,
         taylor_core, 
+        taylor_series_core, 
         newton_raphson_core, 
-        secant_core 
+        halley_core, 
+        householder_core, 
+        secant_core,
+        inverse_quadratic_core,
+        muller_core,
+        broyden_core,
+        BroydenUpdate,
+        CalError,
+        scan_roots_core,
+        Outcome,
+        RootKind,
+        StopCriterion,
+        StoppingCriteria
     };
 
     #[test]
@@ -15,38 +32,189 @@ mod root_eq {
         const XL: f64 = 0.02;
         const XR: f64 = 0.03;
 
-        let results = bisection_core(EXPRESSION, XL, XR).unwrap();
-        for r in &results {
+        let results = bisection_core(EXPRESSION, XL, XR, &StoppingCriteria::default()).unwrap();
+        for r in &results.iterations {
             println!("iter: {}, xl: {}, xr: {}, xm: {}, error: {}", 
                 r.iteration, r.xl, r.xr, r.xm, r.error);
         }
     }
 
+    #[test]
+    fn test_bisection_criteria() {
+        const EXPRESSION: &str = "x^2 - 7";
+        const XL: f64 = 2.;
+        const XR: f64 = 3.;
+
+        let criteria = StoppingCriteria {
+            abs_tol: 1e-4,
+            rel_tol: 0.,
+            residual_tol: 1e-3,
+            max_iter: 50
+        };
+
+        let results = bisection_core(EXPRESSION, XL, XR, &criteria).unwrap();
+        for r in &results.iterations {
+            println!("iter: {}, xl: {}, xr: {}, xm: {}, error: {}", 
+                r.iteration, r.xl, r.xr, r.xm, r.error);
+        }
+        println!("outcome: {:?}, stopped by: {:?}", results.outcome, results.stopped_by);
+
+        // |f(xm)| < 1e-3 is reached while the bracket is still wider than 1e-4
+        let last = results.iterations.last().unwrap();
+        assert_eq!(results.outcome, Outcome::Converged);
+        assert_eq!(results.stopped_by, Some(StopCriterion::Residual));
+        assert!((last.xm * last.xm - 7.).abs() < 1e-3);
+        assert!(last.xr - last.xl >= 1e-4);
+    }
+
+    #[test]
+    fn test_newton_raphson_auto_derivative() {
+        const X: f64 = 3.;
+
+        // dual numbers, then central difference fallback for gamma(x) which has no dual rule
+        for (equation, diff) in [("x^2 - 7", None), ("x^2 - 7", Some("")), ("gamma(x) - 7", None)].iter() {
+            let results = newton_raphson_core(equation, *diff, X, &StoppingCriteria::default()).unwrap();
+            for r in &results.iterations {
+                println!("iter: {}, x: {}, slope: {}, error: {}", 
+                    r.iteration, r.x, r.slope, r.error);
+            }
+            println!("{} => {:?}, outcome: {:?}\n", equation, results.derivative_source, results.outcome);
+        }
+
+        let supplied  = newton_raphson_core("x^2 - 7", Some("2 * x"), X, &StoppingCriteria::default()).unwrap();
+        let automatic = newton_raphson_core("x^2 - 7", None, X, &StoppingCriteria::default()).unwrap();
+        for (s, a) in supplied.iterations.iter().zip(automatic.iterations.iter()) {
+            assert!((s.x - a.x).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_bisection_invalid() {
+        let criteria = StoppingCriteria::default();
+
+        // xl > xr
+        match bisection_core("43 * x - 1", 0.03, 0.02, &criteria) {
+            Err(e) => {
+                println!("{} => {:?}", e, e);
+                assert!(matches!(e, CalError::InvalidInterval { .. }));
+            },
+            Ok(_)  => panic!("reversed interval must be reported"),
+        }
+
+        // dangling operator, position of the failing token is reported
+        match bisection_core("43 * x -", 0.02, 0.03, &criteria) {
+            Err(e) => {
+                println!("{} => {:?}", e, e);
+                assert!(matches!(e, CalError::ParseError { position: Some(_), .. }));
+            },
+            Ok(_)  => panic!("parse error must be reported"),
+        }
+    }
+
+    #[test]
+    fn test_bisection_evaluation_failed() {
+        let criteria = StoppingCriteria::default();
+
+        // ln(-1) is NaN, must not be counted as 0
+        match bisection_core("ln(x)", -2.0, 0.5, &criteria) {
+            Err(e) => println!("{} => {:?}", e, e),
+            Ok(_)  => panic!("NaN evaluation must be reported"),
+        }
+
+        match bisection_core("y * x - 1", 0.0, 2.0, &criteria) {
+            Err(e) => println!("{} => {:?}", e, e),
+            Ok(_)  => panic!("unknown variable must be reported"),
+        }
+    }
+
     #[test]
     fn test_false_position() {
         const EXPRESSION: &str = "43 * x - 1";
         const XL: f64 = 0.02;
         const XR: f64 = 0.03;
 
-        let results = false_position_core(EXPRESSION, XL, XR).unwrap();
-        for r in &results {
+        let results = false_position_core(EXPRESSION, XL, XR, FalsePositionVariant::RegulaFalsi, &StoppingCriteria::default()).unwrap();
+        for r in &results.iterations {
             println!("iter: {}, xl: {}, xr: {}, xm: {}, error: {}", 
                 r.iteration, r.xl, r.xr, r.xm, r.error);
         }
     }
 
+    #[test]
+    fn test_false_position_variants() {
+        // convex on the bracket, plain regula falsi keeps xr forever
+        const EXPRESSION: &str = "x^10 - 1";
+        const XL: f64 = 0.;
+        const XR: f64 = 1.3;
+
+        let variants = [
+            FalsePositionVariant::RegulaFalsi,
+            FalsePositionVariant::Illinois,
+            FalsePositionVariant::Pegasus,
+            FalsePositionVariant::AndersonBjorck
+        ];
+
+        let mut counts = Vec::new();
+        for variant in variants.iter() {
+            let results = false_position_core(EXPRESSION, XL, XR, *variant, &StoppingCriteria::default()).unwrap();
+            println!("{:?} => outcome: {:?}, stopped by: {:?}", variant, results.outcome, results.stopped_by);
+            for r in &results.iterations {
+                println!("iter: {}, xl: {}, xr: {}, xm: {}, error: {}, scaled: {:?}, factor: {:?}", 
+                    r.iteration, r.xl, r.xr, r.xm, r.error, r.scaled, r.factor);
+            }
+            counts.push(results.iterations.len());
+        }
+
+        // every modification beats the plain method
+        assert!(counts[1..].iter().all(|&count| count < counts[0]));
+    }
+
     #[test]
     fn test_fixed_point() {
         const EXPRESSION: &str = "2 - exp(x/4)";
         const X: f64 = 0.;
 
-        let results = fixed_point_core(EXPRESSION, X).unwrap();
-        for r in &results {
+        let results = fixed_point_core(EXPRESSION, X, FixedPointMode::Plain, &StoppingCriteria::default()).unwrap();
+        for r in &results.iterations {
             println!("iter: {}, x: {}, error: {}", 
                 r.iteration, r.x, r.error);
         }
     }
 
+    #[test]
+    fn test_fixed_point_acceleration() {
+        // x = e^-x, |g'| ~ 0.57 at the root 0.567143...
+        const EXPRESSION: &str = "exp(-x)";
+        const X: f64 = 0.;
+        const ROOT: f64 = 0.567_143_290_409_783_8;
+
+        let plain = fixed_point_core(EXPRESSION, X, FixedPointMode::Plain, &StoppingCriteria::default()).unwrap();
+        println!("Plain => slope: {}, iterations: {}, outcome: {:?}", plain.slope, plain.iterations.len(), plain.outcome);
+
+        for mode in [FixedPointMode::Aitken, FixedPointMode::Steffensen].iter() {
+            let results = fixed_point_core(EXPRESSION, X, *mode, &StoppingCriteria::default()).unwrap();
+            println!("{:?} => outcome: {:?}, stopped by: {:?}", mode, results.outcome, results.stopped_by);
+            for r in &results.accelerated {
+                println!("iter: {}, x0: {}, x1: {}, x2: {}, x: {}, error: {}", 
+                    r.iteration, r.x0, r.x1, r.x2, r.x, r.error);
+            }
+
+            assert!((results.accelerated.last().unwrap().x - ROOT).abs() < 1e-8);
+            assert!(results.accelerated.len() < plain.iterations.len());
+        }
+    }
+
+    #[test]
+    fn test_fixed_point_divergence_warning() {
+        // x = x^2 - 2 has fixed point 2 with g'(2) = 4
+        let results = fixed_point_core("x^2 - 2", 2.1, FixedPointMode::Plain, &StoppingCriteria::default()).unwrap();
+        println!("slope: {}, warning: {:?}, outcome: {:?}", results.slope, results.warning, results.outcome);
+        assert!(results.warning.is_some());
+
+        let results = fixed_point_core("exp(-x)", 0.5, FixedPointMode::Plain, &StoppingCriteria::default()).unwrap();
+        assert!(results.warning.is_none());
+    }
+
     #[test]
     fn test_taylor() {
         /*
@@ -70,29 +238,331 @@ mod root_eq {
         }
     }
 
+    #[test]
+    fn test_taylor_series() {
+        // e^x at 0 to x = 1, derivatives are computed from the equation
+        const EXPRESSION: &str = "exp(x)";
+        const CENTER: f64 = 0.;
+        const EVAL_X: f64 = 1.;
+
+        let result = taylor_series_core(EXPRESSION, CENTER, 6, EVAL_X).unwrap();
+        println!("coefficients: {:?}", result.coefficients);
+        for t in &result.terms {
+            println!("order: {}, term: {}, sum: {}, true error: {}, relative error: {}",
+                t.order, t.term, t.sum, t.true_error, t.relative_error);
+        }
+        println!("true value: {}, remainder bound: {:?}", result.true_value, result.remainder_bound);
+
+        let last  = result.terms.last().unwrap();
+        let bound = result.remainder_bound.unwrap();
+        assert_eq!(result.coefficients.len(), 7);
+        assert!((result.coefficients[3] - 1. / 6.).abs() < 1e-12);
+        assert!((result.true_value - std::f64::consts::E).abs() < 1e-12);
+        assert!(last.true_error.abs() <= bound);
+        assert!((bound - std::f64::consts::E / 5040.).abs() < 1e-12);
+
+        assert!(taylor_series_core(EXPRESSION, CENTER, 20, EVAL_X).is_err());
+    }
+
     #[test]
     fn test_newton_raphson() {
         const EXPRESSION_BASE: &str = "x^2 - 7";
         const EXPRESSION_DIFF: &str = "2 * x";
         const X: f64 = 2.;
 
-        let results = newton_raphson_core(EXPRESSION_BASE, EXPRESSION_DIFF, X).unwrap();
-        for r in &results {
+        let results = newton_raphson_core(EXPRESSION_BASE, Some(EXPRESSION_DIFF), X, &StoppingCriteria::default()).unwrap();
+        for r in &results.iterations {
             println!("iter: {}, x: {}, error: {}", 
                 r.iteration, r.x, r.error);
         }
     }
 
+    #[test]
+    fn test_newton_raphson_small_scale() {
+        // slope stays around 1e-14, only an exact 0 derivative stops the run
+        const X: f64 = 2.;
+
+        for diff in [Some("2e-14 * x"), None].iter() {
+            let results = newton_raphson_core("1e-14 * (x^2 - 7)", *diff, X, &StoppingCriteria::default()).unwrap();
+            for r in &results.iterations {
+                println!("iter: {}, x: {}, slope: {}, error: {}",
+                    r.iteration, r.x, r.slope, r.error);
+            }
+            assert_eq!(results.outcome, Outcome::Converged);
+            assert!((results.iterations.last().unwrap().x - 7f64.sqrt()).abs() < 1e-9);
+        }
+
+        let results = newton_raphson_core("x^2 - 7", Some("0 * x"), X, &StoppingCriteria::default()).unwrap();
+        assert_eq!(results.outcome, Outcome::DerivativeZero);
+    }
+
+    #[test]
+    fn test_newton_raphson_diverged() {
+        // x_{n+1} = -2 x_n on cube root
+        const EXPRESSION_BASE: &str = "signum(x) * abs(x)^(1/3)";
+        const EXPRESSION_DIFF: &str = "abs(x)^(-2/3) / 3";
+        const X: f64 = 0.1;
+
+        let results = newton_raphson_core(EXPRESSION_BASE, Some(EXPRESSION_DIFF), X, &StoppingCriteria::default()).unwrap();
+        for r in &results.iterations {
+            println!("iter: {}, x: {}, error: {}", 
+                r.iteration, r.x, r.error);
+        }
+        println!("outcome: {:?}, stopped by: {:?}", results.outcome, results.stopped_by);
+
+        assert_eq!(results.outcome, Outcome::Diverged);
+        assert_eq!(results.stopped_by, None);
+    }
+
     #[test]
     fn test_secant() {
         const EXPRESSION: &str = "43 * x - 1";
         const X0: f64 = 0.02;
         const X1: f64 = 0.03;
 
-        let results = secant_core(EXPRESSION, X0, X1).unwrap();
-        for r in &results {
+        let results = secant_core(EXPRESSION, X0, X1, &StoppingCriteria::default()).unwrap();
+        for r in &results.iterations {
             println!("iter: {}, x0: {}, x1: {}, error: {}", 
                 r.iteration, r.x0, r.x1, r.error);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_inverse_quadratic() {
+        const EXPRESSION: &str = "x^3 - 13 * x - 12";
+
+        let results = inverse_quadratic_core(EXPRESSION, 4.5, 5.5, 5., &StoppingCriteria::default()).unwrap();
+        for r in &results.iterations {
+            println!("iter: {}, x0: {}, x1: {}, x2: {}, error: {}", 
+                r.iteration, r.x0, r.x1, r.x2, r.error);
+        }
+        println!("outcome: {:?}, stopped by: {:?}", results.outcome, results.stopped_by);
+
+        let root = results.iterations.last().unwrap().x2;
+        assert!((root - 4.).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_muller() {
+        // Chapra example 7.2
+        const EXPRESSION: &str = "x^3 - 13 * x - 12";
+
+        let results = muller_core(EXPRESSION, 4.5, 5.5, 5., &StoppingCriteria::default()).unwrap();
+        for r in &results.iterations {
+            println!("iter: {}, x0: {}, x1: {}, x2: {} + {}i, error: {}", 
+                r.iteration, r.x0, r.x1, r.x2, r.x2_im, r.error);
+        }
+        println!("outcome: {:?}, stopped by: {:?}", results.outcome, results.stopped_by);
+
+        let last = results.iterations.last().unwrap();
+        assert!((last.x2 - 4.).abs() < 1e-6);
+        assert!(last.x2_im.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_muller_complex_root() {
+        // no real root, real starting points move off the axis
+        const EXPRESSION: &str = "x^2 + 1";
+
+        let results = muller_core(EXPRESSION, -1., 0.5, 1., &StoppingCriteria::default()).unwrap();
+        for r in &results.iterations {
+            println!("iter: {}, x2: {} + {}i, error: {}", r.iteration, r.x2, r.x2_im, r.error);
+        }
+        println!("outcome: {:?}, stopped by: {:?}", results.outcome, results.stopped_by);
+
+        let last = results.iterations.last().unwrap();
+        assert!(last.x2.abs() < 1e-9);
+        assert!((last.x2_im.abs() - 1.).abs() < 1e-9);
+
+        assert!(muller_core(EXPRESSION, 1., 1., 2., &StoppingCriteria::default()).is_err());
+    }
+
+    #[test]
+    fn test_brent() {
+        const EXPRESSION: &str = "x^3 - 2 * x - 5";
+        const XL: f64 = 2.;
+        const XR: f64 = 3.;
+
+        let results = brent_core(EXPRESSION, XL, XR, &StoppingCriteria::default()).unwrap();
+        for r in &results.iterations {
+            println!("iter: {}, xl: {}, xr: {}, x: {}, step: {:?}, error: {}", 
+                r.iteration, r.xl, r.xr, r.x, r.step, r.error);
+        }
+        println!("outcome: {:?}, stopped by: {:?}", results.outcome, results.stopped_by);
+
+        let root = results.iterations.last().unwrap().x;
+        assert!((root - 2.094_551_481_542_327).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_ridders() {
+        const EXPRESSION: &str = "x^2 - 7";
+        const XL: f64 = 2.;
+        const XR: f64 = 3.;
+
+        let results = ridders_core(EXPRESSION, XL, XR, &StoppingCriteria::default()).unwrap();
+        for r in &results.iterations {
+            println!("iter: {}, xl: {}, xr: {}, xm: {}, x: {}, error: {}", 
+                r.iteration, r.xl, r.xr, r.xm, r.x, r.error);
+        }
+        println!("outcome: {:?}, stopped by: {:?}", results.outcome, results.stopped_by);
+
+        let root = results.iterations.last().unwrap().x;
+        assert!((root - 7f64.sqrt()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_bracket_endpoint_root() {
+        let criteria = StoppingCriteria::default();
+
+        let brent   = brent_core("x - 1", 1.0, 2.0, &criteria).unwrap();
+        let ridders = ridders_core("x - 1", 0.0, 1.0, &criteria).unwrap();
+        println!("brent: {:?} {:?}, ridders: {:?} {:?}", brent.outcome, brent.stopped_by, ridders.outcome, ridders.stopped_by);
+        for r in &ridders.iterations {
+            println!("iter: {}, x: {}", r.iteration, r.x);
+        }
+
+        // f(endpoint) == 0 is accepted as the root without iterating further
+        assert_eq!(brent.outcome, Outcome::Converged);
+        assert_eq!(brent.stopped_by, Some(StopCriterion::Residual));
+        assert_eq!(brent.iterations.last().unwrap().x, 1.0);
+        assert_eq!(ridders.outcome, Outcome::Converged);
+        assert_eq!(ridders.stopped_by, Some(StopCriterion::Residual));
+        assert_eq!(ridders.iterations.last().unwrap().x, 1.0);
+    }
+
+    #[test]
+    fn test_bracket_no_sign_change() {
+        let criteria = StoppingCriteria::default();
+
+        match brent_core("x^2 + 1", -1.0, 1.0, &criteria) {
+            Err(e) => println!("{} => {:?}", e, e),
+            Ok(_)  => panic!("bracket without sign change must be reported"),
+        }
+
+        match ridders_core("x^2 + 1", -1.0, 1.0, &criteria) {
+            Err(e) => println!("{} => {:?}", e, e),
+            Ok(_)  => panic!("bracket without sign change must be reported"),
+        }
+    }
+
+    #[test]
+    fn test_halley() {
+        const EXPRESSION_BASE: &str = "x^2 - 7";
+        const EXPRESSION_DIFF: &str = "2 * x";
+        const EXPRESSION_DIFF2: &str = "2";
+        const X: f64 = 2.;
+
+        let supplied  = halley_core(EXPRESSION_BASE, Some(EXPRESSION_DIFF), Some(EXPRESSION_DIFF2), X, &StoppingCriteria::default()).unwrap();
+        let symbolic  = halley_core(EXPRESSION_BASE, None, None, X, &StoppingCriteria::default()).unwrap();
+        for r in &supplied.iterations {
+            println!("iter: {}, x: {}, fx: {}, error: {}, order: {:?}", 
+                r.iteration, r.x, r.fx, r.error, r.convergence_order);
+        }
+        println!("source: {:?} / {:?}, outcome: {:?}", supplied.derivative_source, symbolic.derivative_source, supplied.outcome);
+
+        for (s, a) in supplied.iterations.iter().zip(symbolic.iterations.iter()) {
+            assert!((s.x - a.x).abs() < 1e-12);
+        }
+        assert!((supplied.iterations.last().unwrap().x - 7f64.sqrt()).abs() < 1e-12);
+
+        if let Err(e) = halley_core(EXPRESSION_BASE, Some(EXPRESSION_DIFF), None, X, &StoppingCriteria::default()) {
+            println!("{} => {:?}", e, e);
+        }
+    }
+
+    #[test]
+    fn test_householder_order() {
+        // slow start from far away so the order estimate has enough steps
+        const EXPRESSION: &str = "exp(x) - 2";
+        const X: f64 = 4.;
+
+        for order in 1..=4 {
+            let results = householder_core(EXPRESSION, &[], order, X, &StoppingCriteria::default()).unwrap();
+            println!("order: {}, outcome: {:?}, stopped by: {:?}", order, results.outcome, results.stopped_by);
+            for r in &results.iterations {
+                println!("iter: {}, x: {}, error: {}, order: {:?}", 
+                    r.iteration, r.x, r.error, r.convergence_order);
+            }
+            assert!((results.iterations.last().unwrap().x - 2f64.ln()).abs() < 1e-12);
+        }
+
+        if let Err(e) = householder_core(EXPRESSION, &["exp(x)".to_string()], 2, X, &StoppingCriteria::default()) {
+            println!("{} => {:?}", e, e);
+        }
+    }
+
+    #[test]
+    fn test_scan_roots() {
+        // simple roots at -1, 3 and double root at 1
+        const EXPRESSION: &str = "(x + 1) * (x - 1)^2 * (x - 3)";
+        const A: f64 = -2.;
+        const B: f64 = 4.;
+
+        let results = scan_roots_core(EXPRESSION, A, B, 45, &StoppingCriteria::default()).unwrap();
+        for r in &results.roots {
+            println!("x: {}, fx: {}, [{}, {}], kind: {:?}, iterations: {}, outcome: {:?}", 
+                r.x, r.fx, r.xl, r.xr, r.kind, r.iterations, r.outcome);
+        }
+        println!("warning: {:?}", results.warning);
+
+        let expected = [(-1., RootKind::SignChange), (1., RootKind::Touching), (3., RootKind::SignChange)];
+        assert_eq!(results.roots.len(), expected.len());
+        for (r, (x, kind)) in results.roots.iter().zip(expected.iter()) {
+            assert!((r.x - x).abs() < 1e-6);
+            assert_eq!(r.kind, *kind);
+        }
+        assert!(results.warning.is_some());
+    }
+
+    #[test]
+    fn test_scan_roots_pole() {
+        // sign change across pi/2 is a pole, not a root
+        let results = scan_roots_core("tan(x)", -1.0, 2.0, 30, &StoppingCriteria::default()).unwrap();
+        for r in &results.roots {
+            println!("x: {}, fx: {}, kind: {:?}", r.x, r.fx, r.kind);
+        }
+        assert_eq!(results.roots.len(), 1);
+
+        // ln(x) undefined on the left half, sample at 1 is exact
+        let results = scan_roots_core("ln(x)", -1.0, 3.0, 8, &StoppingCriteria::default()).unwrap();
+        for r in &results.roots {
+            println!("x: {}, fx: {}, kind: {:?}", r.x, r.fx, r.kind);
+        }
+        println!("warning: {:?}", results.warning);
+        assert_eq!(results.roots.len(), 1);
+
+        if let Err(e) = scan_roots_core("y * x", -1.0, 3.0, 8, &StoppingCriteria::default()) {
+            println!("{} => {:?}", e, e);
+        }
+
+        // undefined on (-0.1, 0.1) inside [-0.5, 0.5], the other brackets are still scanned
+        let results = scan_roots_core("signum(x) * sqrt(abs(x) - 0.1) * (x - 1.2)", -1.5, 1.5, 3, &StoppingCriteria::default()).unwrap();
+        for r in &results.roots {
+            println!("x: {}, fx: {}, [{}, {}], kind: {:?}, outcome: {:?}", r.x, r.fx, r.xl, r.xr, r.kind, r.outcome);
+        }
+        assert_eq!(results.roots.len(), 2);
+        assert_eq!(results.roots[0].outcome, Outcome::NonFinite);
+        assert!((results.roots[1].x - 1.2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_broyden() {
+        // u = x^2 + xy - 10, v = y + 3xy^2 - 57, root at (2, 3)
+        let equations: Vec<String> = vec!["x^2 + x * y - 10".to_string(), "y + 3 * x * y^2 - 57".to_string()];
+        let variables: Vec<String> = vec!["x".to_string(), "y".to_string()];
+        const INITIAL: [f64; 2] = [1.5, 3.5];
+
+        for update in [BroydenUpdate::Good, BroydenUpdate::Bad].iter() {
+            let results = broyden_core(&equations, &variables, &INITIAL, *update, &StoppingCriteria::default()).unwrap();
+            println!("{:?} => outcome: {:?}, stopped by: {:?}", update, results.outcome, results.stopped_by);
+            for r in &results.iterations {
+                println!("iter: {}, x: {:?}, residual: {}, error: {}, jacobian: {:?}", 
+                    r.iteration, r.x, r.residual, r.error, r.jacobian);
+            }
+
+            let x = &results.iterations.last().unwrap().x;
+            assert!((x[0] - 2.0).abs() < 1e-9 && (x[1] - 3.0).abs() < 1e-9);
+        }
+    }
+}
@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod symbolic {
+    use cal_core::{evaluate_expression_core, symbolic_derivative_core, symbolic_derivatives_core};
+
+    fn eval(equation: &str, x: f64) -> f64 {
+        evaluate_expression_core(equation, &["x".to_string()], &[x]).unwrap()
+    }
+
+    #[test]
+    fn test_symbolic_derivatives() {
+        let derivatives = symbolic_derivatives_core("ln(x)", "x", 4).unwrap();
+        for (i, d) in derivatives.iter().enumerate() {
+            println!("f^({}) = {}", i, d);
+        }
+
+        assert_eq!(symbolic_derivative_core("x^3 + sin(x)", "x", 1).unwrap(), "3 * x^2 + cos(x)");
+        assert_eq!(symbolic_derivative_core("x^3 + sin(x)", "x", 2).unwrap(), "6 * x - sin(x)");
+        assert_eq!(symbolic_derivative_core("43 * x - 1", "x", 1).unwrap(), "43");
+        assert_eq!(symbolic_derivative_core("y * x^2", "y", 1).unwrap(), "x^2");
+    }
+
+    #[test]
+    fn test_symbolic_numeric() {
+        // symbolic derivative is compared with central difference
+        const X: f64 = 0.7;
+        const H: f64 = 1e-5;
+
+        let equations = [
+            "x^x",
+            "exp(2 * x) * cos(x) / (1 + x^2)",
+            "-x^2 + 2^x - tan(x)",
+            "log(2, x) + log10(x) * sqrt(x)",
+            "atan2(x, 1 - x) + hypot(x, 2)",
+            "asin(x) + acosh(1 + x) + sech(x)^3",
+            "erf(x) * j0(x) - y1(x)",
+            "cbrt(x^2 + 1) % 5 - abs(x - 1)",
+        ];
+
+        for equation in equations.iter() {
+            let derivative = symbolic_derivative_core(equation, "x", 1).unwrap();
+            let symbolic   = eval(&derivative, X);
+            let numeric    = (eval(equation, X + H) - eval(equation, X - H)) / (2.0 * H);
+
+            println!("d/dx {} = {}\n  {} ~ {}", equation, derivative, symbolic, numeric);
+            assert!((symbolic - numeric).abs() < 1e-6 * numeric.abs().max(1.0));
+        }
+    }
+
+    #[test]
+    fn test_symbolic_power_of_power() {
+        // (x^2)^0.5 = |x|, the derivative is x / |x| on both sides of 0
+        let derivative = symbolic_derivative_core("(x^2)^0.5", "x", 1).unwrap();
+        println!("d/dx (x^2)^0.5 = {}", derivative);
+
+        for &(x, expected) in [(-2.0, -1.0), (3.0, 1.0)].iter() {
+            assert!((eval(&derivative, x) - expected).abs() < 1e-12);
+        }
+
+        // integer outer exponent is still folded
+        assert_eq!(symbolic_derivative_core("(x^2)^3", "x", 1).unwrap(), "6 * x^5");
+    }
+
+    #[test]
+    fn test_symbolic_unsupported() {
+        for equation in ["gamma(x)", "max(x, 1)", "2 % x"].iter() {
+            match symbolic_derivative_core(equation, "x", 1) {
+                Ok(d)  => panic!("{} => {} must be rejected", equation, d),
+                Err(e) => println!("{} : {}", equation, e),
+            }
+        }
+    }
+}